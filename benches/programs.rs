@@ -0,0 +1,76 @@
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+#[path = "../tests/programs/mod.rs"]
+mod programs;
+
+use programs::FIXTURES;
+
+fn lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lex");
+
+    for fixture in FIXTURES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(fixture.name),
+            fixture.src,
+            |b, src| b.iter(|| lex(black_box(src.to_string())).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+fn optimizer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("optimize");
+    let levels = [OptLevel::Basic, OptLevel::Aggressive];
+
+    for fixture in FIXTURES {
+        let block = lex_unoptimized(fixture.src).unwrap();
+
+        for level in levels {
+            group.bench_with_input(
+                BenchmarkId::new(fixture.name, format!("{:?}", level)),
+                &block,
+                |b, block| b.iter(|| Optimizer::new(level).optimize(black_box(block))),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn interpreter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interpret");
+    let levels = [OptLevel::None, OptLevel::Basic, OptLevel::Aggressive];
+
+    for fixture in FIXTURES {
+        for level in levels {
+            let block = Optimizer::new(level).optimize(&lex_unoptimized(fixture.src).unwrap());
+
+            // Make sure the benchmark measures a correct run of the program
+            let mut out = Vec::new();
+            interpret(&block, &mut &fixture.input[..], &mut out).unwrap();
+            assert_eq!(String::from_utf8_lossy(&out), fixture.output);
+
+            group.bench_with_input(
+                BenchmarkId::new(fixture.name, format!("{:?}", level)),
+                &block,
+                |b, block| {
+                    b.iter(|| {
+                        let mut out = Vec::new();
+                        interpret(black_box(block), &mut &fixture.input[..], &mut out).unwrap();
+                        out
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, lexer, optimizer, interpreter);
+criterion_main!(benches);
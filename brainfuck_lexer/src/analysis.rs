@@ -0,0 +1,157 @@
+//! Static analysis of lexed Brainfuck code.
+//!
+//! Analyzing a program tells what it may do without running it, so programs
+//! from untrusted sources can be checked before they are run, and given a
+//! tape large enough for them.
+
+#[cfg(feature = "precompiled_patterns")]
+use crate::lexer::PreCompiledPattern;
+use crate::lexer::{Block, Token};
+
+/// What is known about a program without running it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramInfo {
+    /// Whether the program can read input.
+    pub reads_input: bool,
+    /// Deepest nesting of loops, which is zero for programs without loops.
+    pub max_depth: usize,
+    /// Leftmost and rightmost cells the program can use, relative to the
+    /// cell it starts on.
+    ///
+    /// This is only known when the body of every loop leaves the pointer
+    /// where it started, as the pointer can otherwise move any distance.
+    pub memory_bounds: Option<(isize, isize)>,
+}
+
+/// Analyze a program.
+///
+/// The analysis is conservative: the program can read input if there is any
+/// input token in it, even one which is never reached.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to analyze.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::analysis::analyze;
+/// use brainfuck_lexer::lex_unoptimized;
+///
+/// let info = analyze(&lex_unoptimized("<<[->>>+<<<]>>>.").unwrap());
+/// assert!(!info.reads_input);
+/// assert_eq!(info.max_depth, 1);
+/// assert_eq!(info.memory_bounds, Some((-2, 1)));
+///
+/// // The pointer moves an unknown distance in the loop
+/// let info = analyze(&lex_unoptimized(",[>,]").unwrap());
+/// assert!(info.reads_input);
+/// assert_eq!(info.memory_bounds, None);
+/// ```
+pub fn analyze(src: &Block) -> ProgramInfo {
+    let mut info = ProgramInfo::default();
+    let mut bounds = (0, 0);
+    let known = walk(src, 0, Some(0), &mut info, &mut bounds).is_some();
+
+    info.memory_bounds = known.then_some(bounds);
+    info
+}
+
+/// Walk a block nested at some depth, starting with the pointer at a cell
+/// relative to the first cell, if known.
+///
+/// Returns where the pointer ends, if known. Once the pointer is unknown it
+/// stays unknown, so the bounds are only correct if the pointer is known at
+/// the end.
+fn walk(
+    block: &Block,
+    depth: usize,
+    start: Option<isize>,
+    info: &mut ProgramInfo,
+    bounds: &mut (isize, isize),
+) -> Option<isize> {
+    info.max_depth = info.max_depth.max(depth);
+
+    let mut ptr = start;
+    for token in block {
+        match token {
+            Token::Next(count) => ptr = ptr.map(|ptr| ptr + *count as isize),
+            Token::Prev(count) => ptr = ptr.map(|ptr| ptr - *count as isize),
+            Token::Input => info.reads_input = true,
+            Token::Closure(inner) => {
+                // The body must leave the pointer where it started to use the
+                // same cells on every iteration
+                let end = walk(inner, depth + 1, ptr, info, bounds);
+                if end != ptr {
+                    ptr = None;
+                }
+            }
+            #[cfg(feature = "precompiled_patterns")]
+            Token::Pattern(pattern) => {
+                let offset = match *pattern {
+                    PreCompiledPattern::SetToZero => 0,
+                    PreCompiledPattern::Multiply { dest_offset, .. } => dest_offset,
+                    PreCompiledPattern::AddAt { offset, .. } => offset,
+                    PreCompiledPattern::SetAt { offset, .. } => offset,
+                };
+                touch(bounds, ptr.map(|ptr| ptr + offset));
+            }
+            _ => {}
+        }
+
+        touch(bounds, ptr);
+    }
+
+    ptr
+}
+
+/// Widen the bounds to include a cell, if known.
+fn touch(bounds: &mut (isize, isize), cell: Option<isize>) {
+    if let Some(cell) = cell {
+        bounds.0 = bounds.0.min(cell);
+        bounds.1 = bounds.1.max(cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{lex, lex_unoptimized};
+
+    #[test]
+    fn input() {
+        assert!(!analyze(&lex("+[->+<]>.").unwrap()).reads_input);
+        assert!(analyze(&lex("+[-[-[,]]]").unwrap()).reads_input);
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(analyze(&vec![]).max_depth, 0);
+        assert_eq!(
+            analyze(&lex_unoptimized("[][[]][[[-]]]").unwrap()).max_depth,
+            3
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let info = analyze(&lex_unoptimized(">>>[<<<<<+>>>>>-]<").unwrap());
+        assert_eq!(info.memory_bounds, Some((-2, 3)));
+
+        // Unbalanced loops anywhere make the bounds unknown
+        let info = analyze(&lex_unoptimized("+[>[>]<-]").unwrap());
+        assert_eq!(info.memory_bounds, None);
+        let info = analyze(&lex_unoptimized("[<]>>").unwrap());
+        assert_eq!(info.memory_bounds, None);
+    }
+
+    #[test]
+    fn optimized_bounds() {
+        // Patterns use cells the pointer never moves to
+        for src in ["[->>>+<<<]", "<<[-]+++>>", ">[-]"] {
+            let unoptimized = analyze(&lex_unoptimized(src).unwrap()).memory_bounds;
+            let optimized = analyze(&lex(src).unwrap()).memory_bounds;
+            assert_eq!(optimized, unoptimized, "{}", src);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Binary serialization of blocks.
+//!
+//! The encoding starts with a header identifying the format and the features
+//! the crate was built with, as the available [`Token`]s depend on them.
+//! Blocks encoded by an incompatible build are rejected when decoding.
+
+use crate::lexer::Block;
+#[cfg(doc)]
+use crate::lexer::Token;
+use alloc::vec::Vec;
+
+const MAGIC: &[u8; 4] = b"BFBK";
+const FORMAT_VERSION: u8 = 1;
+
+/// The features changing the layout of [`Token`].
+fn features() -> u8 {
+    let mut features = 0;
+    if cfg!(feature = "debug_token") {
+        features |= 1;
+    }
+    if cfg!(feature = "precompiled_patterns") {
+        features |= 2;
+    }
+    if cfg!(feature = "breakpoint_token") {
+        features |= 4;
+    }
+    features
+}
+
+fn header() -> [u8; 6] {
+    let [a, b, c, d] = *MAGIC;
+    [a, b, c, d, FORMAT_VERSION, features()]
+}
+
+/// Encode a block to bytes.
+///
+/// # Arguments
+///
+/// * `block` - The [`Block`] to encode.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::binary::{decode, encode};
+/// use brainfuck_lexer::lex;
+///
+/// let code = lex("++[->+<]").unwrap();
+/// let bytes = encode(&code);
+///
+/// assert_eq!(decode(&bytes), Some(code));
+/// ```
+pub fn encode(block: &Block) -> Vec<u8> {
+    let mut bytes = header().to_vec();
+    bytes.extend(
+        bincode::serde::encode_to_vec(block, bincode::config::standard())
+            .expect("encoding to a vector cannot fail"),
+    );
+    bytes
+}
+
+/// Decode a block encoded by [`encode`].
+///
+/// Returns `None` if the bytes are not a valid encoding, or were encoded by
+/// a build of the crate with different features.
+///
+/// # Arguments
+///
+/// * `bytes` - The encoded block.
+pub fn decode(bytes: &[u8]) -> Option<Block> {
+    let body = bytes.strip_prefix(&header())?;
+    let (block, len) = bincode::serde::decode_from_slice(body, bincode::config::standard()).ok()?;
+
+    (len == body.len()).then_some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{lex, lex_with_comments};
+
+    #[test]
+    fn round_trip() {
+        let code = lex("++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.");
+        let code = code.unwrap();
+        assert_eq!(decode(&encode(&code)), Some(code));
+
+        let code = lex_with_comments("Comment +[-] more").unwrap();
+        assert_eq!(decode(&encode(&code)), Some(code));
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = encode(&lex("+[-]>.").unwrap());
+
+        assert_eq!(decode(&[]), None);
+        assert_eq!(decode(&bytes[1..]), None);
+        assert_eq!(decode(&bytes[..bytes.len() - 1]), None);
+
+        let mut other_version = bytes.clone();
+        other_version[4] += 1;
+        assert_eq!(decode(&other_version), None);
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(decode(&trailing), None);
+    }
+}
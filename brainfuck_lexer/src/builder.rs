@@ -0,0 +1,239 @@
+//! Construction of Brainfuck programs without source code.
+
+use crate::error::{LexerError, Result};
+use crate::lexer::{is_command, lex_unoptimized, to_source, Block, Token};
+use alloc::string::String;
+
+/// Builder for a Brainfuck [`Block`].
+///
+/// Commands with a count of zero have no effect and are left out.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::builder::Program;
+///
+/// let program = Program::new()
+///     .inc(5)
+///     .loop_(|body| body.dec(1).right(1).inc(8).left(1))
+///     .right(1)
+///     .print();
+///
+/// assert_eq!(program.to_source().unwrap(), "+++++[->++++++++<]>.");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Program {
+    block: Block,
+}
+
+impl Program {
+    /// Create an empty program.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Increment the current cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The value to add to the cell.
+    pub fn inc(self, count: u8) -> Self {
+        self.push_if(count > 0, Token::Increment(count))
+    }
+
+    /// Decrement the current cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The value to subtract from the cell.
+    pub fn dec(self, count: u8) -> Self {
+        self.push_if(count > 0, Token::Decrement(count))
+    }
+
+    /// Move the pointer to the right.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of cells to move.
+    pub fn right(self, count: usize) -> Self {
+        self.push_if(count > 0, Token::Next(count))
+    }
+
+    /// Move the pointer to the left.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of cells to move.
+    pub fn left(self, count: usize) -> Self {
+        self.push_if(count > 0, Token::Prev(count))
+    }
+
+    /// Print the current cell.
+    pub fn print(self) -> Self {
+        self.push_if(true, Token::Print)
+    }
+
+    /// Read a byte of input into the current cell.
+    pub fn input(self) -> Self {
+        self.push_if(true, Token::Input)
+    }
+
+    /// Repeat a body while the current cell is not zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - Function building the body of the loop from an empty
+    ///   program.
+    pub fn loop_<F>(self, body: F) -> Self
+    where
+        F: FnOnce(Program) -> Program,
+    {
+        let body = body(Program::new());
+        self.push_if(true, Token::Closure(body.block))
+    }
+
+    /// Add a comment.
+    ///
+    /// The comment must not contain any commands, which is checked when the
+    /// program is built.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the comment.
+    pub fn comment<S>(self, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let text = text.into();
+        self.push_if(!text.is_empty(), Token::Comment(text))
+    }
+
+    /// Finish the program.
+    ///
+    /// # Errors
+    ///
+    /// If a comment contains a command, a [`LexerError::SyntaxError`] with the
+    /// command will be returned, as the comment would change the program once
+    /// converted to source.
+    pub fn build(self) -> Result<Block> {
+        validate(&self.block)?;
+        Ok(self.block)
+    }
+
+    /// Convert the program to Brainfuck source.
+    ///
+    /// # Errors
+    ///
+    /// See [`Program::build`].
+    pub fn to_source(&self) -> Result<String> {
+        validate(&self.block)?;
+        Ok(to_source(&self.block))
+    }
+
+    fn push_if(mut self, condition: bool, token: Token) -> Self {
+        if condition {
+            self.block.push(token);
+        }
+        self
+    }
+}
+
+/// Parse Brainfuck source to a program, which can then be extended.
+///
+/// The program is exactly as written, without its comments.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::builder::Program;
+///
+/// let program: Program = "+++".parse().unwrap();
+///
+/// assert_eq!(program.print().to_source().unwrap(), "+++.");
+/// ```
+impl core::str::FromStr for Program {
+    type Err = LexerError;
+
+    fn from_str(src: &str) -> Result<Self> {
+        Ok(Self {
+            block: lex_unoptimized(src)?,
+        })
+    }
+}
+
+impl TryFrom<&str> for Program {
+    type Error = LexerError;
+
+    fn try_from(src: &str) -> Result<Self> {
+        src.parse()
+    }
+}
+
+impl TryFrom<String> for Program {
+    type Error = LexerError;
+
+    fn try_from(src: String) -> Result<Self> {
+        src.parse()
+    }
+}
+
+fn validate(block: &Block) -> Result<()> {
+    for token in block {
+        match token {
+            Token::Closure(inner) => validate(inner)?,
+            Token::Comment(text) => {
+                if let Some(ch) = text.chars().find(|&ch| is_command(ch)) {
+                    return Err(LexerError::SyntaxError(ch));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{lex_unoptimized, lex_with_comments};
+
+    #[test]
+    fn build() {
+        let program = Program::new()
+            .inc(8)
+            .loop_(|body| body.dec(1).right(1).inc(4).loop_(|body| body.dec(1)))
+            .left(2)
+            .input()
+            .print();
+
+        let expected = lex_unoptimized("++++++++[->++++[-]]<<,.");
+        assert_eq!(program.build(), expected);
+    }
+
+    #[test]
+    fn zero_counts() {
+        let program = Program::new().inc(0).dec(0).right(0).left(0).comment("");
+        assert_eq!(program.build(), Ok(vec![]));
+    }
+
+    #[test]
+    fn comments() {
+        let program = Program::new().comment("Print ").inc(1).print();
+        let src = program.to_source();
+        assert_eq!(src, Ok("Print +.".to_string()));
+        assert_eq!(lex_with_comments(src.unwrap()), program.build());
+
+        let program = Program::new().loop_(|body| body.comment("Not a comment."));
+        assert_eq!(program.build(), Err(LexerError::SyntaxError('.')));
+    }
+
+    #[test]
+    fn parse() {
+        let program: Program = "+[->+<]".parse().unwrap();
+        assert_eq!(program.clone().build(), lex_unoptimized("+[->+<]"));
+        assert_eq!(Program::try_from("+[->+<]".to_string()), Ok(program));
+
+        assert_eq!(Program::try_from("+["), Err(LexerError::UnclosedBlock));
+    }
+}
@@ -1,5 +1,7 @@
 //! Errors used in the crate.
 
+use alloc::string::String;
+
 /// The error type of any lexical analysis.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LexerError {
@@ -9,7 +11,67 @@ pub enum LexerError {
     UnclosedBlock,
     /// Syntax error.
     SyntaxError(char),
+    /// Closures nested deeper than the maximum depth.
+    NestingTooDeep(usize),
+}
+
+/// The error type of preprocessing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreprocessorError {
+    /// Included file which could not be read.
+    Include(String),
+    /// Directive with missing or malformed arguments.
+    InvalidDirective(String),
+    /// Use of a macro which has not been defined.
+    UndefinedMacro(String),
+    /// Includes or macros nested too deeply, usually due to recursion.
+    RecursionLimit,
+}
+
+/// The error type of generating programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerateError {
+    /// Character which cannot be printed from a single memory location.
+    Unprintable(char),
 }
 
 /// Specialized [`Result`] type for lexical analysis.
-pub type Result<T> = std::result::Result<T, LexerError>;
+pub type Result<T> = core::result::Result<T, LexerError>;
+
+impl core::fmt::Display for LexerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnexpectedEOF => write!(f, "source ended unexpectedly"),
+            Self::UnclosedBlock => write!(f, "closure with no closing bracket"),
+            Self::SyntaxError(ch) => write!(f, "syntax error at '{}'", ch),
+            Self::NestingTooDeep(max) => {
+                write!(f, "closures nested deeper than the limit of {}", max)
+            }
+        }
+    }
+}
+
+impl core::error::Error for LexerError {}
+
+impl core::fmt::Display for PreprocessorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Include(path) => write!(f, "cannot include \"{}\"", path),
+            Self::InvalidDirective(name) => write!(f, "invalid @{} directive", name),
+            Self::UndefinedMacro(name) => write!(f, "undefined macro @{}", name),
+            Self::RecursionLimit => write!(f, "includes or macros nested too deeply"),
+        }
+    }
+}
+
+impl core::error::Error for PreprocessorError {}
+
+impl core::fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unprintable(ch) => write!(f, "cannot print '{}' from a single cell", ch),
+        }
+    }
+}
+
+impl core::error::Error for GenerateError {}
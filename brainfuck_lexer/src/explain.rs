@@ -0,0 +1,330 @@
+//! Plain English explanations of Brainfuck programs.
+//!
+//! Every loop of a program is described by what it does to the memory, such
+//! as setting a cell to zero or multiplying it into other cells. Cells are
+//! numbered relative to the cell the pointer is on when the loop starts,
+//! which is always cell 0.
+
+use crate::error::Result;
+use crate::ir::{lower, Op};
+#[cfg(feature = "precompiled_patterns")]
+use crate::lexer::PreCompiledPattern;
+use crate::lexer::{lex_with_spans, Block, Span, Token};
+#[cfg(feature = "precompiled_patterns")]
+use crate::optimizer::{OptLevel, Optimizer};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+#[cfg(feature = "precompiled_patterns")]
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Description of a single loop of a program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Where the loop is in the source, from its opening to its closing
+    /// bracket.
+    pub span: Span,
+    /// Number of loops the loop is nested in.
+    pub depth: usize,
+    /// What the loop does.
+    pub text: String,
+}
+
+/// Explain every loop of a program.
+///
+/// The explanations are in the order the loops start in the source, so a
+/// loop comes before the loops nested in it.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to explain.
+///
+/// # Errors
+///
+/// If the given source cannot be lexed, a
+/// [`LexerError`](crate::error::LexerError) will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::explain::explain;
+///
+/// let explanations = explain("++++++++[>++++++++<-]>+.").unwrap();
+///
+/// assert_eq!(explanations[0].text, "multiplies cell 0 by 8 into cell 1");
+/// ```
+pub fn explain(src: &str) -> Result<Vec<Explanation>> {
+    let (block, spans) = lex_with_spans(src)?;
+    let mut res = Vec::new();
+    walk(&block, &mut spans.iter(), 0, &mut res);
+
+    Ok(res)
+}
+
+/// Print a program with every loop annotated with its explanation.
+///
+/// The explanations of the loops starting on a line are printed below it,
+/// each pointing at the opening bracket of its loop.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to annotate.
+///
+/// # Errors
+///
+/// If the given source cannot be lexed, a
+/// [`LexerError`](crate::error::LexerError) will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::explain::annotate;
+///
+/// assert_eq!(
+///     annotate("+[-]").unwrap(),
+///     "+[-]\n ^ sets cell 0 to 0\n"
+/// );
+/// ```
+pub fn annotate(src: &str) -> Result<String> {
+    let explanations = explain(src)?;
+    let mut explanations = explanations.iter().peekable();
+    let mut res = String::new();
+    let mut line_start = 0;
+
+    for line in src.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+        res.push_str(line.trim_end_matches('\n'));
+        res.push('\n');
+
+        while let Some(explanation) =
+            explanations.next_if(|explanation| explanation.span.start < line_end)
+        {
+            let column = src[line_start..explanation.span.start].chars().count();
+            res.push_str(&format!("{}^ {}\n", " ".repeat(column), explanation.text));
+        }
+
+        line_start = line_end;
+    }
+
+    Ok(res)
+}
+
+/// Explain the loops of a block, taking the spans of its tokens from the
+/// spans of the whole program.
+fn walk<'a, I>(block: &Block, spans: &mut I, depth: usize, res: &mut Vec<Explanation>)
+where
+    I: Iterator<Item = &'a Span>,
+{
+    for token in block {
+        // The lexer gives every token a span, and closures one for each of
+        // their brackets
+        let start = *spans.next().unwrap();
+
+        if let Token::Closure(body) = token {
+            let index = res.len();
+            res.push(Explanation {
+                span: start,
+                depth,
+                text: describe(body),
+            });
+
+            walk(body, spans, depth + 1, res);
+            res[index].span.end = spans.next().unwrap().end;
+        }
+    }
+}
+
+/// Describe what a loop with a body does.
+fn describe(body: &Block) -> String {
+    #[cfg(feature = "precompiled_patterns")]
+    if let Some(text) = describe_pattern(body) {
+        return text;
+    }
+
+    let ops = lower(body);
+    if ops.is_empty() {
+        return "never ends unless cell 0 is already 0".to_string();
+    }
+
+    match effect(&ops) {
+        Some((changes, 0)) if changes.get(&0).is_some_and(|&x| x as u8 == u8::MAX) => {
+            let factors: Vec<_> = changes
+                .iter()
+                .filter(|(&offset, &value)| offset != 0 && value as u8 != 0)
+                .map(|(offset, &value)| format!("by {} into cell {}", value as i8, offset))
+                .collect();
+
+            if factors.is_empty() {
+                "sets cell 0 to 0".to_string()
+            } else {
+                format!("multiplies cell 0 {}", join(&factors))
+            }
+        }
+        Some((changes, distance)) if distance != 0 && changes.values().all(|&x| x == 0) => {
+            let direction = if distance > 0 { "right" } else { "left" };
+            let count = distance.unsigned_abs();
+            let cells = if count == 1 { "cell" } else { "cells" };
+            format!(
+                "moves {} {} {} at a time until it finds a cell which is 0",
+                direction, count, cells
+            )
+        }
+        _ => describe_generic(&ops),
+    }
+}
+
+/// Describe a loop matching one of the patterns of the optimizer.
+#[cfg(feature = "precompiled_patterns")]
+fn describe_pattern(body: &Block) -> Option<String> {
+    let optimizer = Optimizer::new(OptLevel::Basic);
+    let text = match optimizer.optimize(&vec![Token::Closure(body.clone())])[..] {
+        [Token::Pattern(PreCompiledPattern::SetToZero)] => "sets cell 0 to 0".to_string(),
+        [Token::Pattern(PreCompiledPattern::Multiply {
+            dest_offset,
+            factor,
+        })] => format!(
+            "multiplies cell 0 by {} into cell {}",
+            factor as i8, dest_offset
+        ),
+        _ => return None,
+    };
+
+    Some(text)
+}
+
+/// Describe what a loop which matches no known shape does along the way.
+fn describe_generic(ops: &[Op]) -> String {
+    let mut text = "repeats while the current cell is not 0".to_string();
+    let mut parts = Vec::new();
+
+    if ops.contains(&Op::Input) {
+        parts.push("reading input");
+    }
+    if ops.contains(&Op::Print) {
+        parts.push("printing");
+    }
+    if ops.iter().any(|op| matches!(op, Op::Loop(_))) {
+        parts.push("running inner loops");
+    }
+
+    if !parts.is_empty() {
+        text.push_str(", ");
+        text.push_str(&join(&parts));
+    }
+
+    text
+}
+
+/// Join the items of a list in English, such as `a, b and c`.
+fn join<S>(items: &[S]) -> String
+where
+    S: AsRef<str>,
+{
+    let items: Vec<_> = items.iter().map(AsRef::as_ref).collect();
+
+    match items[..] {
+        [ref init @ .., last] if !init.is_empty() => format!("{} and {}", init.join(", "), last),
+        _ => items.concat(),
+    }
+}
+
+/// Get the changes to every cell and the pointer made by the body of a loop,
+/// if it only changes cells and moves the pointer.
+fn effect(ops: &[Op]) -> Option<(BTreeMap<isize, i16>, isize)> {
+    let mut changes = BTreeMap::new();
+    let mut ptr = 0;
+
+    for op in ops {
+        match *op {
+            Op::Add(x) => {
+                let value = changes.entry(ptr).or_insert(0);
+                *value = (*value + x) % 256;
+            }
+            Op::Move(distance) => ptr += distance,
+            _ => return None,
+        }
+    }
+
+    Some((changes, ptr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(src: &str) -> Vec<String> {
+        explain(src)
+            .unwrap()
+            .into_iter()
+            .map(|explanation| explanation.text)
+            .collect()
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(texts("[-]"), ["sets cell 0 to 0"]);
+        assert_eq!(texts("[<<+>>-]"), ["multiplies cell 0 by 1 into cell -2"]);
+        assert_eq!(texts("[->---<]"), ["multiplies cell 0 by -3 into cell 1"]);
+    }
+
+    #[test]
+    fn counter_loops() {
+        assert_eq!(
+            texts("[>++>+++>-<<<-]"),
+            ["multiplies cell 0 by 2 into cell 1, by 3 into cell 2 and by -1 into cell 3"]
+        );
+        assert_eq!(texts("[>+<->-<]"), ["sets cell 0 to 0"]);
+    }
+
+    #[test]
+    fn scans() {
+        assert_eq!(
+            texts("[>]"),
+            ["moves right 1 cell at a time until it finds a cell which is 0"]
+        );
+        assert_eq!(
+            texts("[<<]"),
+            ["moves left 2 cells at a time until it finds a cell which is 0"]
+        );
+    }
+
+    #[test]
+    fn other_loops() {
+        assert_eq!(texts("[]"), ["never ends unless cell 0 is already 0"]);
+        assert_eq!(
+            texts(",[.,]"),
+            ["repeats while the current cell is not 0, reading input and printing"]
+        );
+        assert_eq!(
+            texts("[>[-]<-]"),
+            [
+                "repeats while the current cell is not 0, running inner loops",
+                "sets cell 0 to 0"
+            ]
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let explanations = explain("+[>[-]\n<-]").unwrap();
+
+        assert_eq!(explanations[0].span, Span { start: 1, end: 10 });
+        assert_eq!(explanations[0].depth, 0);
+        assert_eq!(explanations[1].span, Span { start: 3, end: 6 });
+        assert_eq!(explanations[1].depth, 1);
+    }
+
+    #[test]
+    fn annotations() {
+        let src = "++[>++<-]>[>[-]\n<-]";
+        let expected = "\
+++[>++<-]>[>[-]
+  ^ multiplies cell 0 by 2 into cell 1
+          ^ repeats while the current cell is not 0, running inner loops
+            ^ sets cell 0 to 0
+<-]
+";
+        assert_eq!(annotate(src).unwrap(), expected);
+    }
+}
@@ -0,0 +1,248 @@
+//! Formatting of Brainfuck source code.
+
+use crate::error::Result;
+use crate::lexer::{lex_with_comments, write_token, Block, Token};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Options for formatting Brainfuck source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// Number of spaces to indent the body of a closure with.
+    pub indent: usize,
+    /// Maximum width of a line of code, including the indentation.
+    pub width: usize,
+    /// Whether to keep comments.
+    pub comments: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            width: 80,
+            comments: true,
+        }
+    }
+}
+
+/// Part of a program being formatted.
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    /// A run of commands which are not brackets.
+    Commands(String),
+    /// A run of non-command characters, with its whitespace collapsed.
+    Comment(String),
+    /// A closure and its body.
+    Closure(Vec<Item>),
+}
+
+/// Format Brainfuck source.
+///
+/// The source is lexed with [`lex_with_comments`]. Closures which contain
+/// other closures or don't fit on the current line are written over several
+/// lines, with their body indented one level deeper. Runs of other commands
+/// are wrapped to the configured line width, and comments are written on lines
+/// of their own.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to format.
+/// * `config` - The [`FormatConfig`] to format with.
+///
+/// # Errors
+///
+/// If the brackets of the source are unbalanced, a [`LexerError`] will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::formatter::{format, FormatConfig};
+///
+/// let src = "+[>+[-]<-]";
+/// let formatted = format(src, &FormatConfig::default()).unwrap();
+///
+/// assert_eq!(formatted, "+\n[\n    >+[-]<-\n]\n");
+/// ```
+pub fn format(src: &str, config: &FormatConfig) -> Result<String> {
+    let items = parse(&lex_with_comments(src)?);
+    let mut lines = Vec::new();
+    render(&items, 0, config, &mut lines);
+
+    Ok(lines.into_iter().map(|line| line + "\n").collect())
+}
+
+/// Convert a block lexed with its comments to a tree of items.
+fn parse(block: &Block) -> Vec<Item> {
+    let mut items = Vec::new();
+
+    for token in block {
+        match token {
+            Token::Closure(inner) => items.push(Item::Closure(parse(inner))),
+            Token::Comment(text) => {
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !text.is_empty() {
+                    items.push(Item::Comment(text));
+                }
+            }
+            token => match items.last_mut() {
+                Some(Item::Commands(run)) => write_token(token, run),
+                _ => {
+                    let mut run = String::new();
+                    write_token(token, &mut run);
+                    items.push(Item::Commands(run));
+                }
+            },
+        }
+    }
+
+    items
+}
+
+/// Get a closure body as a single line, if it has no closures or comments.
+fn inline(items: &[Item], config: &FormatConfig) -> Option<String> {
+    let mut line = String::new();
+
+    for item in items {
+        match item {
+            Item::Commands(commands) => line.push_str(commands),
+            Item::Comment(_) if !config.comments => {}
+            _ => return None,
+        }
+    }
+
+    Some(line)
+}
+
+fn render(items: &[Item], depth: usize, config: &FormatConfig, lines: &mut Vec<String>) {
+    let indent = " ".repeat(depth * config.indent);
+    let mut line = String::new();
+
+    let flush = |line: &mut String, lines: &mut Vec<String>| {
+        if !line.is_empty() {
+            lines.push(format!("{}{}", indent, line));
+            line.clear();
+        }
+    };
+
+    for item in items {
+        match item {
+            Item::Commands(commands) => {
+                for ch in commands.chars() {
+                    if indent.len() + line.len() >= config.width {
+                        flush(&mut line, lines);
+                    }
+                    line.push(ch);
+                }
+            }
+            Item::Comment(comment) => {
+                if config.comments {
+                    flush(&mut line, lines);
+                    lines.push(format!("{}{}", indent, comment));
+                }
+            }
+            Item::Closure(body) => match inline(body, config) {
+                Some(body) if indent.len() + line.len() + body.len() + 2 <= config.width => {
+                    line.push('[');
+                    line.push_str(&body);
+                    line.push(']');
+                }
+                _ => {
+                    flush(&mut line, lines);
+                    lines.push(format!("{}[", indent));
+                    render(body, depth + 1, config, lines);
+                    lines.push(format!("{}]", indent));
+                }
+            },
+        }
+    }
+
+    flush(&mut line, lines);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LexerError;
+
+    #[test]
+    fn inline_closures() {
+        let src = "++ [->+<] >.";
+        let expected = "++[->+<]>.\n";
+        assert_eq!(
+            format(src, &FormatConfig::default()),
+            Ok(expected.to_string())
+        );
+    }
+
+    #[test]
+    fn nested_closures() {
+        let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.";
+        let expected = "\
+++++++++
+[
+    >++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-
+]
+>>.
+";
+        assert_eq!(
+            format(src, &FormatConfig::default()),
+            Ok(expected.to_string())
+        );
+    }
+
+    #[test]
+    fn line_width() {
+        let config = FormatConfig {
+            indent: 2,
+            width: 8,
+            comments: true,
+        };
+
+        let src = "++++++++++[-[>+<-]]";
+        let expected = "\
+++++++++
+++
+[
+  -
+  [
+    >+<-
+  ]
+]
+";
+        assert_eq!(format(src, &config), Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn comments() {
+        let src = "Set  the\ncell +++\n[- loop\n]";
+        let expected = "\
+Set the cell
++++
+[
+    -
+    loop
+]
+";
+        assert_eq!(
+            format(src, &FormatConfig::default()),
+            Ok(expected.to_string())
+        );
+
+        let config = FormatConfig {
+            comments: false,
+            ..Default::default()
+        };
+        let expected = "+++[-]\n";
+        assert_eq!(format(src, &config), Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn unbalanced() {
+        let config = FormatConfig::default();
+        assert_eq!(format("[[]", &config), Err(LexerError::UnclosedBlock));
+        assert_eq!(format("[]]", &config), Err(LexerError::SyntaxError(']')));
+    }
+}
@@ -0,0 +1,98 @@
+//! Generation of Brainfuck programs.
+
+use crate::builder::Program;
+use crate::error::GenerateError;
+use alloc::string::String;
+
+/// Number of commands a multiplication loop needs besides its factors.
+const LOOP_OVERHEAD: usize = 7;
+
+/// Generate a Brainfuck program printing some text.
+///
+/// Every character of the text is printed from the same cell, which is
+/// changed from the previous character by the shortest of adding the
+/// difference directly or multiplying two factors in a loop. A second cell to
+/// the left is the counter of the loops.
+///
+/// The cell holds the code point of the character, which is printed as that
+/// character by the default output of the interpreter, so only characters up
+/// to `U+00FF` can be printed.
+///
+/// # Arguments
+///
+/// * `text` - The text for the program to print.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::generate::text_to_bf;
+///
+/// assert_eq!(text_to_bf("A").unwrap(), "++++++++[>++++++++<-]>+.");
+/// assert_eq!(text_to_bf("AB").unwrap(), "++++++++[>++++++++<-]>+.+.");
+/// ```
+///
+/// # Errors
+///
+/// If the text has a character above `U+00FF`, a
+/// [`GenerateError::Unprintable`] is returned.
+pub fn text_to_bf(text: &str) -> Result<String, GenerateError> {
+    let mut program = Program::new();
+    let mut current = 0u8;
+
+    for (i, ch) in text.chars().enumerate() {
+        let value = u8::try_from(ch).map_err(|_| GenerateError::Unprintable(ch))?;
+        let diff = value.wrapping_sub(current) as i8 as isize;
+        program = change(program, diff, i == 0);
+        program = program.print();
+        current = value;
+    }
+
+    // Only commands are generated, which always convert to source
+    Ok(program.to_source().unwrap())
+}
+
+/// Change the value of the output cell.
+///
+/// The pointer is on the counter cell left of the output cell before the
+/// first change, and on the output cell after it.
+fn change(program: Program, diff: isize, first: bool) -> Program {
+    let to_counter = if first { 0 } else { 1 };
+    let direct = diff.unsigned_abs();
+
+    match factors(direct) {
+        Some((count, step, rest))
+            if count + step + rest.unsigned_abs() + LOOP_OVERHEAD < direct =>
+        {
+            let step = step as isize * diff.signum();
+            let program = program
+                .left(to_counter)
+                .inc(count as u8)
+                .loop_(|body| add(body.right(1), step).left(1).dec(1))
+                .right(1);
+
+            add(program, rest * diff.signum())
+        }
+        _ => add(program.right(1 - to_counter), diff),
+    }
+}
+
+/// Find the factors of a loop adding up to a value, with the rest to add
+/// after it, which give the shortest loop.
+fn factors(value: usize) -> Option<(usize, usize, isize)> {
+    (2..=value / 2)
+        .flat_map(|count| {
+            let step = value / count;
+            [step, step + 1].map(|step| (count, step, value as isize - (count * step) as isize))
+        })
+        .filter(|&(_, step, _)| step > 0)
+        .min_by_key(|&(count, step, rest)| count + step + rest.unsigned_abs())
+}
+
+/// Add a possibly negative value to the current cell.
+fn add(program: Program, value: isize) -> Program {
+    if value < 0 {
+        program.dec(value.unsigned_abs() as u8)
+    } else {
+        program.inc(value as u8)
+    }
+}
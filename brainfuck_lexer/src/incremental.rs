@@ -0,0 +1,359 @@
+//! Incremental lexing of source being edited.
+//!
+//! Editors change a few characters of a document at a time. Instead of lexing
+//! the whole document again on every change, only the tokens around the
+//! change are lexed again, and spliced into the tokens of the rest of the
+//! document. The result is always the same as lexing the whole document
+//! with [`lex_with_spans`].
+
+use crate::error::Result;
+use crate::lexer::{lex_spans_at, lex_with_spans, Block, Span, Token, DEFAULT_MAX_DEPTH};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Brainfuck source with its tokens, which are kept up to date as the
+/// source is edited.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::incremental::IncrementalLexer;
+/// use brainfuck_lexer::{lex_with_spans, Span};
+///
+/// let mut lexer = IncrementalLexer::new("+[>+<-]");
+/// lexer.edit(Span { start: 3, end: 4 }, "++").unwrap();
+///
+/// let (block, spans) = lex_with_spans("+[>++<-]").unwrap();
+/// assert_eq!(lexer.src(), "+[>++<-]");
+/// assert_eq!(lexer.block(), Some(&block));
+/// assert_eq!(lexer.spans(), spans);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalLexer {
+    src: String,
+    lexed: Option<(Block, Vec<Span>)>,
+}
+
+impl IncrementalLexer {
+    /// Lex the whole source.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The Brainfuck source to lex.
+    pub fn new(src: impl Into<String>) -> Self {
+        let src = src.into();
+        let lexed = lex_with_spans(&src).ok();
+
+        Self { src, lexed }
+    }
+
+    /// The current source.
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    /// The tokens of the source, or `None` if it cannot be lexed.
+    pub fn block(&self) -> Option<&Block> {
+        self.lexed.as_ref().map(|(block, _)| block)
+    }
+
+    /// The spans of the tokens of the source, in the order described by
+    /// [`lex_with_spans`]. Empty if the source cannot be lexed.
+    pub fn spans(&self) -> &[Span] {
+        self.lexed.as_ref().map_or(&[], |(_, spans)| spans)
+    }
+
+    /// Replace part of the source, and lex the tokens it changed again.
+    ///
+    /// The whole source is lexed again if the source could not be lexed
+    /// before the edit, or if the edit changes which brackets match.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The byte offsets of the part of the source to replace.
+    /// * `text` - The text to replace it with.
+    ///
+    /// # Errors
+    ///
+    /// If the source cannot be lexed after the edit, the [`LexerError`] is
+    /// returned. The edit is made regardless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of the source, or does not lie
+    /// on [`char`] boundaries.
+    ///
+    /// [`LexerError`]: crate::error::LexerError
+    pub fn edit(&mut self, range: Span, text: &str) -> Result<()> {
+        let spliced = match self.lexed {
+            Some((ref mut block, ref mut spans)) => splice(&self.src, block, spans, range, text),
+            None => false,
+        };
+        self.src.replace_range(range.start..range.end, text);
+
+        if !spliced {
+            match lex_with_spans(&self.src) {
+                Ok(lexed) => self.lexed = Some(lexed),
+                Err(e) => {
+                    self.lexed = None;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A token of a block, with the index of its first span and its number of
+/// spans, and the source from its first to its last character.
+#[derive(Debug, Clone, Copy)]
+struct Child {
+    first: usize,
+    count: usize,
+    span: Span,
+}
+
+/// Lex the tokens around an edit of the source again, and splice them into
+/// the tokens of the rest of it.
+///
+/// Returns `false` without changing anything if the tokens around the edit
+/// cannot be lexed on their own.
+fn splice(src: &str, block: &mut Block, spans: &mut Vec<Span>, range: Span, text: &str) -> bool {
+    // Find the innermost closure with the whole edit between its brackets
+    let mut level = block;
+    let mut bounds = Span {
+        start: 0,
+        end: src.len(),
+    };
+    let mut base = 0;
+    let mut depth = 0;
+
+    let children = loop {
+        let children = children(level, spans, base);
+        let inner = children.iter().position(|child| {
+            let (open, close) = (spans[child.first], spans[child.first + child.count - 1]);
+            child.count > 1 && open.end <= range.start && range.end <= close.start
+        });
+
+        let Some(i) = inner else {
+            break children;
+        };
+        let child = children[i];
+        bounds = Span {
+            start: spans[child.first].end,
+            end: spans[child.first + child.count - 1].start,
+        };
+        base = child.first + 1;
+        depth += 1;
+
+        let Token::Closure(body) = &mut level[i] else {
+            unreachable!("only closures have more than one span");
+        };
+        level = body;
+    };
+
+    // The tokens touching the edit change, and the tokens next to them may
+    // be combined with the new tokens
+    let touching = |child: &Child| child.span.start <= range.end && range.start <= child.span.end;
+    let mut lo = match children.iter().position(touching) {
+        Some(i) => i,
+        None => children.partition_point(|child| child.span.end < range.start),
+    };
+    let mut hi = children.iter().rposition(touching).map_or(lo, |i| i + 1);
+    lo = lo.saturating_sub(1);
+    hi = (hi + 1).min(children.len());
+
+    let (tokens, new_spans) = loop {
+        let start = lo
+            .checked_sub(1)
+            .map_or(bounds.start, |i| children[i].span.end);
+        let end = children
+            .get(hi)
+            .map_or(bounds.end, |child| child.span.start);
+
+        let mut region = String::from(&src[start..range.start]);
+        region.push_str(text);
+        region.push_str(&src[range.end..end]);
+        let Ok((tokens, new_spans)) = lex_spans_at(&region, start, DEFAULT_MAX_DEPTH - depth)
+        else {
+            return false;
+        };
+
+        let before = lo.checked_sub(1).map(|i| &level[i]);
+        let after = level.get(hi);
+        if before.is_some_and(|before| tokens.first().or(after).is_some_and(|t| merges(before, t)))
+        {
+            lo -= 1;
+        } else if after.is_some_and(|after| tokens.last().is_some_and(|t| merges(t, after))) {
+            hi += 1;
+        } else {
+            break (tokens, new_spans);
+        }
+    };
+
+    let end = children
+        .last()
+        .map_or(base, |child| child.first + child.count);
+    let first = |i: usize| children.get(i).map_or(end, |child| child.first);
+    let (from, to) = (first(lo), first(hi));
+    let moved = from + new_spans.len();
+
+    spans.splice(from..to, new_spans);
+    for span in &mut spans[moved..] {
+        span.start = span.start - (range.end - range.start) + text.len();
+        span.end = span.end - (range.end - range.start) + text.len();
+    }
+    level.splice(lo..hi, tokens);
+
+    true
+}
+
+/// Find the spans of the tokens of a block, which start at an index of the
+/// spans of the whole source.
+fn children(block: &Block, spans: &[Span], base: usize) -> Vec<Child> {
+    let mut first = base;
+
+    block
+        .iter()
+        .map(|token| {
+            let count = span_count(token);
+            let span = Span {
+                start: spans[first].start,
+                end: spans[first + count - 1].end,
+            };
+            let child = Child { first, count, span };
+            first += count;
+            child
+        })
+        .collect()
+}
+
+/// The number of spans of a token, counting both brackets of a closure.
+fn span_count(token: &Token) -> usize {
+    match token {
+        Token::Closure(block) => 2 + block.iter().map(span_count).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+/// Whether two tokens next to each other are lexed as a single token.
+///
+/// Arithmetic is only combined up to [`u8::MAX`], so a full token is never
+/// combined with the next.
+fn merges(first: &Token, second: &Token) -> bool {
+    match (first, second) {
+        (Token::Increment(n), Token::Increment(_)) | (Token::Decrement(n), Token::Decrement(_)) => {
+            *n < u8::MAX
+        }
+        (Token::Next(_), Token::Next(_)) | (Token::Prev(_), Token::Prev(_)) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make an edit, and check the result against lexing the whole source.
+    fn check(lexer: &mut IncrementalLexer, start: usize, end: usize, text: &str) {
+        let res = lexer.edit(Span { start, end }, text);
+        let expected = lex_with_spans(lexer.src());
+
+        assert_eq!(res.is_ok(), expected.is_ok(), "{:?}", lexer.src());
+        if let Ok((block, spans)) = expected {
+            assert_eq!(lexer.block(), Some(&block), "{:?}", lexer.src());
+            assert_eq!(lexer.spans(), spans, "{:?}", lexer.src());
+        }
+    }
+
+    #[test]
+    fn edits() {
+        let mut lexer = IncrementalLexer::new("++[>+<-] >.");
+
+        check(&mut lexer, 0, 0, "+");
+        check(&mut lexer, 5, 5, "+");
+        check(&mut lexer, 4, 5, "");
+        check(&mut lexer, 3, 3, "[-]");
+        check(&mut lexer, 9, 10, "");
+        let len = lexer.src().len();
+        check(&mut lexer, 0, len, ">>");
+        check(&mut lexer, 2, 2, " ");
+        check(&mut lexer, 1, 1, "<");
+    }
+
+    #[test]
+    fn combined_tokens() {
+        let mut lexer = IncrementalLexer::new("+ + -");
+
+        // Replacing the decrement combines all of the increments
+        check(&mut lexer, 4, 5, "+");
+        assert_eq!(lexer.block(), Some(&vec![Token::Increment(3)]));
+
+        // Removing the token between two tokens combines them
+        let mut lexer = IncrementalLexer::new(">>.>");
+        check(&mut lexer, 2, 3, "");
+        assert_eq!(lexer.block(), Some(&vec![Token::Next(3)]));
+    }
+
+    #[test]
+    fn full_tokens() {
+        let src = "+".repeat(600);
+        let mut lexer = IncrementalLexer::new(src);
+
+        check(&mut lexer, 10, 11, "");
+        check(&mut lexer, 300, 300, "-+");
+        check(&mut lexer, 598, 598, "+");
+    }
+
+    #[test]
+    fn brackets() {
+        let mut lexer = IncrementalLexer::new("[+][-]");
+
+        // Matching brackets differently
+        check(&mut lexer, 2, 2, "][");
+        check(&mut lexer, 0, 1, "");
+        assert!(lexer.block().is_none());
+        assert!(lexer.spans().is_empty());
+
+        check(&mut lexer, 0, 0, "[");
+        check(&mut lexer, 8, 8, "]");
+    }
+
+    #[test]
+    fn nested() {
+        let mut lexer = IncrementalLexer::new("[[[+]]]");
+
+        for _ in 0..4 {
+            check(&mut lexer, 4, 4, "+>");
+        }
+        check(&mut lexer, 3, 4, "");
+        check(&mut lexer, 2, 3, "[");
+    }
+
+    #[test]
+    fn random_edits() {
+        const CHARS: &[u8] = b"++--<<>>.,[] \n";
+
+        // A fixed linear congruential generator, so failures are reproducible
+        let mut state = 0x2545_f491_u64;
+        let mut next = |n: usize| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize % n
+        };
+
+        let mut lexer = IncrementalLexer::new("++[>+[>++<-]<-]>>.");
+        for _ in 0..2000 {
+            let len = lexer.src().len();
+            let start = next(len + 1);
+            let end = start + next(len - start + 1).min(3);
+            let text: String = (0..next(4))
+                .map(|_| CHARS[next(CHARS.len())] as char)
+                .collect();
+
+            check(&mut lexer, start, end, &text);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Normalized intermediate representation of Brainfuck programs.
+//!
+//! The [`Token`]s of the lexer follow the commands of the source, so both
+//! changing a cell and moving the pointer take one token for each direction.
+//! The IR has a single signed [`Op`] for each instead, which is what the
+//! optimizer and the interpreters work on. Programs are lowered from tokens
+//! with [`lower`], and raised back with [`raise`].
+
+#[cfg(feature = "precompiled_patterns")]
+use crate::lexer::PreCompiledPattern;
+use crate::lexer::{Block, Token};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A single operation of a normalized program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op {
+    /// Add a signed value to the current memory location, wrapping around.
+    Add(i16),
+    /// Move the pointer by a signed distance.
+    Move(isize),
+    /// Print the value at the current memory location.
+    Print,
+    /// Set the value at the current memory location from the input.
+    Input,
+    /// Repeat the operations while the current memory location is not zero.
+    Loop(Vec<Op>),
+    #[cfg(feature = "debug_token")]
+    /// Print the content of the memory as u8.
+    Debug,
+    #[cfg(feature = "breakpoint_token")]
+    /// Pause the program when running in a debugger.
+    Breakpoint,
+    #[cfg(feature = "precompiled_patterns")]
+    /// A block with a known pre-compiled result.
+    Pattern(PreCompiledPattern),
+}
+
+/// Print an operation as a short mnemonic with its arguments, such as
+/// `add -1` or `move 2`. Offsets from the current memory location are
+/// always signed.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::ir::Op;
+///
+/// assert_eq!(Op::Add(-1).to_string(), "add -1");
+/// assert_eq!(Op::Move(2).to_string(), "move 2");
+/// ```
+impl core::fmt::Display for Op {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Op::Add(value) => write!(f, "add {}", value),
+            Op::Move(distance) => write!(f, "move {}", distance),
+            Op::Print => write!(f, "print"),
+            Op::Input => write!(f, "input"),
+            Op::Loop(ops) => write!(f, "loop {}", ops.len()),
+            #[cfg(feature = "debug_token")]
+            Op::Debug => write!(f, "debug"),
+            #[cfg(feature = "breakpoint_token")]
+            Op::Breakpoint => write!(f, "breakpoint"),
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(pattern) => match *pattern {
+                PreCompiledPattern::SetToZero => write!(f, "zero"),
+                PreCompiledPattern::Multiply {
+                    dest_offset,
+                    factor,
+                } => write!(f, "mul {} into {:+}", factor as i8, dest_offset),
+                PreCompiledPattern::AddAt { offset, value } => {
+                    write!(f, "add {} at {:+}", value as i8, offset)
+                }
+                PreCompiledPattern::SetAt { offset, value } => {
+                    write!(f, "set {} at {:+}", value, offset)
+                }
+            },
+        }
+    }
+}
+
+/// Drop operations without recursing into the bodies of their loops.
+///
+/// Like [`drop_block`], this keeps programs nested deeper than
+/// [`DEFAULT_MAX_DEPTH`] from overflowing the stack when they are dropped.
+///
+/// [`drop_block`]: crate::lexer::drop_block
+/// [`DEFAULT_MAX_DEPTH`]: crate::lexer::DEFAULT_MAX_DEPTH
+///
+/// # Arguments
+///
+/// * `ops` - The operations to drop.
+pub fn drop_ops(ops: Vec<Op>) {
+    let mut stack = ops;
+    while let Some(op) = stack.pop() {
+        if let Op::Loop(body) = op {
+            stack.extend(body);
+        }
+    }
+}
+
+/// Lower a block of tokens to operations.
+///
+/// Every token becomes exactly one operation, except for
+/// [`Token::Comment`]s, which are left out.
+///
+/// # Arguments
+///
+/// * `block` - The [`Block`] to lower.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::ir::{lower, Op};
+/// use brainfuck_lexer::lex_unoptimized;
+///
+/// let ops = lower(&lex_unoptimized("+++-<[.]").unwrap());
+///
+/// assert_eq!(
+///     ops,
+///     [Op::Add(3), Op::Add(-1), Op::Move(-1), Op::Loop(vec![Op::Print])]
+/// );
+/// ```
+pub fn lower(block: &Block) -> Vec<Op> {
+    // The tokens left of every closure being lowered, with its operations so
+    // far, innermost last. Using a stack instead of recursion lets deeply
+    // nested blocks be lowered without overflowing the native stack
+    let mut stack = vec![(block.iter(), Vec::new())];
+
+    loop {
+        let (tokens, ops) = stack.last_mut().unwrap();
+        let op = match tokens.next() {
+            Some(Token::Increment(x)) => Op::Add(*x as i16),
+            Some(Token::Decrement(x)) => Op::Add(-(*x as i16)),
+            Some(Token::Next(count)) => Op::Move(*count as isize),
+            Some(Token::Prev(count)) => Op::Move(-(*count as isize)),
+            Some(Token::Print) => Op::Print,
+            Some(Token::Input) => Op::Input,
+            Some(Token::Closure(block)) => {
+                stack.push((block.iter(), Vec::new()));
+                continue;
+            }
+            #[cfg(feature = "debug_token")]
+            Some(Token::Debug) => Op::Debug,
+            #[cfg(feature = "breakpoint_token")]
+            Some(Token::Breakpoint) => Op::Breakpoint,
+            #[cfg(feature = "precompiled_patterns")]
+            Some(Token::Pattern(pattern)) => Op::Pattern(pattern.clone()),
+            Some(Token::Comment(_)) => continue,
+            None => {
+                let (_, body) = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some((_, ops)) => {
+                        ops.push(Op::Loop(body));
+                        continue;
+                    }
+                    None => return body,
+                }
+            }
+        };
+
+        ops.push(op);
+    }
+}
+
+/// Raise operations back to a block of tokens.
+///
+/// Additions and movements are written in the direction of their sign, and
+/// are left out if they have no effect.
+///
+/// # Arguments
+///
+/// * `ops` - The operations to raise.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::ir::{raise, Op};
+/// use brainfuck_lexer::Token;
+///
+/// let block = raise(&[Op::Add(-2), Op::Move(0), Op::Add(256), Op::Move(3)]);
+///
+/// assert_eq!(block, [Token::Decrement(2), Token::Next(3)]);
+/// ```
+pub fn raise(ops: &[Op]) -> Block {
+    ops.iter()
+        .filter_map(|op| {
+            let token = match *op {
+                Op::Add(value) => match (value.unsigned_abs() % 256) as u8 {
+                    0 => return None,
+                    x if value > 0 => Token::Increment(x),
+                    x => Token::Decrement(x),
+                },
+                Op::Move(0) => return None,
+                Op::Move(distance) if distance > 0 => Token::Next(distance as usize),
+                Op::Move(distance) => Token::Prev(distance.unsigned_abs()),
+                Op::Print => Token::Print,
+                Op::Input => Token::Input,
+                Op::Loop(ref ops) => Token::Closure(raise(ops)),
+                #[cfg(feature = "debug_token")]
+                Op::Debug => Token::Debug,
+                #[cfg(feature = "breakpoint_token")]
+                Op::Breakpoint => Token::Breakpoint,
+                #[cfg(feature = "precompiled_patterns")]
+                Op::Pattern(ref pattern) => Token::Pattern(pattern.clone()),
+            };
+
+            Some(token)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{lex, lex_unoptimized, lex_with_comments};
+
+    #[test]
+    fn round_trip() {
+        for src in ["+++[->>+<<]<.,", "-[[-]>]", ""] {
+            let block = lex_unoptimized(src).unwrap();
+            assert_eq!(raise(&lower(&block)), block);
+
+            let block = lex(src).unwrap();
+            assert_eq!(raise(&lower(&block)), block);
+        }
+    }
+
+    #[test]
+    fn comments_are_dropped() {
+        let block = lex_with_comments("add + one").unwrap();
+        assert_eq!(lower(&block), [Op::Add(1)]);
+    }
+
+    #[test]
+    fn wrapping_values() {
+        assert_eq!(lower(&vec![Token::Decrement(255)]), [Op::Add(-255)]);
+        assert_eq!(raise(&[Op::Add(300)]), [Token::Increment(44)]);
+        assert_eq!(raise(&[Op::Add(-300)]), [Token::Decrement(44)]);
+        assert_eq!(raise(&[Op::Add(i16::MIN)]), []);
+    }
+}
@@ -1,10 +1,15 @@
 //! Lexical analysis
 
 use crate::error::{LexerError, Result};
+use crate::optimizer::optimize;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use itertools::Itertools;
 
 /// Recognized Brainfuck tokens.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Token {
     /// Increment the value at the current memory location.
     Increment(u8),
@@ -23,14 +28,22 @@ pub enum Token {
     #[cfg(feature = "debug_token")]
     /// Print the content of the memory as u8.
     Debug,
+    #[cfg(feature = "breakpoint_token")]
+    /// Pause the program when running in a debugger.
+    Breakpoint,
     #[cfg(feature = "precompiled_patterns")]
     /// A block with a known pre-compiled result.
     Pattern(PreCompiledPattern),
+    /// Text which is not a command, only kept by [`lex_with_comments`].
+    ///
+    /// Comments have no effect when executed.
+    Comment(String),
 }
 
 #[cfg(feature = "precompiled_patterns")]
 /// Pre-compiled patterns of Brainfuck code.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PreCompiledPattern {
     /// Set the current memory location to zero.
     SetToZero,
@@ -41,11 +54,86 @@ pub enum PreCompiledPattern {
         /// The constant to multiply the source byte with.
         factor: u8,
     },
+    /// Add a constant to the byte at an offset from the current byte.
+    AddAt {
+        /// The offset from the current byte to add to.
+        offset: isize,
+        /// The constant to add, wrapping around.
+        value: u8,
+    },
+    /// Set the byte at an offset from the current byte to a constant.
+    SetAt {
+        /// The offset from the current byte to set.
+        offset: isize,
+        /// The constant to set the byte to.
+        value: u8,
+    },
 }
 
 /// Vector of [`Token`]s making up a single block of code.
 pub type Block = Vec<Token>;
 
+/// Location of a token in the source, as a range of byte offsets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Offset of the first byte of the token.
+    pub start: usize,
+    /// Offset of the byte after the last byte of the token.
+    pub end: usize,
+}
+
+/// Drop a block without recursing into the bodies of its closures.
+///
+/// Dropping a [`Block`] the usual way drops the body of every closure inside
+/// of it recursively, which overflows the stack for blocks nested much deeper
+/// than [`DEFAULT_MAX_DEPTH`].
+///
+/// # Arguments
+///
+/// * `block` - The [`Block`] to drop.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{drop_block, lex_with_config, LexerConfig};
+///
+/// let depth = 100_000;
+/// let src = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+/// let block = lex_with_config(src, &LexerConfig { max_depth: depth }).unwrap();
+///
+/// drop_block(block);
+/// ```
+pub fn drop_block(block: Block) {
+    let mut stack = block;
+    while let Some(token) = stack.pop() {
+        if let Token::Closure(body) = token {
+            stack.extend(body);
+        }
+    }
+}
+
+/// Maximum number of closures nested in each other accepted by default.
+pub const DEFAULT_MAX_DEPTH: usize = 1024;
+
+/// Options for lexing Brainfuck source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexerConfig {
+    /// Maximum number of closures nested in each other.
+    ///
+    /// Lexing never recurses, but optimizing, formatting and most other uses
+    /// of a [`Block`] do, so the default of [`DEFAULT_MAX_DEPTH`] keeps them
+    /// from overflowing the stack.
+    pub max_depth: usize,
+}
+
+impl Default for LexerConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
 const TOKEN_INCREMENT: char = '+';
 const TOKEN_DECREMENT: char = '-';
 const TOKEN_NEXT: char = '>';
@@ -56,6 +144,33 @@ const TOKEN_LOOP_BEGIN: char = '[';
 const TOKEN_LOOP_END: char = ']';
 #[cfg(feature = "debug_token")]
 const TOKEN_DEBUG: char = '#';
+#[cfg(feature = "breakpoint_token")]
+const TOKEN_BREAKPOINT: char = '@';
+
+/// Whether a character is a command, rather than part of a comment.
+pub(crate) fn is_command(ch: char) -> bool {
+    #[cfg(feature = "debug_token")]
+    if ch == TOKEN_DEBUG {
+        return true;
+    }
+
+    #[cfg(feature = "breakpoint_token")]
+    if ch == TOKEN_BREAKPOINT {
+        return true;
+    }
+
+    matches!(
+        ch,
+        TOKEN_INCREMENT
+            | TOKEN_DECREMENT
+            | TOKEN_NEXT
+            | TOKEN_PREV
+            | TOKEN_PRINT
+            | TOKEN_INPUT
+            | TOKEN_LOOP_BEGIN
+            | TOKEN_LOOP_END
+    )
+}
 
 /// Parse Brainfuck program.
 ///
@@ -64,7 +179,8 @@ const TOKEN_DEBUG: char = '#';
 ///
 /// # Arguments
 ///
-/// * `src` - The Brainfuck source to parse.
+/// * `src` - The Brainfuck source to parse, as a `&str`, a `String` or
+///   anything else which can be borrowed as a `str`.
 ///
 /// # Errors
 ///
@@ -75,93 +191,473 @@ const TOKEN_DEBUG: char = '#';
 /// ```
 /// use brainfuck_lexer::lexer::lex;
 ///
-/// let src = "++++++++[->++++++++<].".to_string();
-/// let code = lex(src);
+/// let code = lex("++++++++[->++++++++<].");
 /// ```
-pub fn lex(src: String) -> Result<Block> {
-    let mut slice = src
-        .chars()
-        .into_iter()
-        .filter(|ch| !ch.is_whitespace())
-        .map(|c| (c, 1))
-        .coalesce(|(c, n), (d, m)| {
-            if c == d
-                && (c == TOKEN_INCREMENT
-                    || c == TOKEN_DECREMENT
-                    || c == TOKEN_NEXT
-                    || c == TOKEN_PREV)
-            {
-                Ok((c, n + m))
-            } else {
-                Err(((c, n), (d, m)))
-            }
-        });
+pub fn lex(src: impl AsRef<str>) -> Result<Block> {
+    Ok(optimize(&lex_unoptimized(src)?))
+}
+
+/// Parse Brainfuck program without optimizing it.
+///
+/// Repeated tokens are still combined into a single [`Token`], but the block
+/// is otherwise left exactly as written. Passing the result to
+/// [`optimize`] gives the same block as [`lex`].
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to parse.
+///
+/// # Errors
+///
+/// If the given source cannot be lexed, a [`LexerError`] will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{lex, lex_unoptimized};
+/// use brainfuck_lexer::optimizer::optimize;
+///
+/// let src = "++++++++[->++++++++<].";
+/// let code = lex_unoptimized(src).unwrap();
+///
+/// assert_eq!(optimize(&code), lex(src).unwrap());
+/// ```
+pub fn lex_unoptimized(src: impl AsRef<str>) -> Result<Block> {
+    lex_with_config(src, &LexerConfig::default())
+}
+
+/// Parse Brainfuck program without optimizing it, with the given
+/// configuration.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to parse.
+/// * `config` - The [`LexerConfig`] to lex with.
+///
+/// # Errors
+///
+/// If the closures of the source are nested deeper than the maximum depth
+/// of the configuration, a [`LexerError::NestingTooDeep`] will be returned.
+/// See also [`lex_unoptimized`].
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::error::LexerError;
+/// use brainfuck_lexer::lexer::{lex_with_config, LexerConfig};
+///
+/// let config = LexerConfig { max_depth: 2 };
+///
+/// assert!(lex_with_config("[[-]>]", &config).is_ok());
+/// assert_eq!(
+///     lex_with_config("[[[-]]]", &config),
+///     Err(LexerError::NestingTooDeep(2))
+/// );
+/// ```
+pub fn lex_with_config(src: impl AsRef<str>, config: &LexerConfig) -> Result<Block> {
+    let slice = coalesce(
+        src.as_ref()
+            .char_indices()
+            .filter(|(_, ch)| !ch.is_whitespace()),
+    );
 
-    let res = optimize_block(&tokenize_block(&mut slice, false)?);
+    tokenize_block(slice, false, config.max_depth, None)
+}
 
-    Ok(res)
+/// Parse Brainfuck program without optimizing it, recording where every
+/// token is in the source.
+///
+/// The block is the same as the one from [`lex_unoptimized`]. The spans are
+/// in the order the tokens are written in: a closure has the span of its
+/// opening bracket before the spans of its body, and the span of its closing
+/// bracket after them. This is the order of the instructions of a flattened
+/// program.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to parse.
+///
+/// # Errors
+///
+/// If the given source cannot be lexed, a [`LexerError`] will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{lex_with_spans, Span};
+///
+/// let (_, spans) = lex_with_spans("++ [-]").unwrap();
+/// let starts: Vec<_> = spans.iter().map(|span| span.start).collect();
+///
+/// assert_eq!(spans[0], Span { start: 0, end: 2 });
+/// assert_eq!(starts, [0, 3, 4, 5]);
+/// ```
+pub fn lex_with_spans(src: &str) -> Result<(Block, Vec<Span>)> {
+    lex_spans_at(src, 0, DEFAULT_MAX_DEPTH)
 }
 
-/// Tokenize iterator to Brainfuck block.
-fn tokenize_block<T>(iter: &mut T, is_closure: bool) -> Result<Block>
-where
-    T: Iterator<Item = (char, u32)>,
-{
-    let mut block = vec![];
+/// Parse part of a larger source, which starts at an offset into it, with
+/// the spans of the tokens in the larger source.
+pub(crate) fn lex_spans_at(
+    src: &str,
+    offset: usize,
+    max_depth: usize,
+) -> Result<(Block, Vec<Span>)> {
+    let chars = src.char_indices().map(|(i, ch)| (offset + i, ch));
+    let slice = coalesce(chars.filter(|(_, ch)| !ch.is_whitespace()));
+    let mut spans = Vec::new();
+    let block = tokenize_block(slice, false, max_depth, Some(&mut spans))?;
 
-    while let Some((ch, count)) = iter.next() {
-        let op = match ch {
-            TOKEN_INCREMENT => Token::Increment(count as u8),
-            TOKEN_DECREMENT => Token::Decrement(count as u8),
-            TOKEN_NEXT => Token::Next(count as usize),
-            TOKEN_PREV => Token::Prev(count as usize),
-            TOKEN_PRINT => Token::Print,
-            TOKEN_INPUT => Token::Input,
-            TOKEN_LOOP_BEGIN => Token::Closure(tokenize_block(iter, true)?),
-            TOKEN_LOOP_END if is_closure => return Ok(block),
-            TOKEN_LOOP_END => Err(LexerError::SyntaxError(ch))?,
-            #[cfg(feature = "debug_token")]
-            TOKEN_DEBUG => Token::Debug,
-            #[cfg(feature = "comments")]
-            _ => continue,
-            #[cfg(not(feature = "comments"))]
-            _ => Err(LexerError::SyntaxError(ch))?,
+    Ok((block, spans))
+}
+
+/// Parse Brainfuck program without optimizing it, keeping its comments.
+///
+/// Every run of characters which are not commands, including whitespace, is
+/// kept as a [`Token::Comment`], so the source can be recovered exactly from
+/// the result. Unlike the other lexing functions, this is independent of the
+/// `comments` feature.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to parse.
+///
+/// # Errors
+///
+/// If the brackets of the source are unbalanced, a [`LexerError`] will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{lex_with_comments, Token};
+///
+/// let code = lex_with_comments("++ add two").unwrap();
+///
+/// assert_eq!(code, vec![Token::Increment(2), Token::Comment(" add two".to_string())]);
+/// ```
+pub fn lex_with_comments(src: impl AsRef<str>) -> Result<Block> {
+    tokenize_block(
+        coalesce(src.as_ref().char_indices()),
+        true,
+        DEFAULT_MAX_DEPTH,
+        None,
+    )
+}
+
+/// Find every error in Brainfuck source.
+///
+/// Lexing stops at the first error, while this recovers from every error and
+/// goes on, so all of them can be reported at once. Every bracket without a
+/// match is an error, as is every character which is not a command when the
+/// `comments` feature is disabled.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to check.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::error::LexerError;
+/// use brainfuck_lexer::lexer::{diagnose, Span};
+///
+/// let errors = diagnose("]+[[-]");
+///
+/// assert_eq!(
+///     errors,
+///     [
+///         (Span { start: 0, end: 1 }, LexerError::SyntaxError(']')),
+///         (Span { start: 2, end: 3 }, LexerError::UnclosedBlock),
+///     ]
+/// );
+/// ```
+pub fn diagnose(src: &str) -> Vec<(Span, LexerError)> {
+    scan_brackets(src, &mut Vec::new())
+}
+
+/// Find the pairs of matching brackets in Brainfuck source.
+///
+/// Brackets without a match are left out. The pairs are in the order of
+/// their closing brackets.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to search.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{bracket_pairs, Span};
+///
+/// let pairs = bracket_pairs("[[]]");
+///
+/// assert_eq!(pairs[0], (Span { start: 1, end: 2 }, Span { start: 2, end: 3 }));
+/// assert_eq!(pairs[1], (Span { start: 0, end: 1 }, Span { start: 3, end: 4 }));
+/// ```
+pub fn bracket_pairs(src: &str) -> Vec<(Span, Span)> {
+    let mut pairs = Vec::new();
+    scan_brackets(src, &mut pairs);
+
+    pairs
+}
+
+/// Match the brackets of the source, returning the errors found on the way.
+fn scan_brackets(src: &str, pairs: &mut Vec<(Span, Span)>) -> Vec<(Span, LexerError)> {
+    let mut errors = Vec::new();
+    let mut open = Vec::new();
+
+    for (i, ch) in src.char_indices() {
+        let span = Span {
+            start: i,
+            end: i + ch.len_utf8(),
         };
 
-        block.push(op);
+        match ch {
+            TOKEN_LOOP_BEGIN => open.push(span),
+            TOKEN_LOOP_END => match open.pop() {
+                Some(start) => pairs.push((start, span)),
+                None => errors.push((span, LexerError::SyntaxError(ch))),
+            },
+            _ if cfg!(not(feature = "comments")) && !is_command(ch) && !ch.is_whitespace() => {
+                errors.push((span, LexerError::SyntaxError(ch)));
+            }
+            _ => {}
+        }
     }
 
-    if is_closure {
-        Err(LexerError::UnclosedBlock)
+    errors.extend(
+        open.into_iter()
+            .map(|span| (span, LexerError::UnclosedBlock)),
+    );
+    errors.sort_by_key(|(span, _)| span.start);
+
+    errors
+}
+
+/// Convert a block back to Brainfuck source.
+///
+/// Pre-compiled patterns are written as the simplest loop or sequence of
+/// commands with the same effect, so any block can be converted, including
+/// optimized ones. The source of a block from [`lex_with_comments`] is
+/// identical to the source it was lexed from.
+///
+/// # Arguments
+///
+/// * `block` - The [`Block`] to convert.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::{lex, to_source};
+///
+/// let code = lex("+++[->++<]").unwrap();
+///
+/// assert_eq!(to_source(&code), "+++[->++<]");
+/// ```
+pub fn to_source(block: &Block) -> String {
+    let mut src = String::new();
+
+    for token in block {
+        write_token(token, &mut src);
+    }
+
+    src
+}
+
+/// Append the source of a single token.
+pub(crate) fn write_token(token: &Token, src: &mut String) {
+    match token {
+        Token::Increment(count) => repeat(src, TOKEN_INCREMENT, *count as usize),
+        Token::Decrement(count) => repeat(src, TOKEN_DECREMENT, *count as usize),
+        Token::Next(count) => repeat(src, TOKEN_NEXT, *count),
+        Token::Prev(count) => repeat(src, TOKEN_PREV, *count),
+        Token::Print => src.push(TOKEN_PRINT),
+        Token::Input => src.push(TOKEN_INPUT),
+        Token::Closure(block) => {
+            src.push(TOKEN_LOOP_BEGIN);
+            block.iter().for_each(|token| write_token(token, src));
+            src.push(TOKEN_LOOP_END);
+        }
+        #[cfg(feature = "debug_token")]
+        Token::Debug => src.push(TOKEN_DEBUG),
+        #[cfg(feature = "breakpoint_token")]
+        Token::Breakpoint => src.push(TOKEN_BREAKPOINT),
+        #[cfg(feature = "precompiled_patterns")]
+        Token::Pattern(pattern) => write_pattern(pattern, src),
+        Token::Comment(text) => src.push_str(text),
+    }
+}
+
+#[cfg(feature = "precompiled_patterns")]
+fn write_pattern(pattern: &PreCompiledPattern, src: &mut String) {
+    let set_to_zero = [TOKEN_LOOP_BEGIN, TOKEN_DECREMENT, TOKEN_LOOP_END];
+
+    match *pattern {
+        PreCompiledPattern::SetToZero => src.extend(set_to_zero),
+        PreCompiledPattern::Multiply {
+            dest_offset,
+            factor,
+        } => {
+            src.push(TOKEN_LOOP_BEGIN);
+            src.push(TOKEN_DECREMENT);
+            at_offset(src, dest_offset, |src| add(src, factor));
+            src.push(TOKEN_LOOP_END);
+        }
+        PreCompiledPattern::AddAt { offset, value } => {
+            at_offset(src, offset, |src| add(src, value))
+        }
+        PreCompiledPattern::SetAt { offset, value } => at_offset(src, offset, |src| {
+            src.extend(set_to_zero);
+            add(src, value);
+        }),
+    }
+}
+
+/// Write commands working on the cell at an offset from the current cell.
+#[cfg(feature = "precompiled_patterns")]
+fn at_offset<F>(src: &mut String, offset: isize, body: F)
+where
+    F: FnOnce(&mut String),
+{
+    let (to, back) = if offset < 0 {
+        (TOKEN_PREV, TOKEN_NEXT)
+    } else {
+        (TOKEN_NEXT, TOKEN_PREV)
+    };
+
+    repeat(src, to, offset.unsigned_abs());
+    body(src);
+    repeat(src, back, offset.unsigned_abs());
+}
+
+/// Append the shortest source adding a value to a cell, which subtracts
+/// instead of adding when the value has wrapped around.
+#[cfg(feature = "precompiled_patterns")]
+fn add(src: &mut String, value: u8) {
+    if value > 128 {
+        repeat(src, TOKEN_DECREMENT, 256 - value as usize);
     } else {
-        Ok(block)
+        repeat(src, TOKEN_INCREMENT, value as usize);
     }
 }
 
-fn optimize_block(block: &Block) -> Block {
-    block
-        .into_iter()
-        .map(|token| match token {
-            Token::Closure(block) => Token::Closure(optimize_block(block)),
-            _ => token.clone(),
-        })
-        .filter(|token| match token {
-            Token::Closure(block) => !block.is_empty(),
-            _ => true,
+fn repeat(src: &mut String, ch: char, count: usize) {
+    src.extend(core::iter::repeat_n(ch, count));
+}
+
+/// Combine repeated arithmetic and movement characters with their count and
+/// the span from the first to the last of them.
+///
+/// Arithmetic is never combined past [`u8::MAX`], so the count of every run
+/// fits in its [`Token`].
+fn coalesce<T>(chars: T) -> impl Iterator<Item = (char, u32, Span)>
+where
+    T: Iterator<Item = (usize, char)>,
+{
+    chars
+        .map(|(i, c)| {
+            let span = Span {
+                start: i,
+                end: i + c.len_utf8(),
+            };
+            (c, 1, span)
         })
-        .map(|token| match token {
-            #[cfg(feature = "precompiled_patterns")]
-            Token::Closure(block) => match &block[..] {
-                &[Token::Decrement(1)] => Token::Pattern(PreCompiledPattern::SetToZero),
-                &[Token::Decrement(1), Token::Next(offset), Token::Increment(factor), Token::Prev(rev_offset)] if offset == rev_offset => Token::Pattern(PreCompiledPattern::Multiply { dest_offset: offset as isize, factor: factor }),
-                &[Token::Decrement(1), Token::Prev(offset), Token::Increment(factor), Token::Next(rev_offset)] if offset == rev_offset => Token::Pattern(PreCompiledPattern::Multiply { dest_offset: -(offset as isize), factor: factor }),
-                &[Token::Next(offset), Token::Increment(factor), Token::Prev(rev_offset), Token::Decrement(1)] if offset == rev_offset => Token::Pattern(PreCompiledPattern::Multiply { dest_offset: offset as isize, factor: factor }),
-                &[Token::Prev(offset), Token::Increment(factor), Token::Next(rev_offset), Token::Decrement(1)] if offset == rev_offset => Token::Pattern(PreCompiledPattern::Multiply { dest_offset: -(offset as isize), factor: factor }),
-                _ => Token::Closure(block),
-            },
-            _ => token,
+        .coalesce(|(c, n, first), (d, m, last)| {
+            let arithmetic = (c == TOKEN_INCREMENT || c == TOKEN_DECREMENT) && n < u8::MAX as u32;
+            let movement = c == TOKEN_NEXT || c == TOKEN_PREV;
+
+            if c == d && (arithmetic || movement) {
+                let span = Span {
+                    start: first.start,
+                    end: last.end,
+                };
+                Ok((c, n + m, span))
+            } else {
+                Err(((c, n, first), (d, m, last)))
+            }
         })
-        .collect()
+}
+
+/// Tokenize iterator to Brainfuck block, optionally recording the span of
+/// every token.
+fn tokenize_block<T>(
+    iter: T,
+    comments: bool,
+    max_depth: usize,
+    mut spans: Option<&mut Vec<Span>>,
+) -> Result<Block>
+where
+    T: Iterator<Item = (char, u32, Span)>,
+{
+    // The blocks the closures being tokenized are in, innermost last. Nesting
+    // is kept on this stack instead of by recursion, so deeply nested sources
+    // cannot overflow the native stack
+    let mut outer: Vec<Block> = vec![];
+    let mut block = vec![];
+
+    let error = 'tokenize: {
+        for (ch, count, span) in iter {
+            if let Some(spans) = spans.as_deref_mut() {
+                match (ch, block.last()) {
+                    // Runs of comments are a single token
+                    (_, Some(Token::Comment(_))) if comments && !is_command(ch) => {
+                        spans.last_mut().unwrap().end = span.end;
+                    }
+                    (TOKEN_LOOP_END, _) if outer.is_empty() => {}
+                    _ if comments || is_command(ch) => spans.push(span),
+                    _ => {}
+                }
+            }
+
+            let op = match ch {
+                TOKEN_INCREMENT => Token::Increment(count as u8),
+                TOKEN_DECREMENT => Token::Decrement(count as u8),
+                TOKEN_NEXT => Token::Next(count as usize),
+                TOKEN_PREV => Token::Prev(count as usize),
+                TOKEN_PRINT => Token::Print,
+                TOKEN_INPUT => Token::Input,
+                TOKEN_LOOP_BEGIN if outer.len() == max_depth => {
+                    break 'tokenize LexerError::NestingTooDeep(max_depth)
+                }
+                TOKEN_LOOP_BEGIN => {
+                    outer.push(core::mem::take(&mut block));
+                    continue;
+                }
+                TOKEN_LOOP_END => match outer.pop() {
+                    Some(parent) => Token::Closure(core::mem::replace(&mut block, parent)),
+                    None => break 'tokenize LexerError::SyntaxError(ch),
+                },
+                #[cfg(feature = "debug_token")]
+                TOKEN_DEBUG => Token::Debug,
+                #[cfg(feature = "breakpoint_token")]
+                TOKEN_BREAKPOINT => Token::Breakpoint,
+                _ if comments => {
+                    match block.last_mut() {
+                        Some(Token::Comment(text)) => text.push(ch),
+                        _ => block.push(Token::Comment(ch.to_string())),
+                    }
+                    continue;
+                }
+                #[cfg(feature = "comments")]
+                _ => continue,
+                #[cfg(not(feature = "comments"))]
+                _ => break 'tokenize LexerError::SyntaxError(ch),
+            };
+
+            block.push(op);
+        }
+
+        if outer.is_empty() {
+            return Ok(block);
+        }
+
+        LexerError::UnclosedBlock
+    };
+
+    // The unfinished blocks may be nested as deep as the limit
+    outer.into_iter().chain([block]).for_each(drop_block);
+    Err(error)
 }
 
 #[cfg(test)]
@@ -293,6 +789,40 @@ mod tests {
         assert_eq!(lex(src), Err(LexerError::SyntaxError(']')));
     }
 
+    #[test]
+    fn nesting_depth() {
+        let depth = 100_000;
+        let src = format!("+{}-{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(
+            lex(&src),
+            Err(LexerError::NestingTooDeep(DEFAULT_MAX_DEPTH))
+        );
+
+        let config = LexerConfig { max_depth: depth };
+        let block = lex_with_config(&src, &config).unwrap();
+        assert_eq!(block.len(), 2);
+        drop_block(block);
+
+        // The closures lexed before the error are dropped without recursing
+        let config = LexerConfig {
+            max_depth: depth + 1,
+        };
+        assert_eq!(
+            lex_with_config(format!("[{}", src), &config),
+            Err(LexerError::UnclosedBlock)
+        );
+
+        let config = LexerConfig { max_depth: 1 };
+        assert_eq!(
+            lex_with_config("[][]", &config).map(|block| block.len()),
+            Ok(2)
+        );
+        assert_eq!(
+            lex_with_config("[[]]", &config),
+            Err(LexerError::NestingTooDeep(1))
+        );
+    }
+
     #[test]
     fn whitespace() {
         let src = "+ +\n\n\n - -    ".to_string();
@@ -300,6 +830,27 @@ mod tests {
         assert_eq!(lex(src), Ok(expected));
     }
 
+    #[test]
+    fn unoptimized() {
+        let src = "[+][][-]".to_string();
+        let expected = vec![
+            Token::Closure(vec![Token::Increment(1)]),
+            Token::Closure(vec![]),
+            Token::Closure(vec![Token::Decrement(1)]),
+        ];
+        assert_eq!(lex_unoptimized(src), Ok(expected));
+
+        let src = "[][".to_string();
+        assert_eq!(lex_unoptimized(src), Err(LexerError::UnclosedBlock));
+    }
+
+    #[test]
+    fn optimize_unoptimized() {
+        let src = "++[->+++<]>[-][][<]".to_string();
+        let unoptimized = lex_unoptimized(src.clone()).unwrap();
+        assert_eq!(Ok(optimize(&unoptimized)), lex(src));
+    }
+
     #[cfg(feature = "comments")]
     #[test]
     fn comments() {
@@ -308,6 +859,64 @@ mod tests {
         assert_eq!(lex(src), Ok(expected));
     }
 
+    #[test]
+    fn kept_comments() {
+        let src = "Add two ++\n[- loop ]".to_string();
+        let expected = vec![
+            Token::Comment("Add two ".to_string()),
+            Token::Increment(2),
+            Token::Comment("\n".to_string()),
+            Token::Closure(vec![
+                Token::Decrement(1),
+                Token::Comment(" loop ".to_string()),
+            ]),
+        ];
+        assert_eq!(lex_with_comments(src), Ok(expected));
+
+        let src = "] no closure".to_string();
+        assert_eq!(lex_with_comments(src), Err(LexerError::SyntaxError(']')));
+    }
+
+    #[test]
+    fn source_round_trip() {
+        let src = "Add two ++\n[->+<] then print it.\n";
+        let code = lex_with_comments(src).unwrap();
+        assert_eq!(to_source(&code), src);
+    }
+
+    #[test]
+    fn spans() {
+        let src = "++ +\n[ - ]\t.";
+        let (code, spans) = lex_with_spans(src).unwrap();
+        let text: Vec<_> = spans
+            .iter()
+            .map(|span| &src[span.start..span.end])
+            .collect();
+        assert_eq!(code, lex_unoptimized(src).unwrap());
+        assert_eq!(text, ["++ +", "[", "-", "]", "."]);
+
+        let (_, spans) = lex_with_spans(&"+".repeat(300)).unwrap();
+        assert_eq!(
+            spans,
+            [
+                Span { start: 0, end: 255 },
+                Span {
+                    start: 255,
+                    end: 300
+                }
+            ]
+        );
+
+        assert_eq!(lex_with_spans("[[]"), Err(LexerError::UnclosedBlock));
+    }
+
+    #[test]
+    fn long_runs() {
+        let src = "+".repeat(300);
+        let expected = vec![Token::Increment(255), Token::Increment(45)];
+        assert_eq!(lex_unoptimized(src), Ok(expected));
+    }
+
     #[cfg(feature = "debug_token")]
     #[test]
     fn debug_token() {
@@ -316,6 +925,19 @@ mod tests {
         assert_eq!(lex(src), Ok(expected));
     }
 
+    #[cfg(feature = "breakpoint_token")]
+    #[test]
+    fn breakpoint_token() {
+        let src = "+@[-@]".to_string();
+        let expected = vec![
+            Token::Increment(1),
+            Token::Breakpoint,
+            Token::Closure(vec![Token::Decrement(1), Token::Breakpoint]),
+        ];
+        assert_eq!(lex_unoptimized(src.clone()), Ok(expected));
+        assert_eq!(to_source(&lex_unoptimized(src.clone()).unwrap()), src);
+    }
+
     #[cfg(feature = "precompiled_patterns")]
     mod precompiled_patterns {
         use super::*;
@@ -362,5 +984,69 @@ mod tests {
             ])];
             assert_eq!(lex(src), Ok(expected));
         }
+
+        #[test]
+        fn patterns_to_source() {
+            let src = "[-]>[->+++<]".to_string();
+            assert_eq!(to_source(&lex(src.clone()).unwrap()), src);
+
+            let block = vec![
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: 2,
+                    value: 1,
+                }),
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: -1,
+                    value: 2,
+                }),
+            ];
+            assert_eq!(to_source(&block), ">>+<<<[-]++>");
+        }
+
+        #[test]
+        fn wrapped_patterns_to_source() {
+            let block = vec![
+                Token::Pattern(PreCompiledPattern::Multiply {
+                    dest_offset: 1,
+                    factor: 255,
+                }),
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: -1,
+                    value: 254,
+                }),
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: -3,
+                    value: 129,
+                }),
+            ];
+            let expected = format!("[->-<]<--><<<[-]{}>>>", "-".repeat(127));
+            assert_eq!(to_source(&block), expected);
+        }
+    }
+
+    #[test]
+    fn diagnostics() {
+        let errors = diagnose("[]]\n[+[");
+        let errors: Vec<_> = errors.iter().map(|(span, e)| (span.start, *e)).collect();
+
+        assert_eq!(
+            errors,
+            [
+                (2, LexerError::SyntaxError(']')),
+                (4, LexerError::UnclosedBlock),
+                (6, LexerError::UnclosedBlock),
+            ]
+        );
+        assert!(diagnose("+[->+<]").is_empty());
+    }
+
+    #[test]
+    fn brackets() {
+        let pairs: Vec<_> = bracket_pairs("][[-]+]]")
+            .iter()
+            .map(|(open, close)| (open.start, close.start))
+            .collect();
+
+        assert_eq!(pairs, [(2, 4), (1, 6)]);
     }
 }
@@ -1,8 +1,35 @@
 //! This crate provides a lexer for Brainfuck code.
+//!
+//! The crate only needs an allocator, so it can be used without the standard
+//! library by disabling the default `std` feature. Preprocessing includes
+//! files from the file system, and is only available with it.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+pub mod analysis;
+#[cfg(feature = "serde")]
+pub mod binary;
+pub mod builder;
 pub mod error;
+pub mod explain;
+pub mod formatter;
+pub mod generate;
+pub mod incremental;
+pub mod ir;
 pub mod lexer;
+pub mod optimizer;
+#[cfg(feature = "std")]
+pub mod preprocessor;
+pub mod symbols;
 
-pub use lexer::{lex, Block, Token};
+pub use builder::Program;
+pub use lexer::{
+    bracket_pairs, diagnose, lex, lex_unoptimized, lex_with_comments, lex_with_config,
+    lex_with_spans, to_source, Block, LexerConfig, Span, Token,
+};
+pub use optimizer::optimize;
+#[cfg(feature = "std")]
+pub use preprocessor::preprocess;
@@ -0,0 +1,1012 @@
+//! Optimization of lexed Brainfuck code.
+//!
+//! The optimizer is made up of a list of named [`Pass`]es, each of which
+//! rewrites the [`Op`]s of a program to cheaper but equivalent ones. Which
+//! passes are run is selected with an [`OptLevel`], and passes can be
+//! individually disabled.
+
+use crate::ir::{lower, raise, Op};
+use crate::lexer::Block;
+#[cfg(feature = "precompiled_patterns")]
+use crate::lexer::PreCompiledPattern;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// How much to optimize a program.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OptLevel {
+    /// Leave the program as it was written.
+    None,
+    /// Remove empty closures and recognize common patterns.
+    #[default]
+    Basic,
+    /// Also combine and cancel out tokens, and fuse pointer movement into
+    /// the tokens using it.
+    Aggressive,
+}
+
+/// A single named optimization pass.
+#[derive(Debug, Clone, Copy)]
+pub struct Pass {
+    /// The name used to refer to the pass.
+    pub name: &'static str,
+    /// Short description of what the pass does.
+    pub description: &'static str,
+    /// The lowest level the pass is run at.
+    pub level: OptLevel,
+    run: fn(&[Op]) -> Vec<Op>,
+}
+
+impl Pass {
+    /// Run the pass on the operations of a program.
+    pub fn run(&self, ops: &[Op]) -> Vec<Op> {
+        (self.run)(ops)
+    }
+}
+
+/// All optimization passes, in the order they are run.
+pub const PASSES: &[Pass] = &[
+    Pass {
+        name: "coalesce",
+        description: "Combine repeated tokens",
+        level: OptLevel::Aggressive,
+        run: coalesce,
+    },
+    Pass {
+        name: "cancel",
+        description: "Cancel out opposite tokens",
+        level: OptLevel::Aggressive,
+        run: cancel,
+    },
+    Pass {
+        name: "empty-loops",
+        description: "Remove empty closures",
+        level: OptLevel::Basic,
+        run: remove_empty_loops,
+    },
+    #[cfg(feature = "precompiled_patterns")]
+    Pass {
+        name: "zero",
+        description: "Recognize closures setting a cell to zero",
+        level: OptLevel::Basic,
+        run: set_to_zero,
+    },
+    #[cfg(feature = "precompiled_patterns")]
+    Pass {
+        name: "multiply",
+        description: "Recognize closures multiplying a cell into another",
+        level: OptLevel::Basic,
+        run: multiply,
+    },
+    Pass {
+        name: "dead-code",
+        description: "Remove closures and tokens on cells known to be zero",
+        level: OptLevel::Aggressive,
+        run: dead_code_elimination,
+    },
+    #[cfg(feature = "precompiled_patterns")]
+    Pass {
+        name: "offset",
+        description: "Fuse pointer movement into the tokens between them",
+        level: OptLevel::Aggressive,
+        run: offset_fusion,
+    },
+    #[cfg(feature = "precompiled_patterns")]
+    Pass {
+        name: "fold",
+        description: "Evaluate the start of the program ahead of time",
+        level: OptLevel::Aggressive,
+        run: constant_folding,
+    },
+];
+
+/// Names of the passes which assume arithmetic on cells wraps around.
+///
+/// They combine, evaluate or replace arithmetic as if every value is taken
+/// modulo 256, so they must be disabled for interpreters where cells
+/// saturate or overflow is an error.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::lex_unoptimized;
+/// use brainfuck_lexer::optimizer::{OptLevel, Optimizer, WRAPPING_PASSES};
+///
+/// let optimizer = WRAPPING_PASSES
+///     .iter()
+///     .fold(Optimizer::new(OptLevel::Aggressive), |optimizer, pass| {
+///         optimizer.disable(pass)
+///     });
+/// let code = optimizer.optimize(&lex_unoptimized("+-[-]").unwrap());
+///
+/// assert_eq!(code, lex_unoptimized("+-[-]").unwrap());
+/// ```
+pub const WRAPPING_PASSES: &[&str] = &[
+    "coalesce",
+    "cancel",
+    "zero",
+    "multiply",
+    "dead-code",
+    "offset",
+    "fold",
+];
+
+/// Names of the passes which track the values of cells around the pointer.
+///
+/// They assume every cell within [`MAX_DISTANCE`] of where they start is a
+/// distinct cell, so they are skipped for tapes wrapping around sooner.
+const TRACKING_PASSES: &[&str] = &["dead-code", "fold"];
+
+/// Maximum number of tokens evaluated by the constant folding pass.
+#[cfg(feature = "precompiled_patterns")]
+const FOLD_MAX_STEPS: usize = 1_000_000;
+/// Maximum distance the pointer is followed by passes which track cells.
+///
+/// Cells 30000 cells apart are the same cell on the default wrapping tape,
+/// so cells within this distance on either side are always distinct.
+const MAX_DISTANCE: isize = 4096;
+
+/// Configurable pipeline of optimization passes.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::lex_unoptimized;
+/// use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+///
+/// let src = "+-[->+<]".to_string();
+/// let optimizer = Optimizer::new(OptLevel::Aggressive).disable("multiply");
+/// let code = optimizer.optimize(&lex_unoptimized(src).unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Optimizer {
+    level: OptLevel,
+    disabled: Vec<String>,
+    tape_len: Option<usize>,
+}
+
+impl Optimizer {
+    /// Create an optimizer running every pass of the given level.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The [`OptLevel`] to select passes by.
+    pub fn new(level: OptLevel) -> Self {
+        Self {
+            level,
+            disabled: Vec::new(),
+            tape_len: None,
+        }
+    }
+
+    /// Set the number of cells of the wrapping tape the program is run on.
+    ///
+    /// Programs are otherwise assumed to run on a tape of at least 30000
+    /// cells, or one which does not wrap around. The passes which track
+    /// cells are skipped for tapes too short to keep the cells they track
+    /// apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The number of cells of the tape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+    ///
+    /// let optimizer = Optimizer::new(OptLevel::Aggressive).with_tape_len(16);
+    ///
+    /// assert!(optimizer.passes().all(|pass| pass.name != "dead-code"));
+    /// ```
+    pub fn with_tape_len(mut self, len: usize) -> Self {
+        self.tape_len = Some(len);
+        self
+    }
+
+    /// Disable a pass by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the [`Pass`] to disable.
+    pub fn disable(mut self, name: &str) -> Self {
+        self.disabled.push(name.to_string());
+        self
+    }
+
+    /// The passes which will be run, in order.
+    pub fn passes(&self) -> impl Iterator<Item = &'static Pass> + '_ {
+        let short_tape = self
+            .tape_len
+            .is_some_and(|len| len <= 2 * MAX_DISTANCE as usize);
+
+        PASSES.iter().filter(move |pass| {
+            pass.level <= self.level
+                && !self.disabled.iter().any(|name| name == pass.name)
+                && !(short_tape && TRACKING_PASSES.contains(&pass.name))
+        })
+    }
+
+    /// Optimize a block.
+    ///
+    /// The block is lowered to [`Op`]s for the passes, and raised back to
+    /// tokens afterwards. Without any passes to run, the block is left
+    /// exactly as it was, including its comments.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The [`Block`] to optimize.
+    pub fn optimize(&self, block: &Block) -> Block {
+        let mut passes = self.passes().peekable();
+        if passes.peek().is_none() {
+            return block.clone();
+        }
+
+        raise(&passes.fold(lower(block), |ops, pass| pass.run(&ops)))
+    }
+}
+
+/// Optimize Brainfuck block.
+///
+/// Runs every pass of the default [`OptLevel`]: empty closures are removed,
+/// and closures matching a known pattern are replaced by a
+/// [`Token::Pattern`](crate::lexer::Token::Pattern) when the `precompiled_patterns` feature is enabled.
+///
+/// # Arguments
+///
+/// * `block` - The [`Block`] to optimize.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lexer::lex_unoptimized;
+/// use brainfuck_lexer::optimizer::optimize;
+///
+/// let src = "+[]".to_string();
+/// let code = optimize(&lex_unoptimized(src).unwrap());
+///
+/// assert_eq!(code.len(), 1);
+/// ```
+pub fn optimize(block: &Block) -> Block {
+    Optimizer::default().optimize(block)
+}
+
+/// Apply a rewrite to the operations and the bodies of all loops in them.
+fn rewrite<F>(ops: &[Op], f: &F) -> Vec<Op>
+where
+    F: Fn(Vec<Op>) -> Vec<Op>,
+{
+    f(ops
+        .iter()
+        .map(|op| match op {
+            Op::Loop(body) => Op::Loop(rewrite(body, f)),
+            _ => op.clone(),
+        })
+        .collect())
+}
+
+/// Whether an addition decrements the cell by one, wrapping around.
+#[cfg(feature = "precompiled_patterns")]
+fn is_decrement(value: i16) -> bool {
+    value as u8 == u8::MAX
+}
+
+/// Create the operation moving the pointer by a distance, if it moves at all.
+#[cfg(feature = "precompiled_patterns")]
+fn move_op(distance: isize) -> Option<Op> {
+    (distance != 0).then_some(Op::Move(distance))
+}
+
+/// Merge adjacent additions and movements.
+///
+/// With `opposite` set, values with opposite signs are merged too. Values
+/// cancelling out are removed entirely.
+fn merge(ops: Vec<Op>, opposite: bool) -> Vec<Op> {
+    let mut res: Vec<Op> = Vec::with_capacity(ops.len());
+
+    for op in ops {
+        let merged = match (res.last(), &op) {
+            (Some(&Op::Add(a)), &Op::Add(b)) if opposite || a.signum() == b.signum() => {
+                // Keep the sum within a cell, so it can't overflow
+                Some(Op::Add((a + b) % 256))
+            }
+            (Some(&Op::Move(a)), &Op::Move(b)) if opposite || a.signum() == b.signum() => {
+                Some(Op::Move(a + b))
+            }
+            _ => None,
+        };
+
+        match merged {
+            Some(Op::Add(0) | Op::Move(0)) => {
+                res.pop();
+            }
+            Some(merged) => *res.last_mut().unwrap() = merged,
+            None => res.push(op),
+        }
+    }
+
+    res
+}
+
+/// Combine repeated tokens, such as `+` and `+`.
+fn coalesce(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| merge(ops, false))
+}
+
+/// Cancel out opposite tokens, such as `+` and `-`.
+fn cancel(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| merge(ops, true))
+}
+
+/// Remove closures with no tokens in them.
+fn remove_empty_loops(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| {
+        ops.into_iter()
+            .filter(|op| match op {
+                Op::Loop(body) => !body.is_empty(),
+                _ => true,
+            })
+            .collect()
+    })
+}
+
+/// Replace closures decrementing the current cell to zero.
+#[cfg(feature = "precompiled_patterns")]
+fn set_to_zero(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| {
+        ops.into_iter()
+            .map(|op| match op {
+                Op::Loop(body) => match body[..] {
+                    [Op::Add(value)] if is_decrement(value) => {
+                        Op::Pattern(PreCompiledPattern::SetToZero)
+                    }
+                    _ => Op::Loop(body),
+                },
+                _ => op,
+            })
+            .collect()
+    })
+}
+
+/// Replace closures multiplying the current cell into another cell.
+///
+/// The cell is multiplied by whatever the closure adds to the other cell,
+/// so subtracting from it multiplies by a wrapped around factor.
+#[cfg(feature = "precompiled_patterns")]
+fn multiply(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| {
+        ops.into_iter()
+            .map(|op| match op {
+                Op::Loop(body) => match body[..] {
+                    [Op::Add(dec), Op::Move(offset), Op::Add(factor), Op::Move(back)]
+                    | [Op::Move(offset), Op::Add(factor), Op::Move(back), Op::Add(dec)]
+                        if is_decrement(dec) && offset != 0 && offset == -back =>
+                    {
+                        Op::Pattern(PreCompiledPattern::Multiply {
+                            dest_offset: offset,
+                            factor: factor as u8,
+                        })
+                    }
+                    _ => Op::Loop(body),
+                },
+                _ => op,
+            })
+            .collect()
+    })
+}
+
+/// What is known about the memory around the pointer.
+#[derive(Debug, Clone, Default)]
+struct KnownCells {
+    /// Known values of cells, by their offset from the pointer.
+    cells: BTreeMap<isize, u8>,
+    /// Whether every cell not in `cells` is known to be zero.
+    rest_zero: bool,
+    /// Distance the pointer has moved since the analysis started.
+    ptr: isize,
+}
+
+impl KnownCells {
+    /// Check that a cell is close enough to where the analysis started to
+    /// not be the same cell as any other tracked cell.
+    fn tracked(&self, offset: isize) -> bool {
+        (self.ptr + offset).abs() <= MAX_DISTANCE
+    }
+
+    fn get(&self, offset: isize) -> Option<u8> {
+        if !self.tracked(offset) {
+            return None;
+        }
+
+        match self.cells.get(&offset) {
+            Some(&value) => Some(value),
+            None if self.rest_zero => Some(0),
+            None => None,
+        }
+    }
+
+    fn set(&mut self, offset: isize, value: Option<u8>) {
+        if !self.tracked(offset) {
+            // The cell may be any of the tracked cells
+            self.rest_zero = false;
+            self.cells.clear();
+            return;
+        }
+
+        match value {
+            Some(value) => self.cells.insert(offset, value),
+            None if self.rest_zero => {
+                // Forgetting a single cell would make it look like zero
+                self.rest_zero = false;
+                self.cells.clear();
+                None
+            }
+            None => self.cells.remove(&offset),
+        };
+    }
+
+    fn shift(&mut self, distance: isize) {
+        self.ptr += distance;
+        if !self.tracked(0) {
+            // Start over from the current cell
+            *self = Self::default();
+            return;
+        }
+
+        self.cells = self
+            .cells
+            .iter()
+            .map(|(&offset, &value)| (offset - distance, value))
+            .collect();
+    }
+
+    /// Update the knowledge with the effect of a token which is kept.
+    fn apply(&mut self, op: &Op) {
+        match *op {
+            Op::Add(x) => self.set(0, self.get(0).map(|v| v.wrapping_add(x as u8))),
+            Op::Move(distance) => self.shift(distance),
+            Op::Input => self.set(0, None),
+            Op::Loop(_) => {
+                // The loop can have done anything, except leaving the current
+                // cell as anything but zero
+                *self = Self::default();
+                self.set(0, Some(0));
+            }
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(ref pattern) => match *pattern {
+                PreCompiledPattern::SetToZero => self.set(0, Some(0)),
+                PreCompiledPattern::Multiply {
+                    dest_offset,
+                    factor,
+                } => {
+                    let dest = match (self.get(0), self.get(dest_offset)) {
+                        (Some(src), Some(dest)) => {
+                            Some(dest.wrapping_add(src.wrapping_mul(factor)))
+                        }
+                        _ => None,
+                    };
+                    self.set(dest_offset, dest);
+                    self.set(0, Some(0));
+                }
+                PreCompiledPattern::AddAt { offset, value } => {
+                    self.set(offset, self.get(offset).map(|v| v.wrapping_add(value)))
+                }
+                PreCompiledPattern::SetAt { offset, value } => self.set(offset, Some(value)),
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Remove closures which can never run and tokens with no effect.
+///
+/// A closure can never run when the current cell is known to be zero, such
+/// as at the start of the program, right after another closure or right
+/// after the cell has been set to zero.
+fn dead_code_elimination(ops: &[Op]) -> Vec<Op> {
+    let known = KnownCells {
+        cells: BTreeMap::new(),
+        rest_zero: true,
+        ptr: 0,
+    };
+
+    eliminate_dead_code(ops, known)
+}
+
+fn eliminate_dead_code(ops: &[Op], mut known: KnownCells) -> Vec<Op> {
+    let mut res: Vec<Op> = Vec::with_capacity(ops.len());
+
+    for op in ops {
+        let op = match op {
+            Op::Loop(_) if known.get(0) == Some(0) => continue,
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(PreCompiledPattern::SetToZero) if known.get(0) == Some(0) => continue,
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(PreCompiledPattern::SetAt { offset, value })
+                if known.get(*offset) == Some(*value) =>
+            {
+                continue
+            }
+            // Nothing is known about the memory when a closure is entered,
+            // except that the current cell is not zero
+            Op::Loop(body) => Op::Loop(eliminate_dead_code(body, KnownCells::default())),
+            op => op.clone(),
+        };
+
+        known.apply(&op);
+        res.push(op);
+    }
+
+    res
+}
+
+/// Replace arithmetic between movements with arithmetic at an offset.
+///
+/// Movement is delayed until the next token which depends on the pointer, so
+/// `>+>+<` becomes adding one to the next two cells, followed by moving one
+/// cell to the right.
+#[cfg(feature = "precompiled_patterns")]
+fn offset_fusion(ops: &[Op]) -> Vec<Op> {
+    rewrite(ops, &|ops| {
+        let mut res: Vec<Op> = Vec::with_capacity(ops.len());
+        let mut offset = 0;
+
+        for op in ops {
+            match op {
+                Op::Move(distance) => offset += distance,
+                Op::Add(value) if offset != 0 => {
+                    res.push(Op::Pattern(PreCompiledPattern::AddAt {
+                        offset,
+                        value: value as u8,
+                    }));
+                }
+                op => {
+                    res.extend(move_op(offset));
+                    res.push(op);
+                    offset = 0;
+                }
+            }
+        }
+
+        res.extend(move_op(offset));
+        res
+    })
+}
+
+/// Memory and pointer of a program being evaluated by the optimizer.
+#[cfg(feature = "precompiled_patterns")]
+#[derive(Debug, Clone, Default)]
+struct FoldState {
+    memory: BTreeMap<isize, u8>,
+    ptr: isize,
+    steps: usize,
+}
+
+#[cfg(feature = "precompiled_patterns")]
+impl FoldState {
+    /// Get a cell, or `None` if it is too far from the first cell to be
+    /// known not to wrap around the tape.
+    fn cell(&mut self, offset: isize) -> Option<&mut u8> {
+        let cell = self.ptr + offset;
+        if cell.abs() > MAX_DISTANCE {
+            return None;
+        }
+
+        Some(self.memory.entry(cell).or_insert(0))
+    }
+
+    /// Evaluate an operation, returning `None` if it cannot be evaluated
+    /// ahead of time.
+    fn run(&mut self, op: &Op) -> Option<()> {
+        self.steps += 1;
+        if self.steps > FOLD_MAX_STEPS {
+            return None;
+        }
+
+        match *op {
+            Op::Add(x) => {
+                let cell = self.cell(0)?;
+                *cell = cell.wrapping_add(x as u8);
+            }
+            Op::Move(distance) => {
+                self.ptr += distance;
+                self.cell(0)?;
+            }
+            Op::Loop(ref body) => {
+                while *self.cell(0)? != 0 {
+                    for op in body {
+                        self.run(op)?;
+                    }
+
+                    self.steps += 1;
+                    if self.steps > FOLD_MAX_STEPS {
+                        return None;
+                    }
+                }
+            }
+            Op::Pattern(PreCompiledPattern::SetToZero) => *self.cell(0)? = 0,
+            Op::Pattern(PreCompiledPattern::Multiply {
+                dest_offset,
+                factor,
+            }) => {
+                let mul_res = self.cell(0)?.wrapping_mul(factor);
+                let dest = self.cell(dest_offset)?;
+                *dest = dest.wrapping_add(mul_res);
+                *self.cell(0)? = 0;
+            }
+            Op::Pattern(PreCompiledPattern::AddAt { offset, value }) => {
+                let cell = self.cell(offset)?;
+                *cell = cell.wrapping_add(value);
+            }
+            Op::Pattern(PreCompiledPattern::SetAt { offset, value }) => *self.cell(offset)? = value,
+            _ => return None,
+        }
+
+        Some(())
+    }
+}
+
+/// Evaluate the program from its start until it reads input.
+///
+/// As the memory is known to be zero when the program starts, everything up
+/// to the first [`Op::Input`] can be evaluated ahead of time and replaced
+/// by setting the cells to their resulting values. Closures which print or
+/// don't finish within a fixed number of steps stop the evaluation early.
+/// Only the top level block is folded, as it is the only block where the
+/// memory is known.
+#[cfg(feature = "precompiled_patterns")]
+fn constant_folding(ops: &[Op]) -> Vec<Op> {
+    let mut res: Vec<Op> = Vec::with_capacity(ops.len());
+    let mut state = FoldState::default();
+    let mut flushed = FoldState::default();
+
+    // Emit operations bringing the memory and pointer of the program to the
+    // evaluated state
+    let flush = |res: &mut Vec<Op>, state: &FoldState, flushed: &mut FoldState| {
+        for (&cell, &value) in &state.memory {
+            if flushed.memory.get(&cell).copied().unwrap_or(0) != value {
+                let offset = cell - flushed.ptr;
+                res.push(Op::Pattern(PreCompiledPattern::SetAt { offset, value }));
+            }
+        }
+
+        res.extend(move_op(state.ptr - flushed.ptr));
+        flushed.memory.clone_from(&state.memory);
+        flushed.ptr = state.ptr;
+    };
+
+    for (i, op) in ops.iter().enumerate() {
+        if *op == Op::Print {
+            flush(&mut res, &state, &mut flushed);
+            res.push(Op::Print);
+            continue;
+        }
+
+        let mut next = state.clone();
+        if next.run(op).is_none() {
+            flush(&mut res, &state, &mut flushed);
+            res.extend_from_slice(&ops[i..]);
+            return res;
+        }
+
+        state = next;
+    }
+
+    flush(&mut res, &state, &mut flushed);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{lex_unoptimized, Token};
+
+    fn optimize_with(src: &str, optimizer: &Optimizer) -> Block {
+        optimizer.optimize(&lex_unoptimized(src).unwrap())
+    }
+
+    #[test]
+    fn no_optimization() {
+        let src = "+-[][-]";
+        let optimizer = Optimizer::new(OptLevel::None);
+        assert_eq!(
+            optimize_with(src, &optimizer),
+            lex_unoptimized(src).unwrap()
+        );
+    }
+
+    #[test]
+    fn coalesce_tokens() {
+        let block = vec![
+            Token::Increment(1),
+            Token::Increment(2),
+            Token::Prev(1),
+            Token::Prev(4),
+        ];
+        let expected = vec![Token::Increment(3), Token::Prev(5)];
+        assert_eq!(raise(&coalesce(&lower(&block))), expected);
+
+        // Only values going the same way are combined
+        let block = lex_unoptimized("+-").unwrap();
+        assert_eq!(raise(&coalesce(&lower(&block))), block);
+    }
+
+    #[test]
+    fn cancel_tokens() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+
+        let expected = vec![Token::Increment(1)];
+        assert_eq!(optimize_with("++-", &optimizer), expected);
+
+        let expected = vec![Token::Print];
+        assert_eq!(optimize_with(">+-<.", &optimizer), expected);
+
+        let expected = vec![Token::Prev(2)];
+        assert_eq!(optimize_with("><<<", &optimizer), expected);
+
+        // The result keeps the direction of the larger value
+        let expected = vec![Token::Decrement(2)];
+        assert_eq!(optimize_with("+---", &optimizer), expected);
+    }
+
+    #[test]
+    fn cancel_wraps_around() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+        let block = vec![
+            Token::Increment(200),
+            Token::Increment(100),
+            Token::Decrement(255),
+            Token::Decrement(255),
+        ];
+
+        let expected = vec![Token::Decrement(210)];
+        assert_eq!(optimizer.optimize(&block), expected);
+    }
+
+    #[test]
+    fn cancel_creates_empty_loop() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+        let expected = vec![Token::Print];
+        assert_eq!(optimize_with("[+-].", &optimizer), expected);
+    }
+
+    #[test]
+    fn disable_pass() {
+        let optimizer = Optimizer::new(OptLevel::Basic).disable("empty-loops");
+        assert!(optimizer.passes().all(|pass| pass.name != "empty-loops"));
+
+        let expected = vec![Token::Closure(vec![])];
+        assert_eq!(optimize_with("[]", &optimizer), expected);
+    }
+
+    /// Count the tokens in a block, including the blocks of closures.
+    fn count_tokens(block: &Block) -> usize {
+        block
+            .iter()
+            .map(|token| match token {
+                Token::Closure(block) => 1 + count_tokens(block),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn dead_code() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+
+        let expected = vec![Token::Increment(1)];
+        assert_eq!(optimize_with("[.]+", &optimizer), expected);
+
+        let expected = vec![Token::Input, Token::Closure(vec![Token::Print])];
+        assert_eq!(optimize_with(",[.][.]", &optimizer), expected);
+
+        let expected = vec![Token::Increment(1), Token::Next(1), Token::Print];
+        assert_eq!(optimize_with("+>[.].", &optimizer), expected);
+
+        let expected = vec![
+            Token::Increment(1),
+            Token::Next(1),
+            Token::Input,
+            Token::Prev(1),
+            Token::Closure(vec![Token::Next(1), Token::Closure(vec![Token::Print])]),
+        ];
+        assert_eq!(optimize_with("+>,<[>[.]]", &optimizer), expected);
+    }
+
+    #[test]
+    fn dead_code_around_tape() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+
+        // Moving 30000 cells left from the first cell wraps around to it
+        let src = format!("+{}[.-]", "<".repeat(30_000));
+        let expected = vec![
+            Token::Increment(1),
+            Token::Prev(30_000),
+            Token::Closure(vec![Token::Print, Token::Decrement(1)]),
+        ];
+        assert_eq!(optimize_with(&src, &optimizer), expected);
+    }
+
+    #[test]
+    fn short_tape() {
+        // Moving 16 cells right on a tape of 16 cells wraps around to the
+        // first cell
+        let src = format!("+{}[.[-]]", ">".repeat(16));
+        let optimizer = Optimizer::new(OptLevel::Aggressive);
+        assert_eq!(optimize_with(&src, &optimizer).len(), 2);
+
+        let optimizer = optimizer.with_tape_len(16);
+        assert!(optimizer.passes().all(|pass| pass.name != "fold"));
+        assert_eq!(optimize_with(&src, &optimizer).len(), 3);
+    }
+
+    #[test]
+    fn dead_code_in_real_programs() {
+        let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+        let without = Optimizer::new(OptLevel::Aggressive)
+            .disable("fold")
+            .disable("dead-code");
+
+        // Programs commonly start with a comment in a closure which is never
+        // run, as the first cell is always zero
+        let src = "[ Print the alphabet, one letter at a time. ]
+            ++++++++[>++++++++<-]>>++++++++++++++++++++++++++[<+.>-]";
+        let res = count_tokens(&optimize_with(src, &optimizer));
+        assert!(res < count_tokens(&optimize_with(src, &without)));
+
+        let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.[-][-]";
+        let res = count_tokens(&optimize_with(src, &optimizer));
+        assert!(res < count_tokens(&optimize_with(src, &without)));
+    }
+
+    #[test]
+    fn pass_names_are_unique() {
+        for (i, pass) in PASSES.iter().enumerate() {
+            assert!(PASSES[i + 1..].iter().all(|other| other.name != pass.name));
+        }
+    }
+
+    #[cfg(feature = "precompiled_patterns")]
+    mod precompiled_patterns {
+        use super::*;
+
+        #[test]
+        fn disable_multiply() {
+            let optimizer = Optimizer::new(OptLevel::Basic).disable("multiply");
+            let expected = vec![Token::Closure(vec![
+                Token::Decrement(1),
+                Token::Next(1),
+                Token::Increment(1),
+                Token::Prev(1),
+            ])];
+            assert_eq!(optimize_with("[->+<]", &optimizer), expected);
+        }
+
+        #[test]
+        fn offset_fusion() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+
+            let expected = vec![Token::Pattern(PreCompiledPattern::AddAt {
+                offset: 2,
+                value: 3,
+            })];
+            assert_eq!(optimize_with(">>+++<<", &optimizer), expected);
+
+            let expected = vec![
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: -1,
+                    value: 255,
+                }),
+                Token::Prev(2),
+            ];
+            assert_eq!(optimize_with("<-<", &optimizer), expected);
+        }
+
+        #[test]
+        fn offset_fusion_in_closure() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive)
+                .disable("fold")
+                .disable("dead-code");
+            let expected = vec![Token::Closure(vec![
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: 1,
+                    value: 1,
+                }),
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: 2,
+                    value: 1,
+                }),
+                Token::Decrement(1),
+            ])];
+            assert_eq!(optimize_with("[>+>+<<-]", &optimizer), expected);
+        }
+
+        #[test]
+        fn constant_folding() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive);
+            let expected = vec![
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: 1,
+                    value: 5,
+                }),
+                Token::Next(1),
+                Token::Print,
+                Token::Input,
+                Token::Print,
+            ];
+            assert_eq!(optimize_with("++>+++<[->+<]>.,.", &optimizer), expected);
+        }
+
+        #[test]
+        fn constant_folding_stops_at_input() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive);
+            let expected = vec![
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: 0,
+                    value: 2,
+                }),
+                Token::Closure(vec![Token::Input]),
+                Token::Increment(1),
+            ];
+            assert_eq!(optimize_with("++[,]+", &optimizer), expected);
+        }
+
+        #[test]
+        fn constant_folding_stops_at_infinite_loop() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive);
+            let expected = vec![
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: 0,
+                    value: 1,
+                }),
+                Token::Closure(vec![Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: 1,
+                    value: 1,
+                })]),
+            ];
+            assert_eq!(optimize_with("+[>+<]", &optimizer), expected);
+        }
+
+        #[test]
+        fn constant_folding_stops_near_tape_edge() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive);
+
+            // Adding to the cell 30000 cells away adds to the first cell of
+            // a wrapping tape
+            let src = format!("+{}+{}.", ">".repeat(30_000), "<".repeat(30_000));
+            let expected = vec![
+                Token::Pattern(PreCompiledPattern::SetAt {
+                    offset: 0,
+                    value: 1,
+                }),
+                Token::Pattern(PreCompiledPattern::AddAt {
+                    offset: 30_000,
+                    value: 1,
+                }),
+                Token::Print,
+            ];
+            assert_eq!(optimize_with(&src, &optimizer), expected);
+        }
+
+        #[test]
+        fn multiply_before_offset_fusion() {
+            let optimizer = Optimizer::new(OptLevel::Aggressive).disable("fold");
+            let expected = vec![Token::Pattern(PreCompiledPattern::Multiply {
+                dest_offset: 1,
+                factor: 2,
+            })];
+            assert_eq!(optimize_with("[->++<]", &optimizer), expected);
+        }
+
+        #[test]
+        fn multiply_by_negative_factor() {
+            let optimizer = Optimizer::new(OptLevel::Basic);
+            let expected = vec![Token::Pattern(PreCompiledPattern::Multiply {
+                dest_offset: -2,
+                factor: 255,
+            })];
+            assert_eq!(optimize_with("[<<->>-]", &optimizer), expected);
+        }
+    }
+}
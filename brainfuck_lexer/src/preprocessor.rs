@@ -0,0 +1,293 @@
+//! Expansion of preprocessor directives to plain Brainfuck.
+//!
+//! Directives start with `@` and can be placed anywhere in the source, as
+//! none of their characters are Brainfuck commands:
+//!
+//! * `@include "file.b"` is replaced with the preprocessed content of the
+//!   file, looked up relative to the including file and then in the include
+//!   directories.
+//! * `@define NAME body` defines a macro with the rest of the line as its
+//!   body, and is replaced with nothing.
+//! * `@NAME` is replaced with the preprocessed body of the macro.
+//! * `@repeat 10 { body }` is replaced with the preprocessed body repeated
+//!   the given number of times.
+//!
+//! An `@` which is not followed by a name is left untouched. With the
+//! `breakpoint_token` feature, this keeps breakpoints in preprocessed source,
+//! as long as they are not directly followed by a name: `@ here` is a
+//! breakpoint, but `@here` is a macro.
+
+use crate::error::PreprocessorError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Specialized [`Result`] type for preprocessing.
+pub type Result<T> = std::result::Result<T, PreprocessorError>;
+
+/// Options for preprocessing Brainfuck source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreprocessorConfig {
+    /// Directory to look up includes of the source in first, instead of the
+    /// current directory.
+    pub base_dir: Option<PathBuf>,
+    /// Directories to look up includes in when they are not found relative
+    /// to the including file.
+    pub include_dirs: Vec<PathBuf>,
+    /// Maximum number of nested includes and macros.
+    pub max_depth: usize,
+}
+
+impl Default for PreprocessorConfig {
+    fn default() -> Self {
+        Self {
+            base_dir: None,
+            include_dirs: Vec::new(),
+            max_depth: 64,
+        }
+    }
+}
+
+/// Expand the preprocessor directives of Brainfuck source.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to preprocess.
+/// * `config` - The [`PreprocessorConfig`] to preprocess with.
+///
+/// # Errors
+///
+/// If an include cannot be read, a directive is malformed, an undefined
+/// macro is used or the expansion is nested deeper than allowed, a
+/// [`PreprocessorError`] will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::preprocessor::{preprocess, PreprocessorConfig};
+///
+/// let src = "@define CLEAR [-]\n@repeat 3 { + } @CLEAR";
+/// let expanded = preprocess(src, &PreprocessorConfig::default()).unwrap();
+///
+/// assert_eq!(expanded, "\n +  +  +  [-]");
+/// ```
+pub fn preprocess(src: &str, config: &PreprocessorConfig) -> Result<String> {
+    let mut preprocessor = Preprocessor {
+        config,
+        macros: HashMap::new(),
+        depth: 0,
+    };
+
+    preprocessor.expand(src, config.base_dir.as_deref())
+}
+
+struct Preprocessor<'a> {
+    config: &'a PreprocessorConfig,
+    macros: HashMap<String, String>,
+    depth: usize,
+}
+
+impl Preprocessor<'_> {
+    /// Expand the directives of source found in a directory.
+    fn expand(&mut self, src: &str, dir: Option<&Path>) -> Result<String> {
+        if self.depth >= self.config.max_depth {
+            return Err(PreprocessorError::RecursionLimit);
+        }
+        self.depth += 1;
+
+        let mut res = String::with_capacity(src.len());
+        let mut rest = src;
+
+        while let Some(at) = rest.find('@') {
+            res.push_str(&rest[..at]);
+            rest = &rest[at + 1..];
+
+            let (name, tail) = split_name(rest);
+            rest = match name {
+                "" => {
+                    res.push('@');
+                    continue;
+                }
+                "include" => {
+                    let (path, tail) = quoted(tail).ok_or_else(|| invalid(name))?;
+                    res.push_str(&self.include(path, dir)?);
+                    tail
+                }
+                "define" => {
+                    let (line, tail) = tail.split_at(tail.find('\n').unwrap_or(tail.len()));
+                    let (macro_name, body) = split_name(line.trim_start());
+                    if macro_name.is_empty() {
+                        return Err(invalid(name));
+                    }
+                    self.macros
+                        .insert(macro_name.to_string(), body.trim().to_string());
+                    tail
+                }
+                "repeat" => {
+                    let (count, body, tail) = repeat(tail).ok_or_else(|| invalid(name))?;
+                    res.push_str(&self.expand(body, dir)?.repeat(count));
+                    tail
+                }
+                _ => {
+                    let body = self
+                        .macros
+                        .get(name)
+                        .cloned()
+                        .ok_or_else(|| PreprocessorError::UndefinedMacro(name.to_string()))?;
+                    res.push_str(&self.expand(&body, dir)?);
+                    tail
+                }
+            };
+        }
+
+        res.push_str(rest);
+        self.depth -= 1;
+
+        Ok(res)
+    }
+
+    /// Read and expand an included file.
+    fn include(&mut self, path: &str, dir: Option<&Path>) -> Result<String> {
+        let candidates = std::iter::once(dir.unwrap_or(Path::new(".")))
+            .chain(self.config.include_dirs.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(path));
+
+        for candidate in candidates {
+            if let Ok(src) = std::fs::read_to_string(&candidate) {
+                return self.expand(&src, candidate.parent());
+            }
+        }
+
+        Err(PreprocessorError::Include(path.to_string()))
+    }
+}
+
+fn invalid(name: &str) -> PreprocessorError {
+    PreprocessorError::InvalidDirective(name.to_string())
+}
+
+/// Split a name made of alphanumeric characters and underscores from the
+/// start of a string.
+fn split_name(src: &str) -> (&str, &str) {
+    let len = src
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(src.len());
+
+    src.split_at(len)
+}
+
+/// Split a quoted string from the start of a string, ignoring whitespace.
+fn quoted(src: &str) -> Option<(&str, &str)> {
+    let src = src.trim_start().strip_prefix('"')?;
+    let end = src.find('"')?;
+
+    Some((&src[..end], &src[end + 1..]))
+}
+
+/// Split the count and body of a repeat from the start of a string.
+fn repeat(src: &str) -> Option<(usize, &str, &str)> {
+    let src = src.trim_start();
+    let digits = src
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(src.len());
+    let count = src[..digits].parse().ok()?;
+    let src = src[digits..].trim_start().strip_prefix('{')?;
+
+    let mut depth = 0;
+    for (i, ch) in src.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some((count, &src[..i], &src[i + 1..])),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(src: &str) -> Result<String> {
+        preprocess(src, &PreprocessorConfig::default())
+    }
+
+    #[test]
+    fn plain_source() {
+        let src = "++[->+<] an email@ address";
+        assert_eq!(expand(src), Ok(src.to_string()));
+    }
+
+    #[test]
+    fn macros() {
+        let src = "@define ZERO [-]\n@define MOVE @ZERO>\n+@MOVE";
+        assert_eq!(expand(src), Ok("\n\n+[-]>".to_string()));
+
+        let src = "@UNKNOWN";
+        let expected = PreprocessorError::UndefinedMacro("UNKNOWN".to_string());
+        assert_eq!(expand(src), Err(expected));
+
+        let src = "@define\n";
+        let expected = PreprocessorError::InvalidDirective("define".to_string());
+        assert_eq!(expand(src), Err(expected));
+    }
+
+    #[test]
+    fn repeats() {
+        assert_eq!(expand("@repeat 3 {+}"), Ok("+++".to_string()));
+        assert_eq!(
+            expand("@repeat 2 {>@repeat 2 {+}}"),
+            Ok(">++>++".to_string())
+        );
+
+        let expected = PreprocessorError::InvalidDirective("repeat".to_string());
+        assert_eq!(expand("@repeat {+}"), Err(expected.clone()));
+        assert_eq!(expand("@repeat 2 {+"), Err(expected));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let src = "+@ >@.\n@";
+        assert_eq!(expand(src), Ok(src.to_string()));
+
+        #[cfg(feature = "breakpoint_token")]
+        {
+            use crate::lexer::{lex_unoptimized, Token};
+
+            let block = lex_unoptimized(expand(src).unwrap()).unwrap();
+            let breakpoints = block.iter().filter(|token| **token == Token::Breakpoint);
+            assert_eq!(breakpoints.count(), 3);
+        }
+
+        let expected = PreprocessorError::UndefinedMacro("here".to_string());
+        assert_eq!(expand("+@here"), Err(expected));
+    }
+
+    #[test]
+    fn recursion() {
+        let src = "@define LOOP @LOOP\n@LOOP";
+        assert_eq!(expand(src), Err(PreprocessorError::RecursionLimit));
+    }
+
+    #[test]
+    fn includes() {
+        let dir = std::env::temp_dir().join(format!("bf-preprocess-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::write(dir.join("lib/zero.b"), "@define ZERO [-]\n").unwrap();
+        std::fs::write(dir.join("lib/util.b"), "@include \"zero.b\"").unwrap();
+
+        let config = PreprocessorConfig {
+            include_dirs: vec![dir.join("lib")],
+            ..Default::default()
+        };
+        let src = "@include \"util.b\"+@ZERO";
+        assert_eq!(preprocess(src, &config), Ok("\n+[-]".to_string()));
+
+        let src = "@include \"missing.b\"";
+        let expected = PreprocessorError::Include("missing.b".to_string());
+        assert_eq!(preprocess(src, &config), Err(expected));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
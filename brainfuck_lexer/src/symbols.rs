@@ -0,0 +1,204 @@
+//! Brainfuck dialects with other symbols for the commands.
+//!
+//! Many dialects are trivial substitutions of Brainfuck, where every command
+//! is written as another string. Their source is translated to Brainfuck
+//! before it is lexed.
+
+use crate::error::Result;
+use crate::lexer::{lex, Block};
+use alloc::string::String;
+
+/// The strings standing for each of the commands.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::symbols::Symbols;
+///
+/// // Alphuck
+/// let symbols = Symbols {
+///     increment: "e".into(),
+///     decrement: "p".into(),
+///     next: "a".into(),
+///     prev: "c".into(),
+///     print: "j".into(),
+///     input: "s".into(),
+///     loop_begin: "i".into(),
+///     loop_end: "o".into(),
+/// };
+///
+/// assert_eq!(symbols.translate("eeeeeeeeiapppppppppppco"), "++++++++[>-----------<]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    /// Symbol for incrementing the current cell, `+` in Brainfuck.
+    pub increment: String,
+    /// Symbol for decrementing the current cell, `-` in Brainfuck.
+    pub decrement: String,
+    /// Symbol for moving to the next cell, `>` in Brainfuck.
+    pub next: String,
+    /// Symbol for moving to the previous cell, `<` in Brainfuck.
+    pub prev: String,
+    /// Symbol for printing the current cell, `.` in Brainfuck.
+    pub print: String,
+    /// Symbol for reading into the current cell, `,` in Brainfuck.
+    pub input: String,
+    /// Symbol for the start of a loop, `[` in Brainfuck.
+    pub loop_begin: String,
+    /// Symbol for the end of a loop, `]` in Brainfuck.
+    pub loop_end: String,
+}
+
+impl Default for Symbols {
+    /// The symbols of Brainfuck itself.
+    fn default() -> Self {
+        Self {
+            increment: "+".into(),
+            decrement: "-".into(),
+            next: ">".into(),
+            prev: "<".into(),
+            print: ".".into(),
+            input: ",".into(),
+            loop_begin: "[".into(),
+            loop_end: "]".into(),
+        }
+    }
+}
+
+impl Symbols {
+    /// Translate source written with these symbols to Brainfuck.
+    ///
+    /// The longest symbol matching the source is used, so symbols may start
+    /// with other symbols. Brainfuck commands which are not symbols of the
+    /// dialect are comments, and are left out. Empty symbols never match.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The source to translate.
+    pub fn translate(&self, src: &str) -> String {
+        let mut symbols = [
+            (&self.increment, '+'),
+            (&self.decrement, '-'),
+            (&self.next, '>'),
+            (&self.prev, '<'),
+            (&self.print, '.'),
+            (&self.input, ','),
+            (&self.loop_begin, '['),
+            (&self.loop_end, ']'),
+        ];
+        symbols.sort_by_key(|(symbol, _)| core::cmp::Reverse(symbol.len()));
+
+        let mut res = String::new();
+        let mut rest = src;
+        while let Some(ch) = rest.chars().next() {
+            let matched = symbols
+                .iter()
+                .find(|(symbol, _)| !symbol.is_empty() && rest.starts_with(symbol.as_str()));
+
+            match matched {
+                Some((symbol, command)) => {
+                    res.push(*command);
+                    rest = &rest[symbol.len()..];
+                    continue;
+                }
+                None if "+-<>.,[]".contains(ch) => {}
+                None => res.push(ch),
+            }
+            rest = &rest[ch.len_utf8()..];
+        }
+
+        res
+    }
+}
+
+/// Parse a program written with other symbols for the commands.
+///
+/// The source is translated with [`Symbols::translate`], and then lexed and
+/// optimized like [`lex`].
+///
+/// # Arguments
+///
+/// * `src` - The source to parse.
+/// * `symbols` - The [`Symbols`] the source is written with.
+///
+/// # Errors
+///
+/// If the translated source cannot be lexed, a
+/// [`LexerError`](crate::error::LexerError) will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_lexer::symbols::{lex_with_symbols, Symbols};
+///
+/// let symbols = Symbols {
+///     increment: "🍕".into(),
+///     print: "📣".into(),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(lex_with_symbols("🍕🍕📣", &symbols), lex("++."));
+/// ```
+pub fn lex_with_symbols(src: &str, symbols: &Symbols) -> Result<Block> {
+    lex(symbols.translate(src))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex_unoptimized;
+
+    fn ook() -> Symbols {
+        Symbols {
+            increment: "Ook. Ook.".into(),
+            decrement: "Ook! Ook!".into(),
+            next: "Ook. Ook?".into(),
+            prev: "Ook? Ook.".into(),
+            print: "Ook! Ook.".into(),
+            input: "Ook. Ook!".into(),
+            loop_begin: "Ook! Ook?".into(),
+            loop_end: "Ook? Ook!".into(),
+        }
+    }
+
+    #[test]
+    fn multi_character() {
+        let src = "Ook. Ook. Ook. Ook. Ook! Ook? Ook. Ook? Ook. Ook. Ook? Ook. Ook! Ook! Ook? Ook!";
+        assert_eq!(ook().translate(src), "+ + [ > + < - ]");
+    }
+
+    #[test]
+    fn longest_first() {
+        let symbols = Symbols {
+            increment: "a".into(),
+            decrement: "aa".into(),
+            next: "aaa".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(symbols.translate("aaaaaaaa"), ">>-");
+        assert_eq!(symbols.translate("a a"), "+ +");
+    }
+
+    #[test]
+    fn standard_commands_are_comments() {
+        let symbols = Symbols {
+            increment: "inc".into(),
+            print: "out".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(symbols.translate("inc+inc out."), "++ .");
+        assert_eq!(Symbols::default().translate("+[-]. x"), "+[-]. x");
+    }
+
+    #[test]
+    fn coalescing() {
+        let src = "Ook. Ook. Ook. Ook. Ook. Ook.";
+        assert_eq!(
+            lex_unoptimized(ook().translate(src)),
+            lex_unoptimized("+++")
+        );
+    }
+}
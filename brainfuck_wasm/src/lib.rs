@@ -0,0 +1,65 @@
+//! WebAssembly bindings for the Brainfuck lexer and interpreter.
+//!
+//! All I/O is done in memory, so the bindings can be used to build a web
+//! playground without reimplementing the semantics of the interpreter.
+
+#![warn(missing_docs)]
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_lexer::lex;
+use wasm_bindgen::prelude::*;
+
+/// Lex Brainfuck source to its tokens.
+///
+/// The tokens are returned as a JavaScript array with the same structure as
+/// the serialized [`brainfuck_lexer::Block`].
+///
+/// # Errors
+///
+/// If the source cannot be lexed, the [`brainfuck_lexer::error::LexerError`]
+/// is thrown as a JavaScript error.
+#[wasm_bindgen]
+pub fn lex_js(src: String) -> Result<JsValue, JsError> {
+    let block = lex(src)?;
+    Ok(serde_wasm_bindgen::to_value(&block)?)
+}
+
+/// Run Brainfuck source with the given input, returning the output.
+///
+/// # Errors
+///
+/// If the source cannot be lexed or the program fails to run, the error is
+/// thrown as a JavaScript error.
+#[wasm_bindgen]
+pub fn run_js(src: String, input: String) -> Result<String, JsError> {
+    Ok(run(src, &input)?)
+}
+
+/// Run Brainfuck source with in-memory input and output.
+fn run(src: String, input: &str) -> Result<String, BrainfuckError> {
+    let block = lex(src)?;
+    let mut input = input.as_bytes();
+    let mut out = Vec::new();
+
+    interpret(&block, &mut input, &mut out)?;
+
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_in_memory() {
+        let src = ",[.,]".to_string();
+        assert_eq!(run(src, "Hello").unwrap(), "Hello".to_string());
+    }
+
+    #[test]
+    fn run_lexer_error() {
+        let src = "[".to_string();
+        assert!(matches!(run(src, ""), Err(BrainfuckError::ParserError(_))));
+    }
+}
@@ -0,0 +1,21 @@
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_header();
+}
+
+/// Generate the C header for the `capi` feature.
+#[cfg(feature = "capi")]
+fn generate_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate C bindings")
+        .write_to_file(format!("{}/include/brainfuck.h", crate_dir));
+
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,70 @@
+#![no_main]
+
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized};
+use libfuzzer_sys::fuzz_target;
+use std::io::Cursor;
+
+#[path = "../../tests/reference/mod.rs"]
+mod reference;
+
+const MAX_STEPS: usize = 100_000;
+
+/// Build a program with balanced brackets from arbitrary bytes.
+fn program(data: &[u8]) -> String {
+    let mut src = String::new();
+    let mut depth = 0;
+
+    for byte in data {
+        match byte % 8 {
+            0 => src.push('+'),
+            1 => src.push('-'),
+            2 => src.push('>'),
+            3 => src.push('<'),
+            4 => src.push('.'),
+            5 => src.push(','),
+            6 => {
+                src.push('[');
+                depth += 1;
+            }
+            _ if depth > 0 => {
+                src.push(']');
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+
+    src.extend(std::iter::repeat(']').take(depth));
+    src
+}
+
+// The optimized interpreter must behave exactly like the reference
+fuzz_target!(|data: (Vec<u8>, Vec<u8>)| {
+    let (src, input) = data;
+    let src = program(&src);
+
+    // Only compare programs which are known to terminate
+    let Some(expected) = reference::run(&src, &input, MAX_STEPS) else {
+        return;
+    };
+
+    let block = lex_unoptimized(src.clone()).expect("valid program failed to lex");
+    let mut out = Vec::new();
+    interpret(&block, &mut Cursor::new(&input), &mut out).unwrap();
+
+    assert_eq!(out, expected);
+
+    let block = lex(src.clone()).expect("valid program failed to lex");
+    let mut out = Vec::new();
+    interpret(&block, &mut Cursor::new(&input), &mut out).unwrap();
+
+    assert_eq!(out, expected);
+
+    let block = Optimizer::new(OptLevel::Aggressive).optimize(&lex_unoptimized(src).unwrap());
+    let mut out = Vec::new();
+    interpret(&block, &mut Cursor::new(&input), &mut out).unwrap();
+
+    assert_eq!(out, expected);
+});
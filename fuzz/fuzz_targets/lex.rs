@@ -0,0 +1,9 @@
+#![no_main]
+
+use brainfuck_lexer::lex;
+use libfuzzer_sys::fuzz_target;
+
+// Lexing must never panic, whatever the source is
+fuzz_target!(|data: &[u8]| {
+    let _ = lex(String::from_utf8_lossy(data).into_owned());
+});
@@ -0,0 +1,126 @@
+#ifndef BRAINFUCK_H
+#define BRAINFUCK_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The call succeeded.
+ */
+#define BF_OK 0
+
+/**
+ * A required pointer argument was null.
+ */
+#define BF_ERROR_NULL_POINTER 1
+
+/**
+ * The source was not valid UTF-8.
+ */
+#define BF_ERROR_INVALID_UTF8 2
+
+/**
+ * The source could not be lexed.
+ */
+#define BF_ERROR_LEXER 3
+
+/**
+ * The program failed to read its input or write its output.
+ */
+#define BF_ERROR_IO 4
+
+/**
+ * The program was cancelled.
+ */
+#define BF_ERROR_CANCELLED 5
+
+/**
+ * The program went over one of its resource limits.
+ */
+#define BF_ERROR_LIMIT 6
+
+/**
+ * A memory location went out of the range of a byte.
+ */
+#define BF_ERROR_OVERFLOW 7
+
+/**
+ * The interpreter panicked.
+ */
+#define BF_ERROR_PANIC 8
+
+/**
+ * A lexed Brainfuck program.
+ */
+typedef struct BfProgram BfProgram;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Lex Brainfuck source to a program.
+ *
+ * On success, `*out` is set to a program which must be released with
+ * [`bf_free`].
+ *
+ * # Safety
+ *
+ * `src` must point to `src_len` readable bytes, and `out` must be a valid
+ * pointer to write the program to.
+ */
+int bf_lex(const uint8_t *src, size_t src_len, struct BfProgram **out);
+
+/**
+ * Run a program with in-memory input and output.
+ *
+ * On success, `*output` and `*output_len` are set to a buffer holding the
+ * output of the program, which must be released with [`bf_free_buffer`].
+ *
+ * # Safety
+ *
+ * `program` must be a program returned by [`bf_lex`], `input` must point to
+ * `input_len` readable bytes (or be null if `input_len` is zero), and
+ * `output` and `output_len` must be valid pointers to write the result to.
+ */
+int bf_run(const struct BfProgram *program,
+           const uint8_t *input,
+           size_t input_len,
+           uint8_t **output,
+           size_t *output_len);
+
+/**
+ * Release a program returned by [`bf_lex`].
+ *
+ * # Safety
+ *
+ * `program` must be null or a program returned by [`bf_lex`] which has not
+ * already been released.
+ */
+void bf_free(struct BfProgram *program);
+
+/**
+ * Release an output buffer returned by [`bf_run`].
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a buffer returned by [`bf_run`] with the same
+ * `len`, which has not already been released.
+ */
+void bf_free_buffer(uint8_t *buffer, size_t len);
+
+/**
+ * Get a description of the last error on the calling thread.
+ *
+ * Returns null if no error has occurred. The string is owned by the library
+ * and is valid until the next failing call on the same thread.
+ */
+const char *bf_last_error_message(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BRAINFUCK_H */
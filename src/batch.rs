@@ -0,0 +1,197 @@
+//! Running many independent programs at once.
+//!
+//! The programs are shared between a pool of threads, each running one
+//! program at a time, so a slow program only holds up its own thread. This
+//! is meant for running corpora of test programs, or evaluating many
+//! submissions on a server.
+
+use crate::error::BrainfuckError;
+use crate::interpreter::{interpret_with_config, InterpreterConfig};
+use crate::io::StdIo;
+use brainfuck_lexer::Block;
+use std::io::Cursor;
+use std::num::NonZeroUsize;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Options for running many programs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchConfig {
+    /// How every program is interpreted, including the resources each of
+    /// them may use.
+    pub interpreter: InterpreterConfig,
+    /// How long every program may run before it is cancelled.
+    pub timeout: Option<Duration>,
+    /// Number of programs to run at once. Defaults to the available
+    /// parallelism.
+    pub threads: Option<NonZeroUsize>,
+}
+
+/// What a single program did.
+#[derive(Debug)]
+pub struct RunResult {
+    /// Everything the program printed, even if it failed.
+    pub output: Vec<u8>,
+    /// Why the program stopped before finishing, if it did. A panic of the
+    /// interpreter is reported as a [`BrainfuckError::Panicked`].
+    pub error: Option<BrainfuckError>,
+}
+
+/// Run programs in parallel, each with its own input.
+///
+/// # Arguments
+///
+/// * `programs` - The programs to run, with the input of each of them.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::batch::run_many;
+///
+/// let programs = vec![
+///     (lex(",+.").unwrap(), b"a".to_vec()),
+///     (lex(",-.").unwrap(), b"b".to_vec()),
+/// ];
+/// let results = run_many(&programs);
+///
+/// assert_eq!(results[0].output, b"b");
+/// assert_eq!(results[1].output, b"a");
+/// ```
+pub fn run_many(programs: &[(Block, Vec<u8>)]) -> Vec<RunResult> {
+    run_many_with_config(programs, &BatchConfig::default())
+}
+
+/// Run programs in parallel with a configuration.
+///
+/// # Arguments
+///
+/// * `programs` - The programs to run, with the input of each of them.
+/// * `config` - The [`BatchConfig`] to run the programs with.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::batch::{run_many_with_config, BatchConfig};
+/// use brainfuck_interpreter::error::BrainfuckError;
+/// use std::time::Duration;
+///
+/// let programs = vec![(lex("+[>+<]").unwrap(), vec![]), (lex("+.").unwrap(), vec![])];
+/// let config = BatchConfig {
+///     timeout: Some(Duration::from_millis(10)),
+///     ..Default::default()
+/// };
+/// let results = run_many_with_config(&programs, &config);
+///
+/// assert!(matches!(results[0].error, Some(BrainfuckError::Cancelled)));
+/// assert_eq!(results[1].output, [1]);
+/// ```
+pub fn run_many_with_config(programs: &[(Block, Vec<u8>)], config: &BatchConfig) -> Vec<RunResult> {
+    let threads = config
+        .threads
+        .or_else(|| std::thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get)
+        .min(programs.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| work(programs, &next, config)))
+            .collect();
+
+        // Panics are caught around every program, so workers never panic
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, res)| res).collect()
+}
+
+/// Keep taking the next program which has not been started yet and running
+/// it, returning the results with the indices of their programs.
+fn work(
+    programs: &[(Block, Vec<u8>)],
+    next: &AtomicUsize,
+    config: &BatchConfig,
+) -> Vec<(usize, RunResult)> {
+    std::thread::scope(|scope| {
+        // Every worker has its own timer, which is told when each program
+        // starts and finishes
+        let timer = config.timeout.map(|timeout| {
+            let (events, received) = mpsc::channel();
+            scope.spawn(move || time(&received, timeout));
+            events
+        });
+
+        let mut results = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some((program, input)) = programs.get(index) else {
+                return results;
+            };
+
+            // Every program gets its own flag, so a timer running late can
+            // not cancel the next one
+            let cancel = Arc::new(AtomicBool::new(false));
+            if let Some(ref timer) = timer {
+                timer.send(Some(Arc::clone(&cancel))).unwrap();
+            }
+            results.push((index, run(program, input, config, &cancel)));
+            if let Some(ref timer) = timer {
+                timer.send(None).unwrap();
+            }
+        }
+    })
+}
+
+/// Cancel every program which runs for too long.
+///
+/// Every program sends its cancellation flag when it starts, and `None` when
+/// it finishes.
+fn time(events: &Receiver<Option<Arc<AtomicBool>>>, timeout: Duration) {
+    while let Ok(Some(cancel)) = events.recv() {
+        match events.recv_timeout(timeout) {
+            Ok(_) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                cancel.store(true, Ordering::Relaxed);
+                if events.recv().is_err() {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Run a single program, reporting a panic as its error.
+fn run(program: &Block, input: &[u8], config: &BatchConfig, cancel: &AtomicBool) -> RunResult {
+    let mut io = StdIo::new(Cursor::new(input), Vec::new());
+
+    let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        interpret_with_config(program, &mut io, &config.interpreter, cancel)
+    }));
+    let error = match res {
+        Ok(res) => res.err(),
+        Err(payload) => {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => payload
+                    .downcast_ref::<&str>()
+                    .map_or_else(String::new, |message| message.to_string()),
+            };
+            Some(BrainfuckError::Panicked(message))
+        }
+    };
+
+    RunResult {
+        output: io.into_inner().1,
+        error,
+    }
+}
@@ -0,0 +1,89 @@
+//! Caching of lexed and optimized programs.
+//!
+//! Programs are stored in a directory using the encoding from
+//! [`brainfuck_lexer::binary`], in files named after a hash of the source,
+//! the optimization passes and the version of the crate. The hash is stable
+//! across builds, and every file starts with everything that was hashed, so
+//! a program is never mistaken for another with the same hash.
+
+use crate::error::BrainfuckError;
+use brainfuck_lexer::binary::{decode, encode};
+use brainfuck_lexer::lex_unoptimized;
+use brainfuck_lexer::optimizer::Optimizer;
+use brainfuck_lexer::Block;
+use std::path::Path;
+
+/// Offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// Prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Lex and optimize a program, reusing the result of an earlier run.
+///
+/// If the cache holds no valid result for the program, it is lexed and
+/// optimized as usual, and the result is stored in the cache.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to lex.
+/// * `optimizer` - The [`Optimizer`] to optimize the program with.
+/// * `dir` - The directory to store cached programs in.
+///
+/// # Errors
+///
+/// If the source cannot be lexed, a [`BrainfuckError::ParserError`] will be
+/// returned. If the result cannot be stored in the cache, a
+/// [`BrainfuckError::IOError`] will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::cache::lex_cached;
+/// use brainfuck_lexer::optimizer::Optimizer;
+///
+/// let dir = std::env::temp_dir().join("bf-cache-example");
+/// let code = lex_cached("++[->+<]", &Optimizer::default(), &dir).unwrap();
+/// ```
+pub fn lex_cached(src: &str, optimizer: &Optimizer, dir: &Path) -> Result<Block, BrainfuckError> {
+    let key = cache_key(src, optimizer);
+    let path = dir.join(format!("{:016x}.bfc", fnv1a(&key)));
+
+    if let Some(block) = std::fs::read(&path)
+        .ok()
+        .and_then(|bytes| bytes.strip_prefix(&key[..]).and_then(decode))
+    {
+        return Ok(block);
+    }
+
+    let block = optimizer.optimize(&lex_unoptimized(src)?);
+
+    // Write to a temporary file first, so other runs never read a partially
+    // written program
+    std::fs::create_dir_all(dir)?;
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    std::fs::write(&tmp, [key, encode(&block)].concat())?;
+    std::fs::rename(tmp, path)?;
+
+    Ok(block)
+}
+
+/// Get everything the cached program depends on, starting with its length.
+fn cache_key(src: &str, optimizer: &Optimizer) -> Vec<u8> {
+    let passes: Vec<_> = optimizer.passes().map(|pass| pass.name).collect();
+    let key = format!(
+        "{}\0{}\0{}",
+        env!("CARGO_PKG_VERSION"),
+        passes.join(","),
+        src
+    );
+
+    [&(key.len() as u64).to_le_bytes()[..], key.as_bytes()].concat()
+}
+
+/// Hash bytes with 64-bit FNV-1a, which gives the same hash on every
+/// platform and with every version of Rust.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
@@ -0,0 +1,219 @@
+//! C bindings for the lexer and interpreter.
+//!
+//! Strings and buffers are passed as a pointer and a length, and every
+//! fallible function returns one of the `BF_*` status codes. A description of
+//! the last error on the calling thread can be retrieved with
+//! [`bf_last_error_message`].
+
+use crate::error::BrainfuckError;
+use crate::interpreter::interpret;
+use brainfuck_lexer::{lex, Block};
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CString};
+use std::ptr;
+
+/// The call succeeded.
+pub const BF_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const BF_ERROR_NULL_POINTER: c_int = 1;
+/// The source was not valid UTF-8.
+pub const BF_ERROR_INVALID_UTF8: c_int = 2;
+/// The source could not be lexed.
+pub const BF_ERROR_LEXER: c_int = 3;
+/// The program failed to read its input or write its output.
+pub const BF_ERROR_IO: c_int = 4;
+/// The program was cancelled.
+pub const BF_ERROR_CANCELLED: c_int = 5;
+/// The program went over one of its resource limits.
+pub const BF_ERROR_LIMIT: c_int = 6;
+/// A memory location went out of the range of a byte.
+pub const BF_ERROR_OVERFLOW: c_int = 7;
+/// The interpreter panicked.
+pub const BF_ERROR_PANIC: c_int = 8;
+
+/// A lexed Brainfuck program.
+pub struct BfProgram {
+    block: Block,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(code: c_int, message: String) -> c_int {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+    code
+}
+
+fn error_code(e: BrainfuckError) -> c_int {
+    let code = match e {
+        BrainfuckError::IOError(_) => BF_ERROR_IO,
+        BrainfuckError::ParserError(_) | BrainfuckError::PreprocessorError(_) => BF_ERROR_LEXER,
+        BrainfuckError::Cancelled => BF_ERROR_CANCELLED,
+        BrainfuckError::StepLimitExceeded(_)
+        | BrainfuckError::OutputLimitExceeded(_)
+        | BrainfuckError::InputLimitExceeded(_)
+        | BrainfuckError::TapeLimitExceeded(_) => BF_ERROR_LIMIT,
+        BrainfuckError::CellOverflow(_) => BF_ERROR_OVERFLOW,
+        BrainfuckError::Panicked(_) => BF_ERROR_PANIC,
+    };
+
+    set_last_error(code, e.to_string())
+}
+
+/// Lex Brainfuck source to a program.
+///
+/// On success, `*out` is set to a program which must be released with
+/// [`bf_free`].
+///
+/// # Safety
+///
+/// `src` must point to `src_len` readable bytes, and `out` must be a valid
+/// pointer to write the program to.
+#[no_mangle]
+pub unsafe extern "C" fn bf_lex(src: *const u8, src_len: usize, out: *mut *mut BfProgram) -> c_int {
+    if src.is_null() || out.is_null() {
+        return set_last_error(BF_ERROR_NULL_POINTER, "null pointer argument".to_string());
+    }
+
+    let bytes = std::slice::from_raw_parts(src, src_len);
+    let src = match std::str::from_utf8(bytes) {
+        Ok(src) => src.to_string(),
+        Err(e) => return set_last_error(BF_ERROR_INVALID_UTF8, e.to_string()),
+    };
+
+    match lex(src) {
+        Ok(block) => {
+            *out = Box::into_raw(Box::new(BfProgram { block }));
+            BF_OK
+        }
+        Err(e) => error_code(e.into()),
+    }
+}
+
+/// Run a program with in-memory input and output.
+///
+/// On success, `*output` and `*output_len` are set to a buffer holding the
+/// output of the program, which must be released with [`bf_free_buffer`].
+///
+/// # Safety
+///
+/// `program` must be a program returned by [`bf_lex`], `input` must point to
+/// `input_len` readable bytes (or be null if `input_len` is zero), and
+/// `output` and `output_len` must be valid pointers to write the result to.
+#[no_mangle]
+pub unsafe extern "C" fn bf_run(
+    program: *const BfProgram,
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut u8,
+    output_len: *mut usize,
+) -> c_int {
+    if program.is_null() || output.is_null() || output_len.is_null() {
+        return set_last_error(BF_ERROR_NULL_POINTER, "null pointer argument".to_string());
+    }
+
+    let mut input = if input.is_null() {
+        if input_len != 0 {
+            return set_last_error(BF_ERROR_NULL_POINTER, "null input buffer".to_string());
+        }
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(input, input_len)
+    };
+
+    let mut out = Vec::new();
+    if let Err(e) = interpret(&(*program).block, &mut input, &mut out) {
+        return error_code(e);
+    }
+
+    let out = out.into_boxed_slice();
+    *output_len = out.len();
+    *output = Box::into_raw(out) as *mut u8;
+
+    BF_OK
+}
+
+/// Release a program returned by [`bf_lex`].
+///
+/// # Safety
+///
+/// `program` must be null or a program returned by [`bf_lex`] which has not
+/// already been released.
+#[no_mangle]
+pub unsafe extern "C" fn bf_free(program: *mut BfProgram) {
+    if !program.is_null() {
+        drop(Box::from_raw(program));
+    }
+}
+
+/// Release an output buffer returned by [`bf_run`].
+///
+/// # Safety
+///
+/// `buffer` must be null or a buffer returned by [`bf_run`] with the same
+/// `len`, which has not already been released.
+#[no_mangle]
+pub unsafe extern "C" fn bf_free_buffer(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, len)));
+    }
+}
+
+/// Get a description of the last error on the calling thread.
+///
+/// Returns null if no error has occurred. The string is owned by the library
+/// and is valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn bf_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn lex_and_run() {
+        let src = ",[.,]";
+        let input = "Hello";
+
+        unsafe {
+            let mut program = ptr::null_mut();
+            assert_eq!(bf_lex(src.as_ptr(), src.len(), &mut program), BF_OK);
+
+            let mut output = ptr::null_mut();
+            let mut output_len = 0;
+            let res = bf_run(
+                program,
+                input.as_ptr(),
+                input.len(),
+                &mut output,
+                &mut output_len,
+            );
+            assert_eq!(res, BF_OK);
+            assert_eq!(std::slice::from_raw_parts(output, output_len), b"Hello");
+
+            bf_free_buffer(output, output_len);
+            bf_free(program);
+        }
+    }
+
+    #[test]
+    fn lexer_error() {
+        let src = "[";
+
+        unsafe {
+            let mut program = ptr::null_mut();
+            assert_eq!(
+                bf_lex(src.as_ptr(), src.len(), &mut program),
+                BF_ERROR_LEXER
+            );
+
+            let message = CStr::from_ptr(bf_last_error_message());
+            assert!(!message.to_bytes().is_empty());
+        }
+    }
+}
@@ -1,6 +1,288 @@
-use clap::Parser;
+use brainfuck_interpreter::interpreter::OverflowBehavior;
+use brainfuck_interpreter::io::{OutputMode, CP437};
+#[cfg(feature = "llvm")]
+use brainfuck_interpreter::llvm::Emit;
+use brainfuck_interpreter::tape::TapeKind;
+use brainfuck_lexer::optimizer::{OptLevel, PASSES};
+use clap::builder::PossibleValuesParser;
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(
+    ArgGroup::new("limits")
+        .multiple(true)
+        .conflicts_with_all(["debug", "visualize", "stats", "heatmap"])
+))]
 pub struct Args {
-    pub src: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[arg(required = true)]
+    pub src: Option<String>,
+    /// Expand preprocessor directives before lexing the program.
+    #[arg(long)]
+    pub preprocess: bool,
+    /// Read the program with the symbols for the commands in a TOML file.
+    #[cfg(feature = "dialects")]
+    #[arg(long, value_name = "FILE", conflicts_with = "coverage")]
+    pub symbols: Option<std::path::PathBuf>,
+    /// Run the program in the interactive debugger.
+    #[arg(short, long)]
+    pub debug: bool,
+    /// Number of steps the debugger remembers for stepping back.
+    #[arg(long, default_value_t = 1024)]
+    pub history: usize,
+    /// Render the memory around the pointer while the program runs.
+    #[arg(long, conflicts_with = "debug")]
+    pub visualize: bool,
+    /// Number of times per second the visualization is redrawn.
+    #[arg(long, default_value_t = 30)]
+    pub refresh_rate: u32,
+    /// Number of cells shown by the visualization.
+    #[arg(long, default_value_t = 16)]
+    pub window: usize,
+    /// Print statistics about the execution once the program finishes.
+    #[arg(long, conflicts_with_all = ["debug", "visualize"])]
+    pub stats: bool,
+    /// Write the number of reads and writes of every used cell to a .csv or
+    /// .json file once the program finishes.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["debug", "visualize"])]
+    pub heatmap: Option<std::path::PathBuf>,
+    /// The memory to run the program on.
+    #[arg(long, value_enum, default_value_t = Tape::Wrapping, conflicts_with_all = ["debug", "visualize", "stats", "heatmap"])]
+    pub tape: Tape,
+    /// What happens when a cell goes below 0 or above 255. Passes which
+    /// assume wrapping are disabled for the other behaviors.
+    #[arg(long, value_enum, default_value_t = Overflow::Wrap, conflicts_with_all = ["debug", "visualize", "stats", "heatmap"])]
+    pub overflow: Overflow,
+    /// How printed values are written to the standard output.
+    #[arg(long, value_enum, default_value_t = Encoding::Unicode, conflicts_with_all = ["debug", "visualize", "stats", "heatmap"])]
+    pub encoding: Encoding,
+    /// Print values as decimal numbers on lines of their own.
+    #[arg(long, conflicts_with_all = ["encoding", "debug", "visualize", "stats", "heatmap"])]
+    pub print_numeric: bool,
+    /// Save everything the program reads from the standard input to a JSON
+    /// file, for running the program again with the same input.
+    #[cfg(feature = "session")]
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["debug", "visualize", "stats", "heatmap", "coverage", "emit"])]
+    pub record: Option<std::path::PathBuf>,
+    /// Read the input of the program from a session saved with --record
+    /// instead of the standard input.
+    #[cfg(feature = "session")]
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record", "debug", "visualize", "stats", "heatmap", "coverage", "emit"])]
+    pub replay: Option<std::path::PathBuf>,
+    /// Write how often every command was executed to a report, running the
+    /// program without optimizing it.
+    #[arg(long, value_enum, conflicts_with_all = ["preprocess", "debug", "visualize", "stats", "heatmap", "tape", "overflow", "limits", "encoding", "print_numeric"])]
+    pub coverage: Option<CoverageFormat>,
+    /// File to write the coverage report to [default: lcov.info or
+    /// coverage.html]
+    #[arg(long, value_name = "FILE", requires = "coverage")]
+    pub coverage_file: Option<std::path::PathBuf>,
+    /// Stop the program after executing this many instructions.
+    #[arg(long, group = "limits")]
+    pub max_steps: Option<u64>,
+    /// Stop the program if it prints more than this many bytes.
+    #[arg(long, group = "limits")]
+    pub max_output: Option<u64>,
+    /// Stop the program if it reads more than this many bytes.
+    #[arg(long, group = "limits")]
+    pub max_input: Option<u64>,
+    /// Stop the program if its tape uses more than this many cells.
+    #[arg(long, group = "limits")]
+    pub max_cells: Option<usize>,
+    /// How much to optimize the program.
+    #[arg(short = 'O', long, value_enum, default_value_t = Opt::Basic)]
+    pub opt_level: Opt,
+    /// Disable an optimization pass.
+    #[arg(long, value_parser = PossibleValuesParser::new(PASSES.iter().map(|pass| pass.name)))]
+    pub disable_pass: Vec<String>,
+    /// Directory to cache lexed and optimized programs in.
+    #[cfg(feature = "cache")]
+    #[arg(long)]
+    pub cache_dir: Option<std::path::PathBuf>,
+    /// Print a listing of the program, or compile it with LLVM, instead of
+    /// running it.
+    #[arg(long, value_enum, conflicts_with_all = ["debug", "visualize", "tape", "overflow", "limits", "encoding", "print_numeric", "coverage"])]
+    pub emit: Option<EmitKind>,
+    /// File to write the listing or compiled program to.
+    #[arg(short, long, requires = "emit")]
+    pub output: Option<std::path::PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print a program with consistent formatting.
+    Fmt {
+        src: String,
+        /// Maximum width of a line, including the indentation.
+        #[arg(long, default_value_t = 80)]
+        width: usize,
+        /// Number of spaces to indent the body of a loop with.
+        #[arg(long, default_value_t = 4)]
+        indent: usize,
+        /// Remove comments instead of keeping them.
+        #[arg(long)]
+        strip_comments: bool,
+        /// Overwrite the source file instead of printing the result.
+        #[arg(short, long)]
+        write: bool,
+    },
+    /// Run every program in a directory with a .out file next to it, and
+    /// check that it prints the content of the file.
+    ///
+    /// The program reads the content of the .in file next to it, if there
+    /// is one.
+    Test {
+        dir: std::path::PathBuf,
+        /// How much to optimize the programs.
+        #[arg(short = 'O', long, value_enum, default_value_t = Opt::Basic)]
+        opt_level: Opt,
+        /// Number of seconds a program may run before it fails.
+        #[arg(long, default_value_t = 10.0)]
+        timeout: f64,
+    },
+    /// Print a program which prints some text.
+    Generate { text: String },
+    /// Print a program with an explanation of what every loop does.
+    Explain { src: String },
+    /// Run programs in a pipeline, where the output of every program is the
+    /// input of the next.
+    Pipe {
+        #[arg(required = true)]
+        programs: Vec<String>,
+        /// How much to optimize the programs.
+        #[arg(short = 'O', long, value_enum, default_value_t = Opt::Basic)]
+        opt_level: Opt,
+        /// How the values printed by the last program are written to the
+        /// standard output.
+        #[arg(long, value_enum, default_value_t = Encoding::Unicode)]
+        encoding: Encoding,
+    },
+    /// Serve the Debug Adapter Protocol on the standard input and output.
+    #[cfg(feature = "dap")]
+    Dap,
+    /// Serve the Language Server Protocol on the standard input and output.
+    #[cfg(feature = "lsp")]
+    Lsp,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Opt {
+    /// Leave the program as it was written.
+    #[value(alias = "0")]
+    None,
+    /// Remove empty loops and recognize common patterns.
+    #[value(alias = "1")]
+    Basic,
+    /// Also combine and cancel out tokens, and fuse pointer movement.
+    #[value(alias = "2")]
+    Aggressive,
+}
+
+impl From<Opt> for OptLevel {
+    fn from(opt: Opt) -> Self {
+        match opt {
+            Opt::None => OptLevel::None,
+            Opt::Basic => OptLevel::Basic,
+            Opt::Aggressive => OptLevel::Aggressive,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Tape {
+    /// 30000 cells, wrapping around at both ends.
+    Wrapping,
+    /// Unbounded in both directions.
+    Bidirectional,
+    /// Unbounded in both directions, only allocating the used parts.
+    Sparse,
+}
+
+impl From<Tape> for TapeKind {
+    fn from(tape: Tape) -> Self {
+        match tape {
+            Tape::Wrapping => TapeKind::Wrapping,
+            Tape::Bidirectional => TapeKind::Bidirectional,
+            Tape::Sparse => TapeKind::Sparse,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Overflow {
+    /// Wrap around to the other end.
+    Wrap,
+    /// Stay at 0 or 255.
+    Saturate,
+    /// Stop the program with an error.
+    Error,
+}
+
+impl From<Overflow> for OverflowBehavior {
+    fn from(overflow: Overflow) -> Self {
+        match overflow {
+            Overflow::Wrap => OverflowBehavior::Wrap,
+            Overflow::Saturate => OverflowBehavior::Saturate,
+            Overflow::Error => OverflowBehavior::Error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Encoding {
+    /// Values as the Unicode characters with the same number, in UTF-8.
+    Unicode,
+    /// Values as single bytes, unchanged.
+    Raw,
+    /// Values above 127 as the characters of code page 437, in UTF-8.
+    Cp437,
+}
+
+impl From<Encoding> for OutputMode {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Unicode => OutputMode::Unicode,
+            Encoding::Raw => OutputMode::Raw,
+            Encoding::Cp437 => OutputMode::Codepage(CP437.chars().collect()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CoverageFormat {
+    /// An LCOV tracefile.
+    Lcov,
+    /// An HTML page showing the source.
+    Html,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmitKind {
+    /// The source interleaved with the optimized instructions generated from
+    /// it, printed to the standard output.
+    Listing,
+    /// Textual LLVM IR.
+    #[cfg(feature = "llvm")]
+    Ir,
+    /// An object file.
+    #[cfg(feature = "llvm")]
+    Obj,
+    /// A native executable.
+    #[cfg(feature = "llvm")]
+    Exe,
+}
+
+#[cfg(feature = "llvm")]
+impl EmitKind {
+    /// What to compile the program to with LLVM, if anything.
+    pub fn llvm(self) -> Option<Emit> {
+        match self {
+            EmitKind::Listing => None,
+            EmitKind::Ir => Some(Emit::Ir),
+            EmitKind::Obj => Some(Emit::Object),
+            EmitKind::Exe => Some(Emit::Executable),
+        }
+    }
 }
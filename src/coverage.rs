@@ -0,0 +1,198 @@
+//! Coverage of the source of Brainfuck programs.
+//!
+//! The program is run without optimizing it, so every instruction executed
+//! is a command of the source. Counting how often each of them is executed
+//! tells which parts of the source are dead code, and which are hot.
+
+use crate::error::BrainfuckError;
+use crate::machine::Machine;
+use brainfuck_lexer::{lex_with_spans, Span};
+
+/// How often every command of a program was executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    src: String,
+    /// The offset of the start of every line of the source.
+    line_starts: Vec<usize>,
+    /// The span of every instruction, with the number of times it was
+    /// executed.
+    counts: Vec<(Span, u64)>,
+}
+
+impl Coverage {
+    /// The span of every command in the source, with the number of times it
+    /// was executed.
+    ///
+    /// Repeated commands are counted together, and both brackets of a loop
+    /// are counted every time their condition is checked.
+    pub fn counts(&self) -> &[(Span, u64)] {
+        &self.counts
+    }
+
+    /// The lines with commands on them, numbered from one, with the number of
+    /// times the most executed command on them was executed.
+    pub fn lines(&self) -> Vec<(usize, u64)> {
+        let mut lines: Vec<(usize, u64)> = Vec::new();
+
+        for &(span, count) in &self.counts {
+            let line = self
+                .line_starts
+                .partition_point(|&start| start <= span.start);
+            match lines.last_mut() {
+                Some((last, max)) if *last == line => *max = (*max).max(count),
+                _ => lines.push((line, count)),
+            }
+        }
+
+        lines
+    }
+
+    /// Export the coverage in the LCOV tracefile format.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the source file in the report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brainfuck_interpreter::coverage::interpret_with_coverage;
+    /// use std::io::Cursor;
+    ///
+    /// let src = "+.\n>[.]";
+    /// let coverage = interpret_with_coverage(src, &mut Cursor::new(vec![]), &mut Vec::new()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     coverage.to_lcov("prog.bf"),
+    ///     "SF:prog.bf\nDA:1,1\nDA:2,1\nLF:2\nLH:2\nend_of_record\n"
+    /// );
+    /// ```
+    pub fn to_lcov(&self, name: &str) -> String {
+        let lines = self.lines();
+        let mut res = format!("SF:{}\n", name);
+
+        for (line, count) in &lines {
+            res.push_str(&format!("DA:{},{}\n", line, count));
+        }
+
+        let hit = lines.iter().filter(|(_, count)| *count > 0).count();
+        res.push_str(&format!("LF:{}\nLH:{}\nend_of_record\n", lines.len(), hit));
+        res
+    }
+
+    /// Export the coverage as an HTML page showing the source.
+    ///
+    /// Commands which were never executed are highlighted, and hovering a
+    /// command shows how often it was executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title of the page.
+    pub fn to_html(&self, title: &str) -> String {
+        let mut body = String::new();
+        let mut pos = 0;
+
+        for &(span, count) in &self.counts {
+            body.push_str(&escape(&self.src[pos..span.start]));
+            let class = if count > 0 { "hit" } else { "miss" };
+            body.push_str(&format!(
+                "<span class=\"{}\" title=\"{}\">{}</span>",
+                class,
+                count,
+                escape(&self.src[span.start..span.end])
+            ));
+            pos = span.end;
+        }
+        body.push_str(&escape(&self.src[pos..]));
+
+        let lines = self.lines();
+        let hit = lines.iter().filter(|(_, count)| *count > 0).count();
+
+        format!(
+            "<!DOCTYPE html>\n\
+             <html>\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n\
+             <style>\n\
+             .hit {{ background: #cfc; }}\n\
+             .miss {{ background: #fcc; }}\n\
+             </style>\n\
+             </head>\n\
+             <body>\n\
+             <h1>{title}</h1>\n\
+             <p>{hit} of {total} lines executed</p>\n\
+             <pre>{body}</pre>\n\
+             </body>\n\
+             </html>\n",
+            title = escape(title),
+            hit = hit,
+            total = lines.len(),
+            body = body,
+        )
+    }
+}
+
+/// Escape text for HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Interpret Brainfuck source while counting how often each of its commands
+/// is executed.
+///
+/// The program is not optimized, and runs on the memory of a [`Machine`].
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to interpret.
+/// * `input` - The input stream.
+/// * `out` - The output stream.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::coverage::interpret_with_coverage;
+/// use brainfuck_lexer::Span;
+/// use std::io::Cursor;
+///
+/// let coverage = interpret_with_coverage("+[-]", &mut Cursor::new(vec![]), &mut Vec::new()).unwrap();
+///
+/// assert_eq!(coverage.counts()[0], (Span { start: 0, end: 1 }, 1));
+/// assert_eq!(coverage.counts()[3], (Span { start: 3, end: 4 }, 1));
+/// ```
+///
+/// # Errors
+///
+/// If the source cannot be lexed, a [`BrainfuckError::ParserError`] is
+/// returned. See also [`Machine::step`].
+pub fn interpret_with_coverage<I, O>(
+    src: &str,
+    input: &mut I,
+    out: &mut O,
+) -> Result<Coverage, BrainfuckError>
+where
+    I: std::io::Read,
+    O: std::io::Write,
+{
+    let (block, spans) = lex_with_spans(src)?;
+    let mut machine = Machine::new(&block);
+    let mut counts = vec![0; spans.len()];
+
+    while !machine.is_finished() {
+        counts[machine.pc()] += 1;
+        machine.step(input, out)?;
+    }
+    out.flush()?;
+
+    Ok(Coverage {
+        src: src.to_string(),
+        line_starts: std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(i, _)| i + 1))
+            .collect(),
+        counts: spans.into_iter().zip(counts).collect(),
+    })
+}
@@ -0,0 +1,637 @@
+//! Debug Adapter Protocol server.
+//!
+//! Editors talk to the server over the standard input and output, and launch
+//! a program with a `program` path, an optional `input` string for the
+//! program to read and an optional `stopOnEntry` flag. The program is lexed
+//! without optimizing it, so every instruction of the [`Machine`] maps back
+//! to the source through its span.
+
+use crate::protocol::{read_messages, write_message};
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::machine::{Condition, Instruction, Machine};
+use brainfuck_lexer::{lex_with_spans, Span};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{Cursor, Write};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// Number of steps the debugger may undo.
+const HISTORY: usize = 1 << 16;
+/// Number of steps to run between checking for requests to pause.
+const STEPS_PER_POLL: usize = 1 << 14;
+/// Number of cells around the pointer shown in the memory scope.
+const MEMORY_WINDOW: usize = 8;
+
+const MACHINE_SCOPE: u64 = 1;
+const MEMORY_SCOPE: u64 = 2;
+
+/// Serve debugging sessions on the standard input and output.
+pub fn serve() -> Result<(), BrainfuckError> {
+    let (sender, requests) = mpsc::channel();
+    std::thread::spawn(move || read_messages(std::io::stdin().lock(), sender));
+
+    let mut session = Session::new(requests, std::io::stdout().lock());
+    Ok(session.serve()?)
+}
+
+/// A breakpoint set by the editor.
+struct Breakpoint {
+    pc: usize,
+    condition: Option<Condition>,
+}
+
+/// The program being debugged.
+struct Program {
+    path: String,
+    src: String,
+    line_starts: Vec<usize>,
+    spans: Vec<Span>,
+    machine: Machine,
+    input: Cursor<Vec<u8>>,
+    breakpoints: Vec<Breakpoint>,
+    stop_on_entry: bool,
+    /// Printed bytes which have not been sent to the editor yet.
+    output: Vec<u8>,
+}
+
+impl Program {
+    /// Get the zero-based line and column of an offset in the source.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.src[self.line_starts[line]..offset].chars().count();
+
+        (line, column)
+    }
+
+    /// Get the first instruction starting on or after a zero-based line.
+    fn instruction_at(&self, line: usize) -> Option<usize> {
+        let start = *self.line_starts.get(line)?;
+
+        self.spans.iter().position(|span| span.start >= start)
+    }
+
+    /// Take the printed text, keeping the bytes of a character which is not
+    /// complete yet unless the program has finished.
+    fn take_output(&mut self, finished: bool) -> String {
+        // Characters are at most four bytes long, so only the last three
+        // bytes can be the start of one which is not complete
+        let len = self.output.len();
+        let split = (len.saturating_sub(3)..len)
+            .rev()
+            .find(|&i| self.output[i] & 0xC0 != 0x80)
+            .filter(|&i| {
+                std::str::from_utf8(&self.output[i..]).is_err_and(|e| e.error_len().is_none())
+            })
+            .filter(|_| !finished)
+            .unwrap_or(len);
+
+        let text = String::from_utf8_lossy(&self.output[..split]).into_owned();
+        self.output.drain(..split);
+        text
+    }
+
+    /// Whether the machine has reached a breakpoint whose condition holds.
+    fn at_breakpoint(&self) -> bool {
+        let pc = self.machine.pc();
+
+        self.machine.at_breakpoint()
+            || self.breakpoints.iter().any(|breakpoint| {
+                breakpoint.pc == pc
+                    && breakpoint
+                        .condition
+                        .as_ref()
+                        .is_none_or(|condition| condition.holds(&self.machine))
+            })
+    }
+}
+
+/// Why the program stopped running.
+enum Stop {
+    /// The program reached a breakpoint.
+    Breakpoint,
+    /// A step finished.
+    Step,
+    /// The editor asked to pause the program.
+    Pause,
+    /// The program finished.
+    Finished,
+    /// The editor sent a request which ends the session.
+    Interrupted,
+}
+
+struct Session<W: Write> {
+    requests: Receiver<Value>,
+    pending: VecDeque<Value>,
+    out: W,
+    seq: u64,
+    lines_start_at_1: bool,
+    columns_start_at_1: bool,
+    program: Option<Program>,
+}
+
+impl<W: Write> Session<W> {
+    fn new(requests: Receiver<Value>, out: W) -> Self {
+        Self {
+            requests,
+            pending: VecDeque::new(),
+            out,
+            seq: 0,
+            lines_start_at_1: true,
+            columns_start_at_1: true,
+            program: None,
+        }
+    }
+
+    fn serve(&mut self) -> std::io::Result<()> {
+        loop {
+            let request = match self.pending.pop_front() {
+                Some(request) => request,
+                None => match self.requests.recv() {
+                    Ok(request) => request,
+                    Err(_) => return Ok(()),
+                },
+            };
+
+            if !self.handle(&request)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handle a request, returning whether the session goes on.
+    fn handle(&mut self, request: &Value) -> std::io::Result<bool> {
+        let command = request["command"].as_str().unwrap_or_default();
+        let args = &request["arguments"];
+
+        match command {
+            "initialize" => {
+                self.lines_start_at_1 = args["linesStartAt1"].as_bool().unwrap_or(true);
+                self.columns_start_at_1 = args["columnsStartAt1"].as_bool().unwrap_or(true);
+                let capabilities = json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportsConditionalBreakpoints": true,
+                    "supportsStepBack": true,
+                    "supportsReadMemoryRequest": true,
+                    "supportsTerminateRequest": true,
+                });
+                self.respond(request, Ok(capabilities))?;
+            }
+            "launch" => {
+                let res = self.launch(args);
+                let launched = res.is_ok();
+                self.respond(request, res.map(|_| Value::Null))?;
+                if launched {
+                    self.event("initialized", Value::Null)?;
+                }
+            }
+            "setBreakpoints" => {
+                let body = self.set_breakpoints(args);
+                self.respond(request, Ok(body))?;
+            }
+            "configurationDone" => {
+                self.respond(request, Ok(Value::Null))?;
+                match self.program {
+                    Some(ref program) if program.stop_on_entry => {
+                        self.stopped("entry")?;
+                    }
+                    Some(ref program) if program.at_breakpoint() => {
+                        self.stopped("breakpoint")?;
+                    }
+                    Some(_) => {
+                        let stop = self.run(|_| false)?;
+                        self.report(stop)?;
+                    }
+                    None => {}
+                }
+            }
+            "threads" => {
+                let threads = json!({ "threads": [{ "id": 1, "name": "main" }] });
+                self.respond(request, Ok(threads))?;
+            }
+            "stackTrace" => {
+                let frames = self.stack_trace();
+                let body = json!({ "stackFrames": frames, "totalFrames": frames.len() });
+                self.respond(request, Ok(body))?;
+            }
+            "scopes" => {
+                let scopes = json!({ "scopes": [
+                    { "name": "Machine", "variablesReference": MACHINE_SCOPE, "expensive": false },
+                    { "name": "Memory", "variablesReference": MEMORY_SCOPE, "expensive": false },
+                ]});
+                self.respond(request, Ok(scopes))?;
+            }
+            "variables" => {
+                let body =
+                    json!({ "variables": self.variables(args["variablesReference"].as_u64()) });
+                self.respond(request, Ok(body))?;
+            }
+            "readMemory" => {
+                let res = self.read_memory(args);
+                self.respond(request, res)?;
+            }
+            "continue" => {
+                self.respond(request, Ok(json!({ "allThreadsContinued": true })))?;
+                let stop = self.run(|_| false)?;
+                self.report(stop)?;
+            }
+            "next" => {
+                // Step over a whole loop when at its start
+                self.respond(request, Ok(Value::Null))?;
+                let end = match self.current_instruction() {
+                    Some(Instruction::LoopStart(end)) => end,
+                    _ => 0,
+                };
+                let stop = self.run(|machine| machine.pc() > end)?;
+                self.report(stop)?;
+            }
+            "stepIn" => {
+                self.respond(request, Ok(Value::Null))?;
+                let stop = self.run(|_| true)?;
+                self.report(stop)?;
+            }
+            "stepOut" => {
+                self.respond(request, Ok(Value::Null))?;
+                let end = self.enclosing_loop_end();
+                let stop = self.run(|machine| end.is_none_or(|end| machine.pc() > end))?;
+                self.report(stop)?;
+            }
+            "stepBack" => {
+                self.respond(request, Ok(Value::Null))?;
+                if let Some(ref mut program) = self.program {
+                    program.machine.step_back();
+                }
+                self.stopped("step")?;
+            }
+            "reverseContinue" => {
+                self.respond(request, Ok(Value::Null))?;
+                if let Some(ref mut program) = self.program {
+                    while program.machine.step_back() && !program.at_breakpoint() {}
+                }
+                self.stopped("breakpoint")?;
+            }
+            "pause" => {
+                // The program is not running, as pausing a running program
+                // is handled while running it
+                self.respond(request, Ok(Value::Null))?;
+                self.stopped("pause")?;
+            }
+            "disconnect" | "terminate" => {
+                self.respond(request, Ok(Value::Null))?;
+                if command == "terminate" {
+                    self.event("terminated", Value::Null)?;
+                }
+                return Ok(false);
+            }
+            "setExceptionBreakpoints" => self.respond(request, Ok(Value::Null))?,
+            _ => {
+                let msg = format!("unsupported request '{}'", command);
+                self.respond(request, Err(msg))?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn launch(&mut self, args: &Value) -> Result<(), String> {
+        let path = args["program"]
+            .as_str()
+            .ok_or("missing program to launch")?;
+        let src = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let (code, spans) = lex_with_spans(&src).map_err(|e| e.to_string())?;
+
+        let line_starts = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let input = args["input"].as_str().unwrap_or_default();
+
+        self.program = Some(Program {
+            path: path.to_string(),
+            src,
+            line_starts,
+            spans,
+            machine: Machine::with_history(&code, HISTORY),
+            input: Cursor::new(input.as_bytes().to_vec()),
+            breakpoints: Vec::new(),
+            stop_on_entry: args["stopOnEntry"].as_bool().unwrap_or(false),
+            output: Vec::new(),
+        });
+
+        Ok(())
+    }
+
+    fn set_breakpoints(&mut self, args: &Value) -> Value {
+        let line_base = self.lines_start_at_1 as usize;
+        let requested = args["breakpoints"].as_array().cloned().unwrap_or_default();
+
+        let mut breakpoints = Vec::new();
+        let mut verified = Vec::new();
+        for requested in requested {
+            let line = requested["line"].as_u64().unwrap_or(0) as usize;
+            let condition = requested["condition"].as_str().map(str::parse::<Condition>);
+
+            let pc = self
+                .program
+                .as_ref()
+                .and_then(|program| program.instruction_at(line.checked_sub(line_base)?));
+            let (Some(pc), Some(program)) = (pc, self.program.as_ref()) else {
+                verified.push(json!({ "verified": false, "message": "no instruction on line" }));
+                continue;
+            };
+
+            match condition.transpose() {
+                Ok(condition) => {
+                    let (line, column) = program.position(program.spans[pc].start);
+                    verified.push(json!({
+                        "verified": true,
+                        "line": line + line_base,
+                        "column": column + self.columns_start_at_1 as usize,
+                    }));
+                    breakpoints.push(Breakpoint { pc, condition });
+                }
+                Err(e) => verified.push(json!({ "verified": false, "message": e.to_string() })),
+            }
+        }
+
+        if let Some(ref mut program) = self.program {
+            program.breakpoints = breakpoints;
+        }
+
+        json!({ "breakpoints": verified })
+    }
+
+    fn current_instruction(&self) -> Option<Instruction> {
+        self.program
+            .as_ref()?
+            .machine
+            .current_instruction()
+            .cloned()
+    }
+
+    /// Get the end of the innermost loop containing the next instruction.
+    fn enclosing_loop_end(&self) -> Option<usize> {
+        let machine = &self.program.as_ref()?.machine;
+        let pc = machine.pc();
+
+        machine.program()[..pc]
+            .iter()
+            .rev()
+            .find_map(|instruction| match *instruction {
+                Instruction::LoopStart(end) if end >= pc => Some(end),
+                _ => None,
+            })
+    }
+
+    /// Run the program until it finishes, hits a breakpoint or a condition is
+    /// met after a step.
+    ///
+    /// A breakpoint on the instruction the program is resumed from is not
+    /// hit again.
+    fn run<F>(&mut self, mut done: F) -> std::io::Result<Stop>
+    where
+        F: FnMut(&Machine) -> bool,
+    {
+        if self.program.is_none() {
+            return Ok(Stop::Finished);
+        }
+
+        let mut resumed = true;
+        loop {
+            let stop = self.run_steps(&mut done, resumed)?;
+            resumed = false;
+
+            let finished = matches!(stop, Some(Stop::Finished));
+            let output = self.program.as_mut().unwrap().take_output(finished);
+            if !output.is_empty() {
+                self.output(&output)?;
+            }
+            if let Some(stop) = stop.map_or_else(|| self.poll(), |stop| Ok(Some(stop)))? {
+                return Ok(stop);
+            }
+        }
+    }
+
+    /// Run a limited number of steps of the program, returning why it stopped
+    /// if it did.
+    ///
+    /// Breakpoints are checked before every step, except for the first step
+    /// when the program is being resumed.
+    fn run_steps<F>(&mut self, done: &mut F, resumed: bool) -> std::io::Result<Option<Stop>>
+    where
+        F: FnMut(&Machine) -> bool,
+    {
+        let program = self.program.as_mut().unwrap();
+
+        for i in 0..STEPS_PER_POLL {
+            if !(resumed && i == 0) && program.at_breakpoint() {
+                return Ok(Some(Stop::Breakpoint));
+            }
+
+            let stepped = program
+                .machine
+                .step(&mut program.input, &mut program.output)
+                .map_err(std::io::Error::other)?;
+
+            if !stepped || program.machine.is_finished() {
+                return Ok(Some(Stop::Finished));
+            }
+            if done(&program.machine) {
+                return Ok(Some(Stop::Step));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Handle the requests which arrived while the program was running,
+    /// returning whether it should stop.
+    fn poll(&mut self) -> std::io::Result<Option<Stop>> {
+        loop {
+            let request = match self.requests.try_recv() {
+                Ok(request) => request,
+                Err(TryRecvError::Empty) => return Ok(None),
+                Err(TryRecvError::Disconnected) => return Ok(Some(Stop::Interrupted)),
+            };
+
+            match request["command"].as_str().unwrap_or_default() {
+                "pause" => {
+                    self.respond(&request, Ok(Value::Null))?;
+                    return Ok(Some(Stop::Pause));
+                }
+                "disconnect" | "terminate" => {
+                    self.pending.push_back(request);
+                    return Ok(Some(Stop::Interrupted));
+                }
+                "threads" => {
+                    self.handle(&request)?;
+                }
+                _ => self.pending.push_back(request),
+            }
+        }
+    }
+
+    /// Tell the editor why the program stopped running.
+    fn report(&mut self, stop: Stop) -> std::io::Result<()> {
+        match stop {
+            Stop::Breakpoint => self.stopped("breakpoint"),
+            Stop::Step => self.stopped("step"),
+            Stop::Pause => self.stopped("pause"),
+            Stop::Finished => {
+                self.event("exited", json!({ "exitCode": 0 }))?;
+                self.event("terminated", Value::Null)
+            }
+            Stop::Interrupted => Ok(()),
+        }
+    }
+
+    fn stack_trace(&self) -> Vec<Value> {
+        let Some(ref program) = self.program else {
+            return Vec::new();
+        };
+
+        let offset = program
+            .spans
+            .get(program.machine.pc())
+            .map_or(program.src.len(), |span| span.start);
+        let (line, column) = program.position(offset);
+        let name = std::path::Path::new(&program.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+
+        vec![json!({
+            "id": 0,
+            "name": "main",
+            "source": { "name": name, "path": program.path },
+            "line": line + self.lines_start_at_1 as usize,
+            "column": column + self.columns_start_at_1 as usize,
+        })]
+    }
+
+    fn variables(&self, reference: Option<u64>) -> Vec<Value> {
+        let Some(ref program) = self.program else {
+            return Vec::new();
+        };
+        let machine = &program.machine;
+        let memory = machine.memory();
+        let ptr = machine.ptr();
+
+        match reference {
+            Some(MACHINE_SCOPE) => {
+                let instruction = match machine.current_instruction() {
+                    Some(instruction) => format!("{:?}", instruction),
+                    None => "finished".to_string(),
+                };
+
+                vec![
+                    variable("pointer", ptr.to_string()),
+                    json!({
+                        "name": "cell",
+                        "value": memory[ptr].to_string(),
+                        "variablesReference": 0,
+                        "memoryReference": ptr.to_string(),
+                    }),
+                    variable("instruction", instruction),
+                ]
+            }
+            Some(MEMORY_SCOPE) => {
+                let start = ptr.saturating_sub(MEMORY_WINDOW);
+                let end = (ptr + MEMORY_WINDOW + 1).min(memory.len());
+
+                (start..end)
+                    .map(|i| variable(&format!("[{}]", i), memory[i].to_string()))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn read_memory(&self, args: &Value) -> Result<Value, String> {
+        let program = self.program.as_ref().ok_or("no program is running")?;
+        let memory = program.machine.memory();
+
+        let base: i64 = args["memoryReference"]
+            .as_str()
+            .and_then(|reference| reference.parse().ok())
+            .ok_or("invalid memory reference")?;
+        let start = (base + args["offset"].as_i64().unwrap_or(0)).clamp(0, memory.len() as i64);
+        let start = start as usize;
+        let count = args["count"].as_u64().unwrap_or(0) as usize;
+        let data = &memory[start..start.saturating_add(count).min(memory.len())];
+
+        Ok(json!({
+            "address": format!("0x{:x}", start),
+            "data": base64(data),
+            "unreadableBytes": count - data.len(),
+        }))
+    }
+
+    fn stopped(&mut self, reason: &str) -> std::io::Result<()> {
+        let body = json!({ "reason": reason, "threadId": 1, "allThreadsStopped": true });
+        self.event("stopped", body)
+    }
+
+    fn output(&mut self, text: &str) -> std::io::Result<()> {
+        self.event("output", json!({ "category": "stdout", "output": text }))
+    }
+
+    fn respond(&mut self, request: &Value, res: Result<Value, String>) -> std::io::Result<()> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": res.is_ok(),
+        });
+
+        match res {
+            Ok(Value::Null) => {}
+            Ok(body) => response["body"] = body,
+            Err(msg) => response["message"] = json!(msg),
+        }
+
+        self.send(response)
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> std::io::Result<()> {
+        let mut message = json!({ "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+
+        self.send(message)
+    }
+
+    fn send(&mut self, mut message: Value) -> std::io::Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+
+        write_message(&mut self.out, &message)
+    }
+}
+
+fn variable(name: &str, value: String) -> Value {
+    json!({ "name": name, "value": value, "variablesReference": 0 })
+}
+
+/// Encode bytes as base64, as memory is sent to the editor.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+
+    res
+}
@@ -0,0 +1,124 @@
+//! Interactive command line debugger.
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::machine::{Condition, Machine};
+use brainfuck_lexer::Block;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  step [n]      Execute the next n instructions (s)
+  back [n]      Undo the last n instructions (b)
+  continue      Run until the program finishes or hits a breakpoint (c)
+  break [cond]  Also stop when a condition like 'cell 5 == 42' becomes
+                true, or list the conditions without one (br)
+  delete n      Remove the nth condition (d)
+  memory [n]    Show n cells around the pointer (m)
+  help          Show this message (h)
+  quit          Stop debugging (q)";
+
+/// Debug a program, reading commands from the standard input.
+pub fn debug(src: &Block, history: usize) -> Result<(), BrainfuckError> {
+    let mut machine = Machine::with_history(src, history);
+    let mut conditions: Vec<Condition> = Vec::new();
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    print_state(&machine);
+
+    loop {
+        print!("(bf) ");
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("step");
+        let args: Vec<_> = words.collect();
+        let count = args.first().and_then(|n| n.parse().ok());
+
+        match command {
+            "s" | "step" => {
+                for _ in 0..count.unwrap_or(1) {
+                    if !machine.step(&mut std::io::stdin(), &mut stdout)? {
+                        break;
+                    }
+                }
+            }
+            "b" | "back" => {
+                for _ in 0..count.unwrap_or(1) {
+                    if !machine.step_back() {
+                        println!("no more history");
+                        break;
+                    }
+                }
+            }
+            "c" | "continue" => {
+                // Conditions only stop the machine when they become true, so
+                // continuing from a stop doesn't stop again right away
+                let mut held: Vec<_> = conditions.iter().map(|c| c.holds(&machine)).collect();
+                let stop = |machine: &Machine| {
+                    let mut hit = machine.at_breakpoint();
+                    for (condition, held) in conditions.iter().zip(&mut held) {
+                        let holds = condition.holds(machine);
+                        hit |= holds && !*held;
+                        *held = holds;
+                    }
+                    hit
+                };
+
+                if machine.run_until(&mut std::io::stdin(), &mut stdout, stop)? {
+                    println!("stopped");
+                }
+            }
+            "br" | "break" if args.is_empty() => {
+                for (i, condition) in conditions.iter().enumerate() {
+                    println!("{}: {}", i, condition);
+                }
+            }
+            "br" | "break" => match args.join(" ").parse() {
+                Ok(condition) => conditions.push(condition),
+                Err(e) => println!("{}", e),
+            },
+            "d" | "delete" => match count {
+                Some(i) if i < conditions.len() => {
+                    conditions.remove(i);
+                }
+                _ => println!("no such condition"),
+            },
+            "m" | "memory" => print_memory(&machine, count.unwrap_or(16)),
+            "h" | "help" => println!("{}", HELP),
+            "q" | "quit" => return Ok(()),
+            _ => println!("unknown command '{}', try 'help'", command),
+        }
+
+        print_state(&machine);
+    }
+}
+
+fn print_state(machine: &Machine) {
+    match machine.current_instruction() {
+        Some(instruction) => println!(
+            "pc {} ptr {} cell {}: {:?}",
+            machine.pc(),
+            machine.ptr(),
+            machine.memory()[machine.ptr()],
+            instruction
+        ),
+        None => println!("program finished"),
+    }
+}
+
+fn print_memory(machine: &Machine, count: usize) {
+    let memory = machine.memory();
+    let start = machine.ptr().saturating_sub(count / 2);
+    let end = (start + count).min(memory.len());
+
+    for (i, cell) in memory.iter().enumerate().take(end).skip(start) {
+        let marker = if i == machine.ptr() { '>' } else { ' ' };
+        println!("{}{:>6}: {:>3}", marker, i, cell);
+    }
+}
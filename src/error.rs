@@ -1,6 +1,6 @@
 //! Errors used in the crate
 //!
-use brainfuck_lexer::error::LexerError;
+use brainfuck_lexer::error::{LexerError, PreprocessorError};
 
 /// The error type of any interpreter error.
 #[derive(Debug)]
@@ -9,6 +9,24 @@ pub enum BrainfuckError {
     IOError(std::io::Error),
     /// Error with lexical analysis.
     ParserError(LexerError),
+    /// Error with preprocessing.
+    PreprocessorError(PreprocessorError),
+    /// The program was cancelled by the host before it finished.
+    Cancelled,
+    /// The program executed more instructions than its limit.
+    StepLimitExceeded(u64),
+    /// The program printed more bytes than its limit.
+    OutputLimitExceeded(u64),
+    /// The program read more bytes than its limit.
+    InputLimitExceeded(u64),
+    /// The program used more memory locations than its limit.
+    TapeLimitExceeded(usize),
+    /// Arithmetic took the value of a memory location out of the range of a
+    /// byte.
+    CellOverflow(isize),
+    /// The interpreter panicked while running the program, with the message
+    /// of the panic.
+    Panicked(String),
 }
 
 impl From<std::io::Error> for BrainfuckError {
@@ -22,3 +40,51 @@ impl From<LexerError> for BrainfuckError {
         Self::ParserError(e)
     }
 }
+
+impl From<PreprocessorError> for BrainfuckError {
+    fn from(e: PreprocessorError) -> Self {
+        Self::PreprocessorError(e)
+    }
+}
+
+impl std::fmt::Display for BrainfuckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "io error: {}", e),
+            Self::ParserError(e) => write!(f, "parser error: {}", e),
+            Self::PreprocessorError(e) => write!(f, "preprocessor error: {}", e),
+            Self::Cancelled => write!(f, "program was cancelled"),
+            Self::StepLimitExceeded(max) => {
+                write!(f, "program exceeded the limit of {} steps", max)
+            }
+            Self::OutputLimitExceeded(max) => {
+                write!(f, "program exceeded the limit of {} output bytes", max)
+            }
+            Self::InputLimitExceeded(max) => {
+                write!(f, "program exceeded the limit of {} input bytes", max)
+            }
+            Self::TapeLimitExceeded(max) => {
+                write!(f, "program exceeded the limit of {} memory cells", max)
+            }
+            Self::CellOverflow(ptr) => write!(f, "value of memory cell {} overflowed", ptr),
+            Self::Panicked(message) => write!(f, "interpreter panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for BrainfuckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IOError(e) => Some(e),
+            Self::ParserError(e) => Some(e),
+            Self::PreprocessorError(e) => Some(e),
+            Self::Cancelled
+            | Self::StepLimitExceeded(_)
+            | Self::OutputLimitExceeded(_)
+            | Self::InputLimitExceeded(_)
+            | Self::TapeLimitExceeded(_)
+            | Self::CellOverflow(_)
+            | Self::Panicked(_) => None,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Flattened Brainfuck programs.
+//!
+//! A [`Block`] nests the body of every closure inside of it, so running it
+//! means walking a tree. Flattening the program turns it into a single array
+//! of instructions, where the brackets of every loop know the index of the
+//! matching bracket. The program can then be run with an index as the program
+//! counter, jumping between the brackets. The program is lowered to the
+//! normalized [`Op`]s of the lexer while it is flattened.
+
+use brainfuck_lexer::ir::{drop_ops, lower, Op};
+use brainfuck_lexer::Block;
+
+/// A single instruction of a flattened program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    /// Any operation other than an [`Op::Loop`].
+    Op(Op),
+    /// Start of a loop, holding the index of the matching [`Instruction::LoopEnd`].
+    LoopStart(usize),
+    /// End of a loop, holding the index of the matching [`Instruction::LoopStart`].
+    LoopEnd(usize),
+}
+
+/// Print an instruction like the [`Op`] it holds, with loops printed as
+/// jumps to the instruction after their matching bracket: `jz` jumps if the
+/// current memory location is zero, and `jnz` if it is not.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::flat::flatten;
+///
+/// let program: Vec<_> = flatten(&lex("+[>]").unwrap())
+///     .iter()
+///     .map(ToString::to_string)
+///     .collect();
+///
+/// assert_eq!(program, ["add 1", "jz 4", "move 1", "jnz 2"]);
+/// ```
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Op(op) => write!(f, "{}", op),
+            Instruction::LoopStart(end) => write!(f, "jz {}", end + 1),
+            Instruction::LoopEnd(start) => write!(f, "jnz {}", start + 1),
+        }
+    }
+}
+
+/// Flatten a program to an array of instructions.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to flatten.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::ir::Op;
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::flat::{flatten, Instruction};
+///
+/// let program = flatten(&lex("+[>]").unwrap());
+///
+/// assert_eq!(
+///     program,
+///     [
+///         Instruction::Op(Op::Add(1)),
+///         Instruction::LoopStart(3),
+///         Instruction::Op(Op::Move(1)),
+///         Instruction::LoopEnd(1),
+///     ]
+/// );
+/// ```
+pub fn flatten(src: &Block) -> Vec<Instruction> {
+    let ops = lower(src);
+    let mut program = Vec::new();
+    // The operations left of every loop being flattened, with the index of
+    // its start, innermost last
+    let mut stack = vec![(ops.iter(), None)];
+
+    while let Some((ops, start)) = stack.last_mut() {
+        match ops.next() {
+            Some(Op::Loop(inner)) => {
+                let start = program.len();
+                program.push(Instruction::LoopStart(0));
+                stack.push((inner.iter(), Some(start)));
+            }
+            Some(op) => program.push(Instruction::Op(op.clone())),
+            None => {
+                if let Some(start) = *start {
+                    let end = program.len();
+                    program.push(Instruction::LoopEnd(start));
+                    program[start] = Instruction::LoopStart(end);
+                }
+                stack.pop();
+            }
+        }
+    }
+
+    drop_ops(ops);
+    program
+}
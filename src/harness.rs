@@ -0,0 +1,150 @@
+//! Test runner for collections of Brainfuck programs.
+//!
+//! Every `.b` or `.bf` file in a directory with an `.out` file next to it is
+//! a test. The program is run with the content of the `.in` file next to it
+//! as its input, or no input if there is none, and passes if its output is
+//! the content of the `.out` file. The programs run in parallel, and the
+//! report lists them in order once all of them have finished.
+
+use brainfuck_interpreter::batch::{run_many_with_config, BatchConfig, RunResult};
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_lexer::optimizer::Optimizer;
+use brainfuck_lexer::{lex_unoptimized, Block};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A program with its input.
+type Program = (Block, Vec<u8>);
+
+/// Run the tests in a directory and its subdirectories, printing a report.
+///
+/// Returns whether all of the tests passed.
+pub fn test(dir: &Path, optimizer: &Optimizer, timeout: Duration) -> std::io::Result<bool> {
+    let mut programs = Vec::new();
+    find_programs(dir, &mut programs)?;
+    programs.sort();
+
+    let tests: Vec<_> = programs
+        .into_iter()
+        .filter(|path| path.with_extension("out").is_file())
+        .collect();
+    let plural = if tests.len() == 1 { "" } else { "s" };
+    println!("running {} test{}", tests.len(), plural);
+
+    // Every test is loaded up front, so the programs can run in parallel
+    let mut programs = Vec::new();
+    let mut loaded = Vec::new();
+    for path in &tests {
+        let (expected, program) = load(path, optimizer)?;
+        let lex_error = program.map(|program| programs.push(program)).err();
+        loaded.push((expected, lex_error));
+    }
+
+    let config = BatchConfig {
+        timeout: Some(timeout),
+        ..Default::default()
+    };
+    let mut results = run_many_with_config(&programs, &config).into_iter();
+
+    let mut failed = Vec::new();
+    for (path, (expected, lex_error)) in tests.iter().zip(loaded) {
+        let name = path.strip_prefix(dir).unwrap_or(path).display();
+        let res = match lex_error {
+            Some(e) => Err(e),
+            None => check(&expected, results.next().unwrap(), timeout),
+        };
+
+        match res {
+            Ok(()) => println!("test {} ... ok", name),
+            Err(reason) => {
+                println!("test {} ... FAILED", name);
+                failed.push((name, reason));
+            }
+        }
+    }
+
+    for (name, reason) in &failed {
+        println!("\n---- {} ----\n{}", name, reason.trim_end());
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed",
+        if failed.is_empty() { "ok" } else { "FAILED" },
+        tests.len() - failed.len(),
+        failed.len()
+    );
+
+    Ok(failed.is_empty())
+}
+
+fn find_programs(dir: &Path, programs: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            find_programs(&path, programs)?;
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("b" | "bf")
+        ) {
+            programs.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the expected output of a test, and its program with its input,
+/// or why the program could not be lexed.
+fn load(path: &Path, optimizer: &Optimizer) -> std::io::Result<(Vec<u8>, Result<Program, String>)> {
+    let src = std::fs::read_to_string(path)?;
+    let expected = std::fs::read(path.with_extension("out"))?;
+    let input = match path.with_extension("in") {
+        input if input.is_file() => std::fs::read(input)?,
+        _ => Vec::new(),
+    };
+
+    let program = match lex_unoptimized(src) {
+        Ok(code) => Ok((optimizer.optimize(&code), input)),
+        Err(e) => Err(BrainfuckError::from(e).to_string()),
+    };
+
+    Ok((expected, program))
+}
+
+/// Check the result of a test, returning why it failed if it did.
+fn check(expected: &[u8], res: RunResult, timeout: Duration) -> Result<(), String> {
+    match res.error {
+        None if res.output == expected => Ok(()),
+        None => Err(diff(expected, &res.output)),
+        Some(BrainfuckError::Cancelled) => {
+            Err(format!("timed out after {} seconds", timeout.as_secs_f64()))
+        }
+        Some(e) => Err(e.to_string()),
+    }
+}
+
+/// Describe the differing lines of the expected and actual output.
+fn diff(expected: &[u8], actual: &[u8]) -> String {
+    let expected = String::from_utf8_lossy(expected);
+    let actual = String::from_utf8_lossy(actual);
+    let expected: Vec<_> = expected.split_inclusive('\n').collect();
+    let actual: Vec<_> = actual.split_inclusive('\n').collect();
+
+    let mut res = String::from("output differs (- expected, + actual):\n");
+    for i in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(i), actual.get(i));
+        if old == new {
+            continue;
+        }
+
+        if let Some(line) = old {
+            res.push_str(&format!("-{:?}\n", line));
+        }
+        if let Some(line) = new {
+            res.push_str(&format!("+{:?}\n", line));
+        }
+    }
+
+    res
+}
@@ -1,12 +1,205 @@
 //! Brainfuck interpreter.
 
 use crate::error::BrainfuckError;
+use crate::flat::{flatten, Instruction};
+use crate::io::{IoHandler, StdIo};
+use crate::observer::{Context, IoEvent, Observer};
+use crate::stats::{Counter, ExecutionStats, Heatmap, Recorder};
+use crate::tape::{BidirectionalTape, SparseTape, Tape, TapeKind};
+use brainfuck_lexer::ir::Op;
+#[cfg(feature = "async")]
+use brainfuck_lexer::ir::{drop_ops, lower};
 #[cfg(feature = "precompiled_patterns")]
 use brainfuck_lexer::lexer::PreCompiledPattern;
-use brainfuck_lexer::{Block, Token};
-use std::io::Read;
+use brainfuck_lexer::Block;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-const HEAP_SIZE: usize = 30_000;
+pub(crate) const HEAP_SIZE: usize = 30_000;
+/// Number of tokens to execute between each yield to the async runtime.
+#[cfg(feature = "async")]
+const ASYNC_YIELD_INTERVAL: usize = 1024;
+
+/// Options for interpreting Brainfuck programs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterpreterConfig {
+    /// The kind of memory to run the program on.
+    pub tape: TapeKind,
+    /// The resources the program may use.
+    pub limits: Limits,
+    /// What happens when a memory location goes out of the range of a byte.
+    pub overflow: OverflowBehavior,
+}
+
+/// What happens when arithmetic takes a memory location below 0 or above
+/// 255.
+///
+/// Instructions which were combined by the lexer are checked as a whole,
+/// which gives the same result as checking them one at a time as they all
+/// change the memory location in the same direction. Most optimization
+/// passes assume wrapping arithmetic, and the patterns they generate always
+/// wrap, so the passes in [`WRAPPING_PASSES`] must be disabled when using
+/// any other behavior.
+///
+/// [`WRAPPING_PASSES`]: brainfuck_lexer::optimizer::WRAPPING_PASSES
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowBehavior {
+    /// Wrap around to the other end of the range.
+    #[default]
+    Wrap,
+    /// Stay at the end of the range.
+    Saturate,
+    /// Stop the program with a [`BrainfuckError::CellOverflow`].
+    Error,
+}
+
+/// Limits on the resources a program may use, for running untrusted
+/// programs.
+///
+/// A program going over a limit is stopped with an error. Every limit is
+/// disabled by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum number of instructions to execute, counting both brackets
+    /// of a loop.
+    pub max_steps: Option<u64>,
+    /// Maximum number of bytes to print.
+    pub max_output: Option<u64>,
+    /// Maximum number of bytes to read.
+    pub max_input: Option<u64>,
+    /// Maximum number of memory locations the tape may allocate.
+    ///
+    /// A program on a wrapping tape is stopped when it moves the pointer past
+    /// this many locations instead.
+    pub max_cells: Option<usize>,
+}
+
+/// Resources a program may still use.
+struct Meter<'a> {
+    limits: &'a Limits,
+    tape: TapeKind,
+    steps: u64,
+}
+
+impl<'a> Meter<'a> {
+    fn new(limits: &'a Limits, tape: TapeKind) -> Self {
+        Self {
+            limits,
+            tape,
+            steps: limits.max_steps.unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Count an instruction about to be executed.
+    fn step(&mut self) -> Result<(), BrainfuckError> {
+        match self.steps.checked_sub(1) {
+            Some(steps) => self.steps = steps,
+            None => return Err(BrainfuckError::StepLimitExceeded(self.steps_limit())),
+        }
+
+        Ok(())
+    }
+
+    /// Check the memory after executing an operation.
+    ///
+    /// The memory of a wrapping tape is allocated up front, so only the
+    /// locations before the limit may be pointed to instead.
+    fn check_memory<T>(&self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        let used = match self.tape {
+            TapeKind::Wrapping => ctx.ptr as usize + 1,
+            _ => ctx.memory.allocated(),
+        };
+
+        match self.limits.max_cells {
+            Some(max) if used > max => Err(BrainfuckError::TapeLimitExceeded(max)),
+            _ => Ok(()),
+        }
+    }
+
+    fn steps_limit(&self) -> u64 {
+        self.limits.max_steps.unwrap_or(u64::MAX)
+    }
+}
+
+/// The limits are checked by observing the program.
+impl Observer for Meter<'_> {
+    fn before_instruction<T>(&mut self, _ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.step()
+    }
+
+    fn after_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.check_memory(ctx)
+    }
+}
+
+/// Input and output of a program, counting the bytes passing through them.
+struct MeteredIo<'a, H> {
+    io: &'a mut H,
+    limits: &'a Limits,
+    output: u64,
+    input: u64,
+    /// The limit the program went over, as handlers can only fail with I/O
+    /// errors.
+    exceeded: Option<BrainfuckError>,
+}
+
+impl<'a, H> MeteredIo<'a, H> {
+    fn new(io: &'a mut H, limits: &'a Limits) -> Self {
+        Self {
+            io,
+            limits,
+            output: limits.max_output.unwrap_or(u64::MAX),
+            input: limits.max_input.unwrap_or(u64::MAX),
+            exceeded: None,
+        }
+    }
+
+    /// Remember the limit the program went over, and stop it.
+    fn exceed(&mut self, err: BrainfuckError) -> std::io::Error {
+        self.exceeded = Some(err);
+        std::io::Error::other("resource limit exceeded")
+    }
+}
+
+/// Only bytes actually read count towards the input limit, so reading past
+/// the end of the input does not.
+impl<H: IoHandler> IoHandler for MeteredIo<'_, H> {
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.io.read_byte()?;
+
+        if byte.is_some() {
+            match self.input.checked_sub(1) {
+                Some(input) => self.input = input,
+                None => {
+                    let max = self.limits.max_input.unwrap_or(u64::MAX);
+                    return Err(self.exceed(BrainfuckError::InputLimitExceeded(max)));
+                }
+            }
+        }
+
+        Ok(byte)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        match self.output.checked_sub(1) {
+            Some(output) => self.output = output,
+            None => {
+                let max = self.limits.max_output.unwrap_or(u64::MAX);
+                return Err(self.exceed(BrainfuckError::OutputLimitExceeded(max)));
+            }
+        }
+
+        self.io.write_byte(byte)
+    }
+}
 
 /// Interpret Brainfuck program with [`std::io::Stdin`] and [`std::io::Stdout`].
 ///
@@ -60,87 +253,697 @@ where
     I: std::io::Read,
     O: std::io::Write,
 {
-    let mut memory = [0u8; HEAP_SIZE];
+    interpret_with_cancel(src, input, out, &AtomicBool::new(false))
+}
+
+/// Interpret Brainfuck program until it finishes or is cancelled.
+///
+/// The cancellation flag is checked on every iteration of a loop, so a host
+/// application can stop a runaway program by setting it from another thread.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `input` - The input stream.
+/// * `out` - The output stream.
+/// * `cancel` - Flag to set to stop the interpreter.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::error::BrainfuckError;
+/// use brainfuck_interpreter::interpreter::interpret_with_cancel;
+/// use std::io::Cursor;
+/// use std::sync::atomic::AtomicBool;
+///
+/// let src = "+[>+<]".to_string(); // Never terminates
+/// let mut input = Cursor::new(vec![]);
+/// let mut output = Vec::new();
+/// let cancel = AtomicBool::new(true);
+/// let res = interpret_with_cancel(&lex(src).unwrap(), &mut input, &mut output, &cancel);
+///
+/// assert!(matches!(res, Err(BrainfuckError::Cancelled)));
+/// ```
+///
+/// # Errors
+///
+/// If the cancellation flag is set while the program is running, this
+/// function will return a [`BrainfuckError::Cancelled`]. IO errors are
+/// reported the same way as in [`interpret`].
+pub fn interpret_with_cancel<I, O>(
+    src: &Block,
+    input: &mut I,
+    out: &mut O,
+    cancel: &AtomicBool,
+) -> Result<(), BrainfuckError>
+where
+    I: std::io::Read,
+    O: std::io::Write,
+{
+    let config = InterpreterConfig::default();
+
+    interpret_with_config(src, &mut StdIo::new(input, out), &config, cancel)
+}
+
+/// Interpret Brainfuck program with an [`IoHandler`] for its input and
+/// output.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `io` - The handler of the input and output.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::interpreter::interpret_with_handler;
+/// use brainfuck_interpreter::io::StdIo;
+/// use std::io::Cursor;
+///
+/// let src = ",.".to_string();
+/// let mut io = StdIo::new(Cursor::new(vec![b'a']), Vec::new());
+/// interpret_with_handler(&lex(src).unwrap(), &mut io).unwrap();
+///
+/// assert_eq!(io.into_inner().1, b"a");
+/// ```
+///
+/// # Errors
+///
+/// If the handler fails to either read or write a byte, this function will
+/// return a [`BrainfuckError::IOError`] with the error of the handler.
+pub fn interpret_with_handler<H>(src: &Block, io: &mut H) -> Result<(), BrainfuckError>
+where
+    H: IoHandler,
+{
+    let config = InterpreterConfig::default();
+
+    interpret_with_config(src, io, &config, &AtomicBool::new(false))
+}
+
+/// Interpret Brainfuck program with the given configuration.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `io` - The handler of the input and output.
+/// * `config` - The [`InterpreterConfig`] to interpret with.
+/// * `cancel` - Flag to set to stop the interpreter.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::interpreter::{interpret_with_config, InterpreterConfig};
+/// use brainfuck_interpreter::io::StdIo;
+/// use brainfuck_interpreter::tape::TapeKind;
+/// use std::io::Cursor;
+/// use std::sync::atomic::AtomicBool;
+///
+/// // Move left of the first cell before printing
+/// let src = "<++++++++[>++++++++<-]>+.".to_string();
+/// let config = InterpreterConfig {
+///     tape: TapeKind::Bidirectional,
+///     ..Default::default()
+/// };
+/// let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+/// interpret_with_config(&lex(src).unwrap(), &mut io, &config, &AtomicBool::new(false)).unwrap();
+///
+/// assert_eq!(io.into_inner().1, b"A");
+/// ```
+///
+/// # Errors
+///
+/// If the program goes over any of the [`Limits`] of the configuration, the
+/// error for that limit is returned. See also [`interpret_with_handler`] and
+/// [`interpret_with_cancel`].
+pub fn interpret_with_config<H>(
+    src: &Block,
+    io: &mut H,
+    config: &InterpreterConfig,
+    cancel: &AtomicBool,
+) -> Result<(), BrainfuckError>
+where
+    H: IoHandler,
+{
+    let program = flatten(src);
+
+    // Checking the limits is left out entirely when there are none
+    if config.limits == Limits::default() {
+        interpret_on_tape(&program, config, io, cancel, &mut ())
+    } else {
+        let mut meter = Meter::new(&config.limits, config.tape);
+        let mut io = MeteredIo::new(io, &config.limits);
+        interpret_on_tape(&program, config, &mut io, cancel, &mut meter)
+            .map_err(|e| io.exceeded.take().unwrap_or(e))
+    }
+}
+
+/// Interpret Brainfuck program while calling an [`Observer`] for every
+/// instruction.
+///
+/// The program is run on a [`TapeKind::Wrapping`] tape.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `io` - The handler of the input and output.
+/// * `observer` - The [`Observer`] of the program.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::interpreter::interpret_with_observer;
+/// use brainfuck_interpreter::io::StdIo;
+/// use brainfuck_interpreter::observer::{IoEvent, Observer};
+/// use std::io::Cursor;
+///
+/// /// Copies the output of a program.
+/// struct Tee(Vec<u8>);
+///
+/// impl Observer for Tee {
+///     fn on_io(&mut self, event: IoEvent) {
+///         if let IoEvent::Write(byte) = event {
+///             self.0.push(byte);
+///         }
+///     }
+/// }
+///
+/// let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+/// let mut tee = Tee(Vec::new());
+/// interpret_with_observer(&lex("++++++++[>++++++++<-]>+.").unwrap(), &mut io, &mut tee).unwrap();
+///
+/// assert_eq!(tee.0, b"A");
+/// ```
+///
+/// # Errors
+///
+/// If the observer returns an error, the program is stopped and the error is
+/// returned. See also [`interpret_with_handler`].
+pub fn interpret_with_observer<H, O>(
+    src: &Block,
+    io: &mut H,
+    observer: &mut O,
+) -> Result<(), BrainfuckError>
+where
+    H: IoHandler,
+    O: Observer,
+{
+    let config = InterpreterConfig::default();
+
+    interpret_on_tape(
+        &flatten(src),
+        &config,
+        io,
+        &AtomicBool::new(false),
+        observer,
+    )
+}
+
+/// Interpret a flattened program on memory provided by the caller.
+///
+/// Nothing is allocated while the program runs, and loops are run by jumping
+/// between their brackets, so neither the heap nor the stack grow with the
+/// program. This makes it suitable for running on microcontrollers, with the
+/// program flattened ahead of time. The memory wraps around at both ends, and
+/// is left as the program left it.
+///
+/// Some optimization passes assume the program runs on a tape of at least
+/// 30000 cells, so optimized programs must be optimized for the length of the
+/// memory with [`Optimizer::with_tape_len`].
+///
+/// [`Optimizer::with_tape_len`]: brainfuck_lexer::optimizer::Optimizer::with_tape_len
+///
+/// # Arguments
+///
+/// * `program` - The flattened program to interpret.
+/// * `memory` - The memory to run the program on.
+/// * `io` - The handler of the input and output.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::flat::flatten;
+/// use brainfuck_interpreter::interpreter::interpret_in_place;
+/// use brainfuck_interpreter::io::StdIo;
+/// use std::io::Cursor;
+///
+/// let program = flatten(&lex("++[>+++<-]>.").unwrap());
+/// let mut memory = [0u8; 16];
+/// let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+/// interpret_in_place(&program, &mut memory, &mut io).unwrap();
+///
+/// assert_eq!(memory[1], 6);
+/// assert_eq!(io.into_inner().1, [6]);
+/// ```
+///
+/// # Errors
+///
+/// If the memory is empty, a [`BrainfuckError::TapeLimitExceeded`] is
+/// returned without running the program. See also
+/// [`interpret_with_handler`].
+pub fn interpret_in_place<H>(
+    program: &[Instruction],
+    memory: &mut [u8],
+    io: &mut H,
+) -> Result<(), BrainfuckError>
+where
+    H: IoHandler,
+{
+    if memory.is_empty() {
+        return Err(BrainfuckError::TapeLimitExceeded(0));
+    }
+
+    let config = InterpreterConfig::default();
+    let cancel = AtomicBool::new(false);
+    interpret_flat(program, memory, &mut 0, io, &config, &cancel, &mut ())
+}
+
+fn interpret_on_tape<H, O>(
+    program: &[Instruction],
+    config: &InterpreterConfig,
+    io: &mut H,
+    cancel: &AtomicBool,
+    observer: &mut O,
+) -> Result<(), BrainfuckError>
+where
+    H: IoHandler,
+    O: Observer,
+{
     let mut ptr = 0;
 
-    interpret_block(src, &mut memory, &mut ptr, input, out)
+    match config.tape {
+        TapeKind::Wrapping => {
+            let mut memory = [0u8; HEAP_SIZE];
+            interpret_flat(
+                program,
+                &mut memory[..],
+                &mut ptr,
+                io,
+                config,
+                cancel,
+                observer,
+            )
+        }
+        TapeKind::Bidirectional => {
+            let mut memory = BidirectionalTape::default();
+            interpret_flat(program, &mut memory, &mut ptr, io, config, cancel, observer)
+        }
+        TapeKind::Sparse => {
+            let mut memory = SparseTape::default();
+            interpret_flat(program, &mut memory, &mut ptr, io, config, cancel, observer)
+        }
+    }
 }
 
-fn read_u8<I>(input: &mut I) -> std::io::Result<u8>
+/// Interpret Brainfuck program while collecting statistics about it.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `input` - The input stream.
+/// * `out` - The output stream.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::interpreter::interpret_with_stats;
+/// use std::io::Cursor;
+///
+/// let src = ",.".to_string();
+/// let mut input = Cursor::new(vec![b'a']);
+/// let mut output = Vec::new();
+/// let stats = interpret_with_stats(&lex(src).unwrap(), &mut input, &mut output).unwrap();
+///
+/// assert_eq!(stats.instructions_executed, 2);
+/// assert_eq!(stats.bytes_read, 1);
+/// ```
+///
+/// # Errors
+///
+/// See [`interpret`].
+pub fn interpret_with_stats<I, O>(
+    src: &Block,
+    input: &mut I,
+    out: &mut O,
+) -> Result<ExecutionStats, BrainfuckError>
 where
     I: std::io::Read,
+    O: std::io::Write,
 {
-    input.bytes().next().unwrap_or(Ok(0))
+    Ok(interpret_with_heatmap(src, input, out)?.0)
 }
 
-fn interpret_block<I, O>(
-    block: &Block,
-    memory: &mut [u8],
-    ptr: &mut usize,
+/// Interpret Brainfuck program while collecting statistics about it and
+/// counting the reads and writes of every memory location.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `input` - The input stream.
+/// * `out` - The output stream.
+///
+/// # Errors
+///
+/// See [`interpret`].
+pub fn interpret_with_heatmap<I, O>(
+    src: &Block,
     input: &mut I,
     out: &mut O,
-) -> Result<(), BrainfuckError>
+) -> Result<(ExecutionStats, Heatmap), BrainfuckError>
 where
     I: std::io::Read,
     O: std::io::Write,
 {
-    for op in block {
+    let mut input = Counter::new(input);
+    let mut out = Counter::new(out);
+    let mut recorder = Recorder::new(HEAP_SIZE);
+
+    interpret_with_observer(src, &mut StdIo::new(&mut input, &mut out), &mut recorder)?;
+
+    Ok(recorder.finish(input.count, out.count))
+}
+
+/// Interpret Brainfuck program with asynchronous streams.
+///
+/// The interpreter periodically yields back to the runtime, so long-running
+/// programs don't block the executor they are running on.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to interpret.
+/// * `input` - The asynchronous input stream.
+/// * `out` - The asynchronous output stream.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::interpreter::interpret_async;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let src = ",.".to_string();
+/// let mut input = &b"a"[..];
+/// let mut output = Vec::new();
+/// interpret_async(&lex(src).unwrap(), &mut input, &mut output).await;
+///
+/// assert_eq!(output[0], b'a');
+/// # });
+/// ```
+///
+/// # Errors
+///
+/// If the interpreter fails to either read from the input or write to the
+/// output, this function will return a [`BrainfuckError::IOError`] with the
+/// corresponding [`std::io::Error`].
+#[cfg(feature = "async")]
+pub async fn interpret_async<I, O>(
+    src: &Block,
+    input: &mut I,
+    out: &mut O,
+) -> Result<(), BrainfuckError>
+where
+    I: tokio::io::AsyncRead + Unpin,
+    O: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut memory = [0u8; HEAP_SIZE];
+    let memory = &mut memory[..];
+    let mut ptr = 0;
+    let mut steps = 0usize;
+    let ops = lower(src);
+
+    // Loops are interpreted with an explicit stack of bodies instead of
+    // recursion, as recursive async functions would need to be boxed. The
+    // index of a loop is only advanced once it is done, so reaching the end
+    // of its body re-checks the loop condition.
+    let mut stack = vec![(&ops[..], 0)];
+
+    while let Some((ops, index)) = stack.last_mut() {
+        let Some(op) = ops.get(*index) else {
+            stack.pop();
+            continue;
+        };
+
+        steps += 1;
+        if steps.is_multiple_of(ASYNC_YIELD_INTERVAL) {
+            tokio::task::yield_now().await;
+        }
+
         match op {
-            Token::Increment(x) => memory[*ptr] = memory[*ptr].wrapping_add(*x),
-            Token::Decrement(x) => memory[*ptr] = memory[*ptr].wrapping_sub(*x),
-            Token::Next(count) => *ptr = ptr.wrapping_add(*count) % memory.len(),
-            Token::Prev(count) => *ptr = ptr.wrapping_sub(*count) % memory.len(),
-            Token::Print => write!(out, "{}", memory[*ptr] as char)?,
-            Token::Input => memory[*ptr] = read_u8(input)?,
-            Token::Closure(block) => {
-                while memory[*ptr] != 0 {
-                    interpret_block(block, memory, ptr, input, out)?;
+            Op::Loop(body) => {
+                if memory.cell(ptr) != 0 {
+                    stack.push((body, 0));
+                } else {
+                    *index += 1;
                 }
+                continue;
+            }
+            Op::Add(x) => {
+                let cell = memory.cell_mut(ptr);
+                *cell = cell.wrapping_add(*x as u8);
+            }
+            Op::Move(distance) => ptr = memory.offset(ptr, *distance),
+            Op::Print => {
+                let mut buf = [0u8; 4];
+                let ch = (memory.cell(ptr) as char).encode_utf8(&mut buf);
+                out.write_all(ch.as_bytes()).await?;
+            }
+            Op::Input => {
+                let mut byte = [0u8];
+                *memory.cell_mut(ptr) = match input.read(&mut byte).await? {
+                    0 => 0,
+                    _ => byte[0],
+                };
             }
             #[cfg(feature = "debug_token")]
-            Token::Debug => writeln!(
-                out,
-                "\n{:?}",
-                memory
-                    .iter()
-                    .scan(0, |state, &cell| {
-                        if cell == 0 {
-                            *state += 1;
-                        } else {
-                            *state = 0;
-                        }
-
-                        if *state > 3 {
-                            None
-                        } else {
-                            Some(cell)
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            )?,
+            Op::Debug => {
+                let dump = format!("\n{:?}\n", memory_dump(memory));
+                out.write_all(dump.as_bytes()).await?;
+            }
             #[cfg(feature = "precompiled_patterns")]
-            Token::Pattern(pattern) => match *pattern {
-                PreCompiledPattern::SetToZero => memory[*ptr] = 0,
-                PreCompiledPattern::Multiply {
-                    dest_offset,
-                    factor,
-                } => {
-                    let dest = if dest_offset > 0 {
-                        ptr.wrapping_add(dest_offset as usize)
-                    } else {
-                        ptr.wrapping_sub(dest_offset.abs() as usize)
-                    } % memory.len();
-
-                    // First get the result of the multiplication, then add it
-                    // to the value already in the destination cell
-                    let mul_res = memory[*ptr].wrapping_mul(factor);
-                    memory[dest] = memory[dest].wrapping_add(mul_res);
-
-                    memory[*ptr] = 0;
+            Op::Pattern(pattern) => apply_pattern(pattern, memory, ptr),
+            #[cfg(feature = "breakpoint_token")]
+            Op::Breakpoint => {}
+        }
+
+        *index += 1;
+    }
+
+    drop_ops(ops);
+    out.flush().await?;
+
+    Ok(())
+}
+
+/// Run a flattened program, jumping between the brackets of its loops.
+fn interpret_flat<T, H, O>(
+    program: &[Instruction],
+    memory: &mut T,
+    ptr: &mut isize,
+    io: &mut H,
+    config: &InterpreterConfig,
+    cancel: &AtomicBool,
+    observer: &mut O,
+) -> Result<(), BrainfuckError>
+where
+    T: Tape + ?Sized,
+    H: IoHandler,
+    O: Observer,
+{
+    let mut pc = 0;
+
+    while let Some(instruction) = program.get(pc) {
+        observer.before_instruction(&Context {
+            pc,
+            instruction,
+            ptr: *ptr,
+            memory,
+        })?;
+
+        let next = match *instruction {
+            Instruction::Op(ref op) => {
+                execute(op, memory, ptr, io, config.overflow)?;
+                match op {
+                    Op::Print => observer.on_io(IoEvent::Write(memory.cell(*ptr))),
+                    Op::Input => observer.on_io(IoEvent::Read(memory.cell(*ptr))),
+                    _ => {}
                 }
-            },
+                pc + 1
+            }
+            Instruction::LoopStart(end) if memory.cell(*ptr) == 0 => end + 1,
+            Instruction::LoopStart(_) => {
+                observer.on_loop_enter(&Context {
+                    pc,
+                    instruction,
+                    ptr: *ptr,
+                    memory,
+                });
+                pc + 1
+            }
+            Instruction::LoopEnd(start) if memory.cell(*ptr) != 0 => {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(BrainfuckError::Cancelled);
+                }
+
+                start + 1
+            }
+            _ => pc + 1,
+        };
+
+        observer.after_instruction(&Context {
+            pc,
+            instruction,
+            ptr: *ptr,
+            memory,
+        })?;
+        pc = next;
+    }
+
+    Ok(())
+}
+
+/// Execute a single operation which is not an [`Op::Loop`].
+///
+/// Loops need to know how the caller keeps track of its position in the
+/// program, so they must be handled by the caller.
+pub(crate) fn execute<T, H>(
+    op: &Op,
+    memory: &mut T,
+    ptr: &mut isize,
+    io: &mut H,
+    overflow: OverflowBehavior,
+) -> Result<(), BrainfuckError>
+where
+    T: Tape + ?Sized,
+    H: IoHandler,
+{
+    match op {
+        Op::Add(x) => add(memory, *ptr, *x as i32, overflow)?,
+        Op::Move(distance) => *ptr = memory.offset(*ptr, *distance),
+        Op::Print => io.write_byte(memory.cell(*ptr))?,
+        Op::Input => *memory.cell_mut(*ptr) = io.read_byte()?.unwrap_or(0),
+        Op::Loop(_) => unreachable!("loops are handled by the caller"),
+        #[cfg(feature = "debug_token")]
+        Op::Debug => {
+            let dump = format!("\n{:?}\n", memory_dump(memory));
+            for byte in dump.bytes() {
+                io.write_byte(byte)?;
+            }
         }
+        #[cfg(feature = "precompiled_patterns")]
+        Op::Pattern(pattern) => apply_pattern(pattern, memory, *ptr),
+        #[cfg(feature = "breakpoint_token")]
+        Op::Breakpoint => {}
     }
 
     Ok(())
 }
+
+/// Add a value to a memory location, handling overflow with the given
+/// behavior.
+#[inline(always)]
+fn add<T>(
+    memory: &mut T,
+    ptr: isize,
+    value: i32,
+    overflow: OverflowBehavior,
+) -> Result<(), BrainfuckError>
+where
+    T: Tape + ?Sized,
+{
+    let cell = memory.cell_mut(ptr);
+    *cell = match overflow {
+        OverflowBehavior::Wrap => cell.wrapping_add(value as u8),
+        OverflowBehavior::Saturate => (*cell as i32 + value).clamp(0, u8::MAX as i32) as u8,
+        OverflowBehavior::Error => {
+            u8::try_from(*cell as i32 + value).map_err(|_| BrainfuckError::CellOverflow(ptr))?
+        }
+    };
+
+    Ok(())
+}
+
+/// Get the content of the memory up until the first run of zeroed cells.
+#[cfg(feature = "debug_token")]
+fn memory_dump<T>(memory: &T) -> Vec<u8>
+where
+    T: Tape + ?Sized,
+{
+    // Stop once the cells wrap around to the first one again
+    std::iter::successors(Some(0), |&i| Some(memory.offset(i, 1)).filter(|&i| i != 0))
+        .map(|i| memory.cell(i))
+        .scan(0, |state, cell| {
+            if cell == 0 {
+                *state += 1;
+            } else {
+                *state = 0;
+            }
+
+            if *state > 3 {
+                None
+            } else {
+                Some(cell)
+            }
+        })
+        .collect()
+}
+
+/// Apply a pattern.
+///
+/// Patterns are only generated by passes assuming arithmetic wraps around,
+/// so they always wrap.
+#[cfg(feature = "precompiled_patterns")]
+fn apply_pattern<T>(pattern: &PreCompiledPattern, memory: &mut T, ptr: isize)
+where
+    T: Tape + ?Sized,
+{
+    match *pattern {
+        PreCompiledPattern::SetToZero => *memory.cell_mut(ptr) = 0,
+        PreCompiledPattern::Multiply {
+            dest_offset,
+            factor,
+        } => {
+            let dest = memory.offset(ptr, dest_offset);
+
+            // First get the result of the multiplication, then add it
+            // to the value already in the destination cell
+            let mul_res = memory.cell(ptr).wrapping_mul(factor);
+            let cell = memory.cell_mut(dest);
+            *cell = cell.wrapping_add(mul_res);
+
+            *memory.cell_mut(ptr) = 0;
+        }
+        PreCompiledPattern::AddAt { offset, value } => {
+            let cell = memory.cell_mut(memory.offset(ptr, offset));
+            *cell = cell.wrapping_add(value);
+        }
+        PreCompiledPattern::SetAt { offset, value } => {
+            *memory.cell_mut(memory.offset(ptr, offset)) = value;
+        }
+    }
+}
+
+/// Get the memory location at an offset from the pointer.
+pub(crate) fn offset_ptr(ptr: usize, offset: isize, len: usize) -> usize {
+    if offset > 0 {
+        move_next(ptr, offset as usize, len)
+    } else {
+        move_prev(ptr, offset.unsigned_abs(), len)
+    }
+}
+
+/// Move the pointer forwards, wrapping around the end of the memory.
+fn move_next(ptr: usize, count: usize, len: usize) -> usize {
+    (ptr + count % len) % len
+}
+
+/// Move the pointer backwards, wrapping around the start of the memory.
+fn move_prev(ptr: usize, count: usize, len: usize) -> usize {
+    (ptr + len - count % len) % len
+}
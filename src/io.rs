@@ -0,0 +1,181 @@
+//! Byte level I/O of Brainfuck programs.
+//!
+//! The interpreter reads and writes single bytes through an [`IoHandler`],
+//! so programs can be connected to anything able to produce and consume
+//! bytes. [`StdIo`] connects them to a [`std::io::Read`] and a
+//! [`std::io::Write`].
+
+/// Handler of the input and output of a Brainfuck program.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::interpreter::interpret_with_handler;
+/// use brainfuck_interpreter::io::IoHandler;
+/// use brainfuck_lexer::lex;
+///
+/// /// Gives the same byte forever, and collects the output.
+/// struct Constant(u8, Vec<u8>);
+///
+/// impl IoHandler for Constant {
+///     fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+///         Ok(Some(self.0))
+///     }
+///
+///     fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+///         self.1.push(byte);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut io = Constant(b'a', Vec::new());
+/// interpret_with_handler(&lex(",.,+.").unwrap(), &mut io).unwrap();
+///
+/// assert_eq!(io.1, b"ab");
+/// ```
+pub trait IoHandler {
+    /// Read a byte of input.
+    ///
+    /// Returns `None` when there is no more input, which the program reads
+    /// as zero.
+    ///
+    /// # Errors
+    ///
+    /// Any error is passed on to the caller of the interpreter.
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>>;
+
+    /// Write a byte of output.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte` - The value of the printed memory location.
+    ///
+    /// # Errors
+    ///
+    /// Any error is passed on to the caller of the interpreter.
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()>;
+}
+
+impl<H: IoHandler + ?Sized> IoHandler for &mut H {
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        (**self).read_byte()
+    }
+
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        (**self).write_byte(byte)
+    }
+}
+
+/// Characters of code page 437, the character set of the original IBM PC,
+/// for the values from 128 to 255.
+pub const CP437: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+    ░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀\
+    αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// How the values printed by a program are written to the output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Write every value as the UTF-8 encoding of the Unicode scalar value
+    /// with the same number, so values above 127 are written as two bytes.
+    #[default]
+    Unicode,
+    /// Write every value as a single byte, unchanged.
+    Raw,
+    /// Write values below 128 as ASCII, and the rest as the UTF-8 encoding
+    /// of the character at their position in a codepage, starting from 128.
+    ///
+    /// Values past the end of the codepage are written like
+    /// [`OutputMode::Unicode`].
+    Codepage(Vec<char>),
+    /// Write every value as a decimal number on a line of its own.
+    Numeric,
+}
+
+/// [`IoHandler`] reading from a [`std::io::Read`] and writing to a
+/// [`std::io::Write`].
+///
+/// Values are written as described by its [`OutputMode`], which is
+/// [`OutputMode::Unicode`] by default.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::interpreter::interpret_with_handler;
+/// use brainfuck_interpreter::io::{OutputMode, StdIo, CP437};
+/// use brainfuck_lexer::lex;
+///
+/// let mut io = StdIo::new(&b""[..], Vec::new()).with_output_mode(OutputMode::Numeric);
+/// interpret_with_handler(&lex("+++.>-.").unwrap(), &mut io).unwrap();
+/// assert_eq!(io.into_inner().1, b"3\n255\n");
+///
+/// let mode = OutputMode::Codepage(CP437.chars().collect());
+/// let mut io = StdIo::new(&b""[..], Vec::new()).with_output_mode(mode);
+/// interpret_with_handler(&lex("-.").unwrap(), &mut io).unwrap();
+/// assert_eq!(io.into_inner().1, "\u{a0}".as_bytes());
+/// ```
+#[derive(Debug)]
+pub struct StdIo<I, O> {
+    input: I,
+    output: O,
+    mode: OutputMode,
+}
+
+impl<I, O> StdIo<I, O> {
+    /// Create a handler from an input and an output stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input stream.
+    /// * `output` - The output stream.
+    pub fn new(input: I, output: O) -> Self {
+        Self {
+            input,
+            output,
+            mode: OutputMode::default(),
+        }
+    }
+
+    /// Set how printed values are written to the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The [`OutputMode`] to write with.
+    pub fn with_output_mode(mut self, mode: OutputMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Get the input and output streams back.
+    pub fn into_inner(self) -> (I, O) {
+        (self.input, self.output)
+    }
+}
+
+impl<I, O> IoHandler for StdIo<I, O>
+where
+    I: std::io::Read,
+    O: std::io::Write,
+{
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let mut byte = [0u8];
+
+        match self.input.read_exact(&mut byte) {
+            Ok(()) => Ok(Some(byte[0])),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        match self.mode {
+            OutputMode::Unicode => write!(self.output, "{}", byte as char),
+            OutputMode::Raw => self.output.write_all(&[byte]),
+            OutputMode::Codepage(ref codepage) if byte >= 128 => {
+                let ch = codepage.get(byte as usize - 128).copied();
+                write!(self.output, "{}", ch.unwrap_or(byte as char))
+            }
+            OutputMode::Codepage(_) => self.output.write_all(&[byte]),
+            OutputMode::Numeric => writeln!(self.output, "{}", byte),
+        }
+    }
+}
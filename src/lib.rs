@@ -2,5 +2,20 @@
 
 #![warn(missing_docs)]
 
+pub mod batch;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod coverage;
 pub mod error;
+pub mod flat;
 pub mod interpreter;
+pub mod io;
+pub mod listing;
+#[cfg(feature = "llvm")]
+pub mod llvm;
+pub mod machine;
+pub mod observer;
+pub mod stats;
+pub mod tape;
@@ -0,0 +1,258 @@
+//! Listings of optimized Brainfuck programs.
+//!
+//! A listing shows every line of the source of a program, followed by the
+//! instructions of the optimized and flattened program generated from it,
+//! like a disassembly interleaved with the source it was compiled from.
+//!
+//! The optimizer never adds or reorders loops, so every loop of the
+//! optimized program is matched to the loop of the source it came from.
+//! Every other instruction is generated from the line it was on if each line
+//! between the loops optimizes to the same instructions on its own, and from
+//! all the commands between the loops otherwise, such as when constants are
+//! folded across lines.
+
+use crate::error::BrainfuckError;
+use crate::flat::Instruction;
+use brainfuck_lexer::ir::{lower, Op};
+use brainfuck_lexer::optimizer::Optimizer;
+use brainfuck_lexer::{lex_with_spans, Block, Span, Token};
+
+/// A command of the source, or a loop with the commands in its body.
+enum Node<'a> {
+    Command(&'a Token, Span),
+    Loop {
+        open: Span,
+        close: Span,
+        block: &'a Block,
+        body: Vec<Node<'a>>,
+    },
+}
+
+impl Node<'_> {
+    fn span(&self) -> Span {
+        match *self {
+            Node::Command(_, span) => span,
+            Node::Loop { open, close, .. } => Span {
+                start: open.start,
+                end: close.end,
+            },
+        }
+    }
+
+    fn token(&self) -> Token {
+        match self {
+            Node::Command(token, _) => (*token).clone(),
+            Node::Loop { block, .. } => Token::Closure((*block).clone()),
+        }
+    }
+}
+
+/// Flattened program with the span of the source of every instruction.
+struct Lister<'a> {
+    optimizer: &'a Optimizer,
+    line_starts: Vec<usize>,
+    program: Vec<(Instruction, Option<Span>)>,
+}
+
+/// Print the listing of a program.
+///
+/// # Arguments
+///
+/// * `src` - The Brainfuck source to list.
+/// * `optimizer` - The [`Optimizer`] to optimize the program with.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::listing::listing;
+/// use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+///
+/// let listing = listing("+++\n[>+<-]", &Optimizer::new(OptLevel::None)).unwrap();
+///
+/// assert_eq!(
+///     listing,
+///     "   1 | +++\n\
+///      \x20    |     0  add 3\n\
+///      \x20  2 | [>+<-]\n\
+///      \x20    |     1  jz 7\n\
+///      \x20    |     2  move 1\n\
+///      \x20    |     3  add 1\n\
+///      \x20    |     4  move -1\n\
+///      \x20    |     5  add -1\n\
+///      \x20    |     6  jnz 2\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// If the source cannot be lexed, a [`BrainfuckError::ParserError`] is
+/// returned.
+pub fn listing(src: &str, optimizer: &Optimizer) -> Result<String, BrainfuckError> {
+    let (block, spans) = lex_with_spans(src)?;
+    let nodes = tree(&block, &mut spans.iter());
+    let mut lister = Lister {
+        optimizer,
+        line_starts: src
+            .split_inclusive('\n')
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len();
+                Some(line_start)
+            })
+            .collect(),
+        program: Vec::new(),
+    };
+    lister.align(&nodes, &lower(&optimizer.optimize(&block)));
+
+    let mut lines = src.split_inclusive('\n').enumerate().peekable();
+    let mut line_start = 0;
+    let mut res = String::new();
+
+    for (pc, (instruction, span)) in lister.program.iter().enumerate() {
+        if let Some(span) = span {
+            // Print every line up to the end of the source of the instruction
+            while let Some((i, line)) = lines.next_if(|_| line_start < span.end) {
+                res.push_str(&format!("{:>4} | {}\n", i + 1, line.trim_end()));
+                line_start += line.len();
+            }
+        }
+
+        res.push_str(&format!("     | {:>5}  {}\n", pc, instruction));
+    }
+
+    for (i, line) in lines {
+        res.push_str(&format!("{:>4} | {}\n", i + 1, line.trim_end()));
+    }
+
+    Ok(res)
+}
+
+/// Build the tree of the commands of a block, taking their spans from the
+/// spans of the whole program.
+fn tree<'a, I>(block: &'a Block, spans: &mut I) -> Vec<Node<'a>>
+where
+    I: Iterator<Item = &'a Span>,
+{
+    block
+        .iter()
+        .map(|token| {
+            // The lexer gives every token a span, and closures one for each
+            // of their brackets
+            let span = *spans.next().unwrap();
+
+            match token {
+                Token::Closure(block) => Node::Loop {
+                    open: span,
+                    body: tree(block, spans),
+                    close: *spans.next().unwrap(),
+                    block,
+                },
+                _ => Node::Command(token, span),
+            }
+        })
+        .collect()
+}
+
+impl Lister<'_> {
+    /// Flatten the optimized operations of a block, giving every instruction
+    /// the span of the commands of the source it was generated from.
+    fn align(&mut self, nodes: &[Node], ops: &[Op]) {
+        let mut cursor = 0;
+        let mut pending = Vec::new();
+
+        for op in ops {
+            let Op::Loop(body) = op else {
+                pending.push(op);
+                continue;
+            };
+
+            let mut loops =
+                (cursor..nodes.len()).filter(|&i| matches!(nodes[i], Node::Loop { .. }));
+            let found = loops
+                .clone()
+                .find(|&i| self.is_optimized_to(&nodes[i], body))
+                .or_else(|| loops.next());
+
+            let (open, close, inner) = match found.map(|i| &nodes[i]) {
+                Some(Node::Loop {
+                    open, close, body, ..
+                }) => (Some(*open), Some(*close), &body[..]),
+                _ => (None, None, &[][..]),
+            };
+
+            self.attribute(&nodes[cursor..found.unwrap_or(cursor)], &pending);
+            pending.clear();
+            cursor = found.map_or(cursor, |i| i + 1);
+
+            let start = self.program.len();
+            self.program.push((Instruction::LoopStart(0), open));
+            self.align(inner, body);
+
+            let end = self.program.len();
+            self.program.push((Instruction::LoopEnd(start), close));
+            self.program[start].0 = Instruction::LoopStart(end);
+        }
+
+        self.attribute(&nodes[cursor..], &pending);
+    }
+
+    /// Add the operations generated from some commands, giving them the span
+    /// of the line they were generated from if every line is optimized to
+    /// the same operations on its own, and the span of all the commands
+    /// otherwise.
+    fn attribute(&mut self, nodes: &[Node], ops: &[&Op]) {
+        let line = |node: &Node| {
+            let start = node.span().start;
+            self.line_starts.partition_point(|&line| line <= start)
+        };
+        let lines: Vec<_> = nodes
+            .chunk_by(|a, b| line(a) == line(b))
+            .map(|nodes| (span_of(nodes), self.optimize_alone(nodes)))
+            .collect();
+
+        let split = lines.iter().flat_map(|(_, ops)| ops.iter().flatten());
+        if lines.iter().all(|(_, ops)| ops.is_some()) && split.eq(ops.iter().copied()) {
+            for (span, ops) in lines {
+                let ops = ops.into_iter().flatten();
+                self.program
+                    .extend(ops.map(|op| (Instruction::Op(op), span)));
+            }
+        } else {
+            let span = span_of(nodes);
+            self.program
+                .extend(ops.iter().map(|&op| (Instruction::Op(op.clone()), span)));
+        }
+    }
+
+    /// Whether a loop of the source is optimized to a loop with the given
+    /// body.
+    fn is_optimized_to(&self, node: &Node, body: &[Op]) -> bool {
+        matches!(
+            self.optimize_alone(std::slice::from_ref(node)).as_deref(),
+            Some([Op::Loop(ops)]) if ops == body
+        )
+    }
+
+    /// Optimize some commands on their own, if they can be optimized without
+    /// knowing what runs before them.
+    fn optimize_alone(&self, nodes: &[Node]) -> Option<Vec<Op>> {
+        // Reading the cell first keeps the commands from being optimized for
+        // running on a cell known to be zero
+        let block: Block = std::iter::once(Token::Input)
+            .chain(nodes.iter().map(Node::token))
+            .collect();
+
+        match &lower(&self.optimizer.optimize(&block))[..] {
+            [Op::Input, ops @ ..] => Some(ops.to_vec()),
+            _ => None,
+        }
+    }
+}
+
+/// The span from the first to the last of some nodes.
+fn span_of(nodes: &[Node]) -> Option<Span> {
+    let start = nodes.first()?.span().start;
+    let end = nodes.last()?.span().end;
+
+    Some(Span { start, end })
+}
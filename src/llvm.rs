@@ -0,0 +1,328 @@
+//! Compilation of Brainfuck programs to native code through LLVM.
+//!
+//! Programs are lowered to textual LLVM IR, which is turned into an object
+//! file by `llc` and linked to an executable by the system C compiler. The
+//! tools can be overridden with the `LLC` and `CC` environment variables.
+//! The IR uses opaque pointers, so `llc` must be from LLVM 15 or newer.
+//!
+//! The compiled program behaves like the interpreter: the memory wraps
+//! around at both ends, reading past the end of the input gives zero and
+//! printed bytes are encoded as UTF-8. [`Op::Debug`] is not supported and
+//! compiles to nothing.
+
+use crate::error::BrainfuckError;
+use crate::interpreter::HEAP_SIZE;
+use brainfuck_lexer::ir::{lower, Op};
+#[cfg(feature = "precompiled_patterns")]
+use brainfuck_lexer::lexer::PreCompiledPattern;
+use brainfuck_lexer::Block;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Runtime functions shared by every compiled program.
+const RUNTIME: &str = r#"declare i32 @putchar(i32)
+declare i32 @getchar()
+
+define internal void @bf_print(i8 %c) {
+entry:
+  %wide = zext i8 %c to i32
+  %ascii = icmp sge i8 %c, 0
+  br i1 %ascii, label %one, label %two
+one:
+  call i32 @putchar(i32 %wide)
+  ret void
+two:
+  %high = lshr i32 %wide, 6
+  %lead = or i32 %high, 192
+  call i32 @putchar(i32 %lead)
+  %low = and i32 %wide, 63
+  %cont = or i32 %low, 128
+  call i32 @putchar(i32 %cont)
+  ret void
+}
+
+define internal i8 @bf_input() {
+entry:
+  %c = call i32 @getchar()
+  %eof = icmp slt i32 %c, 0
+  %byte = trunc i32 %c to i8
+  %res = select i1 %eof, i8 0, i8 %byte
+  ret i8 %res
+}
+"#;
+
+/// The kind of output to produce from a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit {
+    /// Textual LLVM IR.
+    Ir,
+    /// An object file exporting a C `main` function.
+    Object,
+    /// A native executable.
+    Executable,
+}
+
+/// Lower a Brainfuck program to an LLVM IR module.
+///
+/// The module defines a C `main` function running the program.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to compile.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::llvm::compile;
+///
+/// let ir = compile(&lex("+.").unwrap());
+/// assert!(ir.contains("define i32 @main()"));
+/// ```
+pub fn compile(src: &Block) -> String {
+    let mut codegen = Codegen::default();
+    codegen.block(&lower(src));
+
+    format!(
+        "@memory = internal global [{len} x i8] zeroinitializer\n\n\
+         {RUNTIME}\n\
+         define i32 @main() {{\n\
+         entry:\n  \
+         %ptr = alloca i64\n  \
+         store i64 0, ptr %ptr\n\
+         {body}  \
+         ret i32 0\n\
+         }}\n",
+        len = HEAP_SIZE,
+        body = codegen.ir,
+    )
+}
+
+/// Compile a Brainfuck program and write the result to a file.
+///
+/// # Arguments
+///
+/// * `src` - The [`Block`] to compile.
+/// * `kind` - The kind of output to produce.
+/// * `output` - The path to write the output to.
+///
+/// # Errors
+///
+/// If a file cannot be written or a tool cannot be run or fails, a
+/// [`BrainfuckError::IOError`] will be returned.
+pub fn emit(src: &Block, kind: Emit, output: &Path) -> Result<(), BrainfuckError> {
+    let ir = compile(src);
+
+    if kind == Emit::Ir {
+        return Ok(std::fs::write(output, ir)?);
+    }
+
+    // Compilations running at the same time need their own temporary files
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let tmp = std::env::temp_dir().join(format!("bf-{}-{}", std::process::id(), id));
+    let ir_path = tmp.with_extension("ll");
+    let obj_path = match kind {
+        Emit::Object => output.to_path_buf(),
+        _ => tmp.with_extension("o"),
+    };
+
+    std::fs::write(&ir_path, ir)?;
+    let res = run_tool("LLC", "llc", |llc| {
+        llc.arg("-filetype=obj")
+            .arg("-O2")
+            .arg("-relocation-model=pic")
+            .arg("-o")
+            .arg(&obj_path)
+            .arg(&ir_path)
+    });
+    std::fs::remove_file(&ir_path)?;
+    res?;
+
+    if kind == Emit::Executable {
+        let res = run_tool("CC", "cc", |cc| cc.arg(&obj_path).arg("-o").arg(output));
+        std::fs::remove_file(&obj_path)?;
+        res?;
+    }
+
+    Ok(())
+}
+
+/// Run a tool which can be overridden by an environment variable.
+fn run_tool<F>(var: &str, default: &str, args: F) -> std::io::Result<()>
+where
+    F: FnOnce(&mut Command) -> &mut Command,
+{
+    let tool = std::env::var_os(var).map_or_else(|| PathBuf::from(default), PathBuf::from);
+    let res = args(&mut Command::new(&tool)).output()?;
+
+    if res.status.success() {
+        Ok(())
+    } else {
+        let msg = format!(
+            "{} failed: {}",
+            tool.display(),
+            String::from_utf8_lossy(&res.stderr).trim()
+        );
+        Err(std::io::Error::other(msg))
+    }
+}
+
+/// Builder of the body of the `main` function.
+#[derive(Debug, Default)]
+struct Codegen {
+    ir: String,
+    next: usize,
+}
+
+impl Codegen {
+    fn block(&mut self, ops: &[Op]) {
+        for op in ops {
+            self.op(op);
+        }
+    }
+
+    fn op(&mut self, op: &Op) {
+        match *op {
+            Op::Add(x) => self.add(0, x as u8),
+            Op::Move(distance) => self.shift(distance),
+            Op::Print => {
+                let (_, value) = self.load(0);
+                self.line(format!("call void @bf_print(i8 {})", value));
+            }
+            Op::Input => {
+                let value = self.tmp();
+                self.line(format!("{} = call i8 @bf_input()", value));
+                let cell = self.cell(0);
+                self.store(&cell, &value);
+            }
+            Op::Loop(ref body) => {
+                let label = self.tmp_label();
+                self.line(format!("br label %cond{}", label));
+                self.label(format!("cond{}", label));
+                let (_, value) = self.load(0);
+                let cond = self.tmp();
+                self.line(format!("{} = icmp ne i8 {}, 0", cond, value));
+                self.line(format!(
+                    "br i1 {}, label %body{label}, label %end{label}",
+                    cond
+                ));
+                self.label(format!("body{}", label));
+                self.block(body);
+                self.line(format!("br label %cond{}", label));
+                self.label(format!("end{}", label));
+            }
+            #[cfg(feature = "debug_token")]
+            Op::Debug => {}
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(ref pattern) => self.pattern(pattern),
+            #[cfg(feature = "breakpoint_token")]
+            Op::Breakpoint => {}
+        }
+    }
+
+    #[cfg(feature = "precompiled_patterns")]
+    fn pattern(&mut self, pattern: &PreCompiledPattern) {
+        match *pattern {
+            PreCompiledPattern::SetToZero => {
+                let cell = self.cell(0);
+                self.store(&cell, "0");
+            }
+            PreCompiledPattern::Multiply {
+                dest_offset,
+                factor,
+            } => {
+                let (cell, value) = self.load(0);
+                let product = self.tmp();
+                self.line(format!("{} = mul i8 {}, {}", product, value, factor));
+                let (dest, dest_value) = self.load(dest_offset);
+                let sum = self.tmp();
+                self.line(format!("{} = add i8 {}, {}", sum, dest_value, product));
+                self.store(&dest, &sum);
+                self.store(&cell, "0");
+            }
+            PreCompiledPattern::AddAt { offset, value } => self.add(offset, value),
+            PreCompiledPattern::SetAt { offset, value } => {
+                let cell = self.cell(offset);
+                self.store(&cell, &value.to_string());
+            }
+        }
+    }
+
+    /// Add a value to the cell at an offset, wrapping around.
+    fn add(&mut self, offset: isize, value: u8) {
+        let (cell, old) = self.load(offset);
+        let new = self.tmp();
+        self.line(format!("{} = add i8 {}, {}", new, old, value));
+        self.store(&cell, &new);
+    }
+
+    /// Move the pointer, wrapping around the ends of the memory.
+    fn shift(&mut self, distance: isize) {
+        let index = self.index(distance);
+        self.line(format!("store i64 {}, ptr %ptr", index));
+    }
+
+    /// Get the index of the cell at an offset from the pointer.
+    fn index(&mut self, offset: isize) -> String {
+        let ptr = self.tmp();
+        self.line(format!("{} = load i64, ptr %ptr", ptr));
+
+        let distance = offset.rem_euclid(HEAP_SIZE as isize);
+        if distance == 0 {
+            return ptr;
+        }
+
+        let sum = self.tmp();
+        self.line(format!("{} = add i64 {}, {}", sum, ptr, distance));
+        let index = self.tmp();
+        self.line(format!("{} = urem i64 {}, {}", index, sum, HEAP_SIZE));
+        index
+    }
+
+    /// Get the address of the cell at an offset from the pointer.
+    fn cell(&mut self, offset: isize) -> String {
+        let index = self.index(offset);
+        let cell = self.tmp();
+        self.line(format!(
+            "{} = getelementptr inbounds [{len} x i8], ptr @memory, i64 0, i64 {}",
+            cell,
+            index,
+            len = HEAP_SIZE
+        ));
+        cell
+    }
+
+    /// Load the cell at an offset from the pointer, returning its address
+    /// and value.
+    fn load(&mut self, offset: isize) -> (String, String) {
+        let cell = self.cell(offset);
+        let value = self.tmp();
+        self.line(format!("{} = load i8, ptr {}", value, cell));
+        (cell, value)
+    }
+
+    fn store(&mut self, cell: &str, value: &str) {
+        self.line(format!("store i8 {}, ptr {}", value, cell));
+    }
+
+    fn tmp(&mut self) -> String {
+        self.next += 1;
+        format!("%t{}", self.next)
+    }
+
+    fn tmp_label(&mut self) -> usize {
+        self.next += 1;
+        self.next
+    }
+
+    fn line(&mut self, line: String) {
+        let _ = writeln!(self.ir, "  {}", line);
+    }
+
+    fn label(&mut self, label: String) {
+        let _ = writeln!(self.ir, "{}:", label);
+    }
+}
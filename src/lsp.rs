@@ -0,0 +1,300 @@
+//! Language Server Protocol server.
+//!
+//! Editors talk to the server over the standard input and output. Open
+//! documents are checked for errors as they change, and the server matches
+//! brackets, folds loops and formats documents with the formatter. Editors
+//! send only the parts of a document which changed, and only the tokens
+//! around them are lexed again.
+
+use crate::protocol::{read_messages, write_message};
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_lexer::formatter::{format, FormatConfig};
+use brainfuck_lexer::incremental::IncrementalLexer;
+use brainfuck_lexer::{bracket_pairs, diagnose, Span};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::mpsc;
+
+/// Error code of requests for methods the server does not support.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// Error code of requests which were valid, but failed.
+const REQUEST_FAILED: i64 = -32803;
+
+/// Serve the language server on the standard input and output.
+pub fn serve() -> Result<(), BrainfuckError> {
+    let (sender, messages) = mpsc::channel();
+    std::thread::spawn(move || read_messages(std::io::stdin().lock(), sender));
+
+    let mut server = Server::new(std::io::stdout().lock());
+    for message in messages {
+        if !server.handle(&message)? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// A document opened in the editor.
+struct Document {
+    lexer: IncrementalLexer,
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    fn new(text: String) -> Self {
+        let line_starts = line_starts(&text);
+
+        Self {
+            lexer: IncrementalLexer::new(text),
+            line_starts,
+        }
+    }
+
+    fn text(&self) -> &str {
+        self.lexer.src()
+    }
+
+    /// Apply a change sent by the editor.
+    fn change(&mut self, change: &Value) {
+        let whole = Span {
+            start: 0,
+            end: self.text().len(),
+        };
+        let range = &change["range"];
+        // Changes without a range replace the whole document
+        let span = match (self.offset(&range["start"]), self.offset(&range["end"])) {
+            (Some(start), Some(end)) if start <= end => Span { start, end },
+            _ => whole,
+        };
+
+        // Errors are found again with the diagnostics of the document
+        let _ = self
+            .lexer
+            .edit(span, change["text"].as_str().unwrap_or_default());
+        self.line_starts = line_starts(self.text());
+    }
+
+    /// Get the position of an offset, counting characters in UTF-16 code
+    /// units as the protocol does.
+    fn position(&self, offset: usize) -> Value {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character: usize = self.text()[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+
+        json!({ "line": line, "character": character })
+    }
+
+    /// Get the offset of a position, clamped to the end of its line.
+    fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let mut character = position["character"].as_u64()? as usize;
+        let start = *self.line_starts.get(line)?;
+
+        for (i, ch) in self.text()[start..].char_indices() {
+            if character == 0 || ch == '\n' {
+                return Some(start + i);
+            }
+            character = character.saturating_sub(ch.len_utf16());
+        }
+
+        Some(self.text().len())
+    }
+
+    fn range(&self, span: Span) -> Value {
+        json!({ "start": self.position(span.start), "end": self.position(span.end) })
+    }
+}
+
+/// Find the offset of the start of every line.
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+struct Server<W: Write> {
+    out: W,
+    documents: HashMap<String, Document>,
+}
+
+impl<W: Write> Server<W> {
+    fn new(out: W) -> Self {
+        Self {
+            out,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Handle a message, returning whether the server goes on.
+    fn handle(&mut self, message: &Value) -> std::io::Result<bool> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let res = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 2 },
+                    "documentHighlightProvider": true,
+                    "foldingRangeProvider": true,
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": { "name": "bf" },
+            })),
+            "shutdown" => Ok(Value::Null),
+            "exit" => return Ok(false),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.open(uri, text.to_string())?;
+                return Ok(true);
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                if let Some(document) = self.documents.get_mut(uri) {
+                    for change in changes.into_iter().flatten() {
+                        document.change(change);
+                    }
+                    self.check(uri)?;
+                }
+                return Ok(true);
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                self.notify(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )?;
+                return Ok(true);
+            }
+            "textDocument/documentHighlight" => Ok(self.highlight(uri, &params["position"])),
+            "textDocument/foldingRange" => Ok(self.folding_ranges(uri)),
+            "textDocument/formatting" => self.format(uri, &params["options"]),
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method '{}'", method))),
+        };
+
+        // Notifications have no id, and are never responded to
+        if !message["id"].is_null() {
+            self.respond(&message["id"], res)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Store the text of a document and report its errors.
+    fn open(&mut self, uri: &str, text: String) -> std::io::Result<()> {
+        self.documents.insert(uri.to_string(), Document::new(text));
+        self.check(uri)
+    }
+
+    /// Report the errors of a document.
+    fn check(&mut self, uri: &str) -> std::io::Result<()> {
+        let Some(document) = self.documents.get(uri) else {
+            return Ok(());
+        };
+
+        // Documents which could be lexed have no errors to look for
+        let errors = match document.lexer.block() {
+            Some(_) => Vec::new(),
+            None => diagnose(document.text()),
+        };
+        let diagnostics: Vec<_> = errors
+            .into_iter()
+            .map(|(span, e)| {
+                json!({
+                    "range": document.range(span),
+                    "severity": 1,
+                    "source": "bf",
+                    "message": e.to_string(),
+                })
+            })
+            .collect();
+
+        self.notify(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )
+    }
+
+    /// Highlight the bracket at a position and its match.
+    fn highlight(&self, uri: &str, position: &Value) -> Value {
+        let Some(document) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+        let Some(offset) = document.offset(position) else {
+            return Value::Null;
+        };
+
+        // The cursor is on a bracket when it is just before or after it
+        let on = |span: Span| span.start <= offset && offset <= span.end;
+        let pair = bracket_pairs(document.text())
+            .into_iter()
+            .find(|&(open, close)| on(open) || on(close));
+
+        match pair {
+            Some((open, close)) => json!([
+                { "range": document.range(open), "kind": 1 },
+                { "range": document.range(close), "kind": 1 },
+            ]),
+            None => Value::Null,
+        }
+    }
+
+    /// Fold every loop written over several lines.
+    fn folding_ranges(&self, uri: &str) -> Value {
+        let Some(document) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+
+        let ranges: Vec<_> = bracket_pairs(document.text())
+            .into_iter()
+            .map(|(open, close)| {
+                let start = &document.position(open.start)["line"];
+                let end = &document.position(close.start)["line"];
+                json!({ "startLine": start, "endLine": end })
+            })
+            .filter(|range| range["startLine"] != range["endLine"])
+            .collect();
+
+        json!(ranges)
+    }
+
+    /// Replace the whole document with its formatted source.
+    fn format(&self, uri: &str, options: &Value) -> Result<Value, (i64, String)> {
+        let document = self
+            .documents
+            .get(uri)
+            .ok_or((REQUEST_FAILED, format!("unknown document {}", uri)))?;
+
+        let config = FormatConfig {
+            indent: options["tabSize"].as_u64().unwrap_or(4) as usize,
+            ..Default::default()
+        };
+        let formatted =
+            format(document.text(), &config).map_err(|e| (REQUEST_FAILED, e.to_string()))?;
+        let whole = Span {
+            start: 0,
+            end: document.text().len(),
+        };
+
+        Ok(json!([{ "range": document.range(whole), "newText": formatted }]))
+    }
+
+    fn respond(&mut self, id: &Value, res: Result<Value, (i64, String)>) -> std::io::Result<()> {
+        let mut response = json!({ "jsonrpc": "2.0", "id": id });
+        match res {
+            Ok(result) => response["result"] = result,
+            Err((code, message)) => response["error"] = json!({ "code": code, "message": message }),
+        }
+
+        write_message(&mut self.out, &response)
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> std::io::Result<()> {
+        let notification = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.out, &notification)
+    }
+}
@@ -0,0 +1,453 @@
+//! Incremental execution of Brainfuck programs.
+//!
+//! Unlike [`crate::interpreter::interpret`], a [`Machine`] can be advanced one
+//! instruction at a time, and can optionally record the effects of every step
+//! so they can be undone again.
+
+use crate::error::BrainfuckError;
+use crate::flat::flatten;
+pub use crate::flat::Instruction;
+#[cfg(feature = "precompiled_patterns")]
+use crate::interpreter::offset_ptr;
+use crate::interpreter::{execute, OverflowBehavior, HEAP_SIZE};
+use crate::io::StdIo;
+#[cfg(any(feature = "precompiled_patterns", feature = "breakpoint_token"))]
+use brainfuck_lexer::ir::Op;
+#[cfg(feature = "precompiled_patterns")]
+use brainfuck_lexer::lexer::PreCompiledPattern;
+use brainfuck_lexer::Block;
+use std::collections::VecDeque;
+
+/// The state before a single step, used to undo it.
+#[derive(Debug, Clone, Copy)]
+struct JournalEntry {
+    pc: usize,
+    ptr: usize,
+    /// The previous values of the cells modified by the step.
+    cells: [Option<(usize, u8)>; 2],
+}
+
+/// A Brainfuck program which can be executed one instruction at a time.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::machine::Machine;
+/// use std::io::Cursor;
+///
+/// let src = "+++".to_string();
+/// let mut machine = Machine::with_history(&lex(src).unwrap(), 16);
+/// let mut input = Cursor::new(vec![]);
+/// let mut output = Vec::new();
+///
+/// machine.step(&mut input, &mut output).unwrap();
+/// assert_eq!(machine.memory()[0], 3);
+///
+/// machine.step_back();
+/// assert_eq!(machine.memory()[0], 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Machine {
+    program: Vec<Instruction>,
+    memory: Vec<u8>,
+    ptr: usize,
+    pc: usize,
+    history: VecDeque<JournalEntry>,
+    history_size: usize,
+}
+
+impl Machine {
+    /// Create a machine ready to execute the first instruction of a program.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The [`Block`] to execute.
+    pub fn new(src: &Block) -> Self {
+        Self::with_history(src, 0)
+    }
+
+    /// Create a machine which records its steps so they can be undone.
+    ///
+    /// Only the last `history_size` steps are kept, so the memory used by the
+    /// recording stays bounded.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The [`Block`] to execute.
+    /// * `history_size` - The maximum number of steps to remember.
+    pub fn with_history(src: &Block, history_size: usize) -> Self {
+        Self {
+            program: flatten(src),
+            memory: vec![0; HEAP_SIZE],
+            ptr: 0,
+            pc: 0,
+            history: VecDeque::with_capacity(history_size),
+            history_size,
+        }
+    }
+
+    /// The memory of the machine.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// The index of the memory location the pointer points to.
+    pub fn ptr(&self) -> usize {
+        self.ptr
+    }
+
+    /// The index of the next instruction to execute.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// The flattened program executed by the machine.
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    /// The next instruction to execute, if the program has not finished.
+    pub fn current_instruction(&self) -> Option<&Instruction> {
+        self.program.get(self.pc)
+    }
+
+    /// Whether the program has finished.
+    pub fn is_finished(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// The number of steps which can currently be undone.
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Execute the next instruction.
+    ///
+    /// Returns `false` without doing anything if the program has finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input stream.
+    /// * `out` - The output stream.
+    ///
+    /// # Errors
+    ///
+    /// If the instruction fails to either read from the input or write to
+    /// the output, this function will return a [`BrainfuckError::IOError`]
+    /// with the corresponding [`std::io::Error`].
+    pub fn step<I, O>(&mut self, input: &mut I, out: &mut O) -> Result<bool, BrainfuckError>
+    where
+        I: std::io::Read,
+        O: std::io::Write,
+    {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return Ok(false);
+        };
+
+        if self.history_size > 0 {
+            let entry = self.journal_entry(instruction);
+            if self.history.len() == self.history_size {
+                self.history.pop_front();
+            }
+            self.history.push_back(entry);
+        }
+
+        self.pc = match *instruction {
+            Instruction::Op(ref op) => {
+                let mut io = StdIo::new(input, out);
+                let mut ptr = self.ptr as isize;
+                execute(
+                    op,
+                    &mut self.memory[..],
+                    &mut ptr,
+                    &mut io,
+                    OverflowBehavior::Wrap,
+                )?;
+                self.ptr = ptr as usize;
+                self.pc + 1
+            }
+            Instruction::LoopStart(end) if self.memory[self.ptr] == 0 => end + 1,
+            Instruction::LoopEnd(start) if self.memory[self.ptr] != 0 => start + 1,
+            _ => self.pc + 1,
+        };
+
+        Ok(true)
+    }
+
+    /// Undo the last recorded step.
+    ///
+    /// Returns `false` if there are no recorded steps left. Output which has
+    /// already been written is not taken back, and input which is read again
+    /// will be read anew from the input stream.
+    pub fn step_back(&mut self) -> bool {
+        let Some(entry) = self.history.pop_back() else {
+            return false;
+        };
+
+        for (cell, value) in entry.cells.into_iter().rev().flatten() {
+            self.memory[cell] = value;
+        }
+
+        self.pc = entry.pc;
+        self.ptr = entry.ptr;
+
+        true
+    }
+
+    /// Whether the next instruction is a breakpoint.
+    ///
+    /// Always `false` without the `breakpoint_token` feature.
+    pub fn at_breakpoint(&self) -> bool {
+        #[cfg(feature = "breakpoint_token")]
+        return matches!(
+            self.current_instruction(),
+            Some(Instruction::Op(Op::Breakpoint))
+        );
+
+        #[cfg(not(feature = "breakpoint_token"))]
+        false
+    }
+
+    /// Execute instructions until the program finishes or a condition is met.
+    ///
+    /// The condition is checked after every step. Returns `true` if the
+    /// machine stopped because of the condition, and `false` if the program
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input stream.
+    /// * `out` - The output stream.
+    /// * `stop` - The condition to stop at.
+    ///
+    /// # Errors
+    ///
+    /// See [`Machine::step`].
+    pub fn run_until<I, O, F>(
+        &mut self,
+        input: &mut I,
+        out: &mut O,
+        mut stop: F,
+    ) -> Result<bool, BrainfuckError>
+    where
+        I: std::io::Read,
+        O: std::io::Write,
+        F: FnMut(&Self) -> bool,
+    {
+        while self.step(input, out)? {
+            if stop(self) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Execute instructions until the program finishes.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input stream.
+    /// * `out` - The output stream.
+    ///
+    /// # Errors
+    ///
+    /// See [`Machine::step`].
+    pub fn run<I, O>(&mut self, input: &mut I, out: &mut O) -> Result<(), BrainfuckError>
+    where
+        I: std::io::Read,
+        O: std::io::Write,
+    {
+        while self.step(input, out)? {}
+
+        Ok(())
+    }
+
+    fn journal_entry(&self, instruction: &Instruction) -> JournalEntry {
+        // Every instruction can only modify the current cell, except for the
+        // patterns working on a cell at an offset from it
+        let dest = match *instruction {
+            #[cfg(feature = "precompiled_patterns")]
+            Instruction::Op(Op::Pattern(
+                PreCompiledPattern::Multiply {
+                    dest_offset: offset,
+                    ..
+                }
+                | PreCompiledPattern::AddAt { offset, .. }
+                | PreCompiledPattern::SetAt { offset, .. },
+            )) => {
+                let dest = offset_ptr(self.ptr, offset, self.memory.len());
+                Some((dest, self.memory[dest]))
+            }
+            _ => None,
+        };
+
+        JournalEntry {
+            pc: self.pc,
+            ptr: self.ptr,
+            cells: [Some((self.ptr, self.memory[self.ptr])), dest],
+        }
+    }
+}
+
+/// A value of a [`Machine`] which a [`Condition`] compares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Watch {
+    /// The value of the memory location at an index.
+    Cell(usize),
+    /// The value of the memory location the pointer points to.
+    CurrentCell,
+    /// The index of the memory location the pointer points to.
+    Pointer,
+}
+
+/// The comparison made by a [`Condition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+/// A condition on the state of a [`Machine`], such as `cell 5 == 42`.
+///
+/// Conditions are parsed from a [`Watch`] (`cell <index>`, `cell` or `ptr`),
+/// a comparison and a value, separated by whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_lexer::lex;
+/// use brainfuck_interpreter::machine::{Condition, Machine};
+/// use std::io::Cursor;
+///
+/// let condition: Condition = "cell 1 == 3".parse().unwrap();
+/// let mut machine = Machine::new(&lex(">+++").unwrap());
+/// let mut input = Cursor::new(vec![]);
+/// let mut output = Vec::new();
+///
+/// assert!(!condition.holds(&machine));
+/// machine.run(&mut input, &mut output).unwrap();
+/// assert!(condition.holds(&machine));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Condition {
+    /// The value to compare.
+    pub watch: Watch,
+    /// How to compare the value.
+    pub comparison: Comparison,
+    /// The value to compare with.
+    pub value: usize,
+}
+
+impl Condition {
+    /// Whether the condition holds for the current state of a machine.
+    ///
+    /// # Arguments
+    ///
+    /// * `machine` - The [`Machine`] to check.
+    pub fn holds(&self, machine: &Machine) -> bool {
+        let actual = match self.watch {
+            Watch::Cell(index) => match machine.memory().get(index) {
+                Some(&cell) => cell as usize,
+                None => return false,
+            },
+            Watch::CurrentCell => machine.memory()[machine.ptr()] as usize,
+            Watch::Pointer => machine.ptr(),
+        };
+
+        match self.comparison {
+            Comparison::Equal => actual == self.value,
+            Comparison::NotEqual => actual != self.value,
+            Comparison::Less => actual < self.value,
+            Comparison::LessOrEqual => actual <= self.value,
+            Comparison::Greater => actual > self.value,
+            Comparison::GreaterOrEqual => actual >= self.value,
+        }
+    }
+}
+
+/// The error returned when parsing an invalid [`Condition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseConditionError(String);
+
+impl std::fmt::Display for ParseConditionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid condition '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseConditionError {}
+
+impl std::str::FromStr for Condition {
+    type Err = ParseConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseConditionError(s.to_string());
+        let mut words = s.split_whitespace().peekable();
+
+        let watch = match words.next() {
+            Some("ptr") => Watch::Pointer,
+            Some("cell") => match words.next_if(|word| word.parse::<usize>().is_ok()) {
+                Some(index) => Watch::Cell(index.parse().unwrap()),
+                None => Watch::CurrentCell,
+            },
+            _ => return Err(err()),
+        };
+
+        let comparison = match words.next() {
+            Some("==") => Comparison::Equal,
+            Some("!=") => Comparison::NotEqual,
+            Some("<") => Comparison::Less,
+            Some("<=") => Comparison::LessOrEqual,
+            Some(">") => Comparison::Greater,
+            Some(">=") => Comparison::GreaterOrEqual,
+            _ => return Err(err()),
+        };
+
+        let value = words
+            .next()
+            .and_then(|word| word.parse().ok())
+            .ok_or_else(err)?;
+
+        match words.next() {
+            Some(_) => Err(err()),
+            None => Ok(Self {
+                watch,
+                comparison,
+                value,
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.watch {
+            Watch::Cell(index) => write!(f, "cell {}", index)?,
+            Watch::CurrentCell => write!(f, "cell")?,
+            Watch::Pointer => write!(f, "ptr")?,
+        }
+
+        let comparison = match self.comparison {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        };
+
+        write!(f, " {} {}", comparison, self.value)
+    }
+}
@@ -1,25 +1,325 @@
 mod cli;
-mod error;
-mod interpreter;
+#[cfg(feature = "dap")]
+mod dap;
+mod debugger;
+mod harness;
+#[cfg(feature = "lsp")]
+mod lsp;
+mod pipe;
+#[cfg(any(feature = "dap", feature = "lsp"))]
+mod protocol;
+#[cfg(feature = "session")]
+mod session;
+mod visualizer;
 
-use brainfuck_lexer::lex;
+#[cfg(feature = "cache")]
+use brainfuck_interpreter::cache::lex_cached;
+use brainfuck_interpreter::coverage::interpret_with_coverage;
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{
+    interpret_with_config, interpret_with_heatmap, InterpreterConfig, Limits,
+};
+use brainfuck_interpreter::io::{OutputMode, StdIo};
+use brainfuck_interpreter::listing::listing;
+#[cfg(feature = "llvm")]
+use brainfuck_interpreter::llvm::{emit, Emit};
+use brainfuck_interpreter::stats::Heatmap;
+use brainfuck_lexer::explain::annotate;
+use brainfuck_lexer::formatter::{format, FormatConfig};
+use brainfuck_lexer::generate::text_to_bf;
+use brainfuck_lexer::lex_unoptimized;
+use brainfuck_lexer::optimizer::{Optimizer, WRAPPING_PASSES};
+use brainfuck_lexer::preprocessor::{preprocess, PreprocessorConfig};
+#[cfg(feature = "dialects")]
+use brainfuck_lexer::symbols::Symbols;
 use clap::Parser;
-use error::BrainfuckError;
-use interpreter::brainfuck;
+#[cfg(feature = "session")]
+use std::io::{Cursor, Read};
+use std::path::Path;
+#[cfg(feature = "llvm")]
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
 
 fn get_source_as_str(src: String) -> std::io::Result<String> {
     let path = std::path::Path::new(&src);
 
     if path.is_file() {
-        std::fs::read_to_string(path.to_path_buf())
+        std::fs::read_to_string(path)
     } else {
         Ok(src)
     }
 }
 
+fn fmt(src: String, config: &FormatConfig, write: bool) -> Result<(), BrainfuckError> {
+    let formatted = format(&get_source_as_str(src.clone())?, config)?;
+
+    if !write {
+        print!("{}", formatted);
+        return Ok(());
+    }
+
+    if !std::path::Path::new(&src).is_file() {
+        let msg = format!("{} is not a file", src);
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg).into());
+    }
+
+    Ok(std::fs::write(src, formatted)?)
+}
+
+/// Name the compiled program after the source file, or `out` if the source
+/// was given directly.
+#[cfg(feature = "llvm")]
+fn default_output(src: &str, kind: Emit) -> PathBuf {
+    let path = std::path::Path::new(src);
+    let stem = match path.file_stem() {
+        Some(stem) if path.is_file() => stem,
+        _ => "out".as_ref(),
+    };
+
+    let output = PathBuf::from(stem);
+    match kind {
+        Emit::Ir => output.with_extension("ll"),
+        Emit::Object => output.with_extension("o"),
+        Emit::Executable => output,
+    }
+}
+
+/// Choose how to export a heatmap from the extension of the file.
+fn heatmap_export(path: &Path) -> std::io::Result<fn(&Heatmap) -> String> {
+    let export = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => Heatmap::to_csv,
+        Some("json") => Heatmap::to_json,
+        _ => {
+            let msg = format!("{} is not a .csv or .json file", path.display());
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg));
+        }
+    };
+
+    Ok(export)
+}
+
+/// Read the symbols of a dialect from a TOML file, where every key is the
+/// name of a command and its value is the symbol for it.
+#[cfg(feature = "dialects")]
+fn read_symbols(path: &Path) -> std::io::Result<Symbols> {
+    let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+    let table: toml::Table = std::fs::read_to_string(path)?
+        .parse()
+        .map_err(|e: toml::de::Error| invalid(format!("{}: {}", path.display(), e.message())))?;
+
+    let mut symbols = Symbols::default();
+    for (key, value) in table {
+        let symbol = match key.as_str() {
+            "increment" => &mut symbols.increment,
+            "decrement" => &mut symbols.decrement,
+            "next" => &mut symbols.next,
+            "prev" => &mut symbols.prev,
+            "print" => &mut symbols.print,
+            "input" => &mut symbols.input,
+            "loop_begin" => &mut symbols.loop_begin,
+            "loop_end" => &mut symbols.loop_end,
+            _ => {
+                return Err(invalid(format!(
+                    "{}: unknown command {}",
+                    path.display(),
+                    key
+                )))
+            }
+        };
+
+        match value {
+            toml::Value::String(value) if !value.is_empty() => *symbol = value,
+            _ => {
+                let msg = format!("{}: {} must be a non-empty string", path.display(), key);
+                return Err(invalid(msg));
+            }
+        }
+    }
+
+    Ok(symbols)
+}
+
 fn main() -> Result<(), BrainfuckError> {
     let args = cli::Args::parse();
-    let src = get_source_as_str(args.src)?;
-    let code = lex(src)?;
-    brainfuck(&code)
+
+    match args.command {
+        Some(cli::Command::Fmt {
+            src,
+            width,
+            indent,
+            strip_comments,
+            write,
+        }) => {
+            let config = FormatConfig {
+                indent,
+                width,
+                comments: !strip_comments,
+            };
+            return fmt(src, &config, write);
+        }
+        Some(cli::Command::Test {
+            dir,
+            opt_level,
+            timeout,
+        }) => {
+            let timeout = Duration::from_secs_f64(timeout);
+            if !harness::test(&dir, &Optimizer::new(opt_level.into()), timeout)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(cli::Command::Generate { text }) => {
+            let program = text_to_bf(&text).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string())
+            })?;
+            println!("{}", program);
+            return Ok(());
+        }
+        Some(cli::Command::Explain { src }) => {
+            print!("{}", annotate(&get_source_as_str(src)?)?);
+            return Ok(());
+        }
+        Some(cli::Command::Pipe {
+            programs,
+            opt_level,
+            encoding,
+        }) => {
+            let optimizer = Optimizer::new(opt_level.into());
+            let programs = programs
+                .into_iter()
+                .map(|src| Ok(optimizer.optimize(&lex_unoptimized(get_source_as_str(src)?)?)))
+                .collect::<Result<Vec<_>, BrainfuckError>>()?;
+            return pipe::pipe(&programs, encoding.into());
+        }
+        #[cfg(feature = "dap")]
+        Some(cli::Command::Dap) => return dap::serve(),
+        #[cfg(feature = "lsp")]
+        Some(cli::Command::Lsp) => return lsp::serve(),
+        None => {}
+    }
+
+    let path = args.src.unwrap_or_default();
+    let mut src = get_source_as_str(path.clone())?;
+
+    if args.preprocess {
+        let path = std::path::Path::new(&path);
+        let config = PreprocessorConfig {
+            base_dir: path.parent().filter(|_| path.is_file()).map(Into::into),
+            ..Default::default()
+        };
+        src = preprocess(&src, &config)?;
+    }
+    #[cfg(feature = "dialects")]
+    if let Some(path) = args.symbols {
+        src = read_symbols(&path)?.translate(&src);
+    }
+
+    if let Some(format) = args.coverage {
+        let coverage =
+            interpret_with_coverage(&src, &mut std::io::stdin(), &mut std::io::stdout())?;
+        // Programs given directly on the command line have no file name
+        let name = if Path::new(&path).is_file() {
+            path.as_str()
+        } else {
+            "<program>"
+        };
+
+        let (default, report) = match format {
+            cli::CoverageFormat::Lcov => ("lcov.info", coverage.to_lcov(name)),
+            cli::CoverageFormat::Html => ("coverage.html", coverage.to_html(name)),
+        };
+        std::fs::write(args.coverage_file.unwrap_or_else(|| default.into()), report)?;
+        return Ok(());
+    }
+
+    // Most passes are only correct when arithmetic wraps
+    let wrapping_passes = (args.overflow != cli::Overflow::Wrap).then_some(WRAPPING_PASSES);
+    let optimizer = args
+        .disable_pass
+        .iter()
+        .map(String::as_str)
+        .chain(wrapping_passes.into_iter().flatten().copied())
+        .fold(Optimizer::new(args.opt_level.into()), |optimizer, pass| {
+            optimizer.disable(pass)
+        });
+    if args.emit == Some(cli::EmitKind::Listing) {
+        let listing = listing(&src, &optimizer)?;
+        return match args.output {
+            Some(path) => Ok(std::fs::write(path, listing)?),
+            None => {
+                print!("{}", listing);
+                Ok(())
+            }
+        };
+    }
+
+    #[cfg(feature = "cache")]
+    let code = match args.cache_dir {
+        Some(ref dir) => lex_cached(&src, &optimizer, dir)?,
+        None => optimizer.optimize(&lex_unoptimized(src)?),
+    };
+    #[cfg(not(feature = "cache"))]
+    let code = optimizer.optimize(&lex_unoptimized(src)?);
+
+    #[cfg(feature = "llvm")]
+    if let Some(kind) = args.emit.and_then(cli::EmitKind::llvm) {
+        let output = args.output.unwrap_or_else(|| default_output(&path, kind));
+        return emit(&code, kind, &output);
+    }
+
+    if args.debug {
+        debugger::debug(&code, args.history)
+    } else if args.visualize {
+        visualizer::visualize(&code, args.refresh_rate, args.window)
+    } else if args.stats || args.heatmap.is_some() {
+        let export = args.heatmap.as_deref().map(heatmap_export).transpose()?;
+        let (stats, heatmap) =
+            interpret_with_heatmap(&code, &mut std::io::stdin(), &mut std::io::stdout())?;
+
+        if args.stats {
+            eprintln!("\n{}", stats);
+        }
+        if let (Some(path), Some(export)) = (args.heatmap, export) {
+            std::fs::write(path, export(&heatmap))?;
+        }
+        Ok(())
+    } else {
+        let config = InterpreterConfig {
+            tape: args.tape.into(),
+            limits: Limits {
+                max_steps: args.max_steps,
+                max_output: args.max_output,
+                max_input: args.max_input,
+                max_cells: args.max_cells,
+            },
+            overflow: args.overflow.into(),
+        };
+        let mode = if args.print_numeric {
+            OutputMode::Numeric
+        } else {
+            args.encoding.into()
+        };
+        #[cfg(feature = "session")]
+        let input: Box<dyn Read> = match args.replay {
+            Some(path) => Box::new(Cursor::new(session::load(&path)?)),
+            None => Box::new(std::io::stdin()),
+        };
+        #[cfg(not(feature = "session"))]
+        let input = std::io::stdin();
+        let mut io = StdIo::new(input, std::io::stdout()).with_output_mode(mode);
+        let cancel = AtomicBool::new(false);
+
+        #[cfg(feature = "session")]
+        if let Some(path) = args.record {
+            let mut recorder = session::Recorder::new(&mut io);
+            let res = interpret_with_config(&code, &mut recorder, &config, &cancel);
+            // The session is saved even if the program failed, so the
+            // failure can be reproduced
+            session::save(&path, recorder.input())?;
+            return res;
+        }
+
+        interpret_with_config(&code, &mut io, &config, &cancel)
+    }
 }
@@ -0,0 +1,155 @@
+//! Instrumentation of running Brainfuck programs.
+//!
+//! An [`Observer`] is called by the interpreter around every instruction it
+//! executes, so profilers, tracers and limits can be built outside of the
+//! interpreter. Observers which do nothing are free, as the calls are
+//! resolved when the interpreter is compiled.
+
+use crate::error::BrainfuckError;
+use crate::flat::Instruction;
+use crate::tape::Tape;
+
+/// The state of the interpreter when an [`Observer`] is called.
+#[derive(Debug)]
+pub struct Context<'a, T: ?Sized> {
+    /// The index of the instruction in the flattened program.
+    pub pc: usize,
+    /// The instruction being executed.
+    pub instruction: &'a Instruction,
+    /// The index of the memory location the pointer points to.
+    pub ptr: isize,
+    /// The memory of the program.
+    pub memory: &'a T,
+}
+
+/// A byte passing through the input or output of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoEvent {
+    /// A byte was read into the current cell, which is zero at the end of
+    /// the input.
+    Read(u8),
+    /// A byte was printed from the current cell.
+    Write(u8),
+}
+
+/// Callbacks for the events of a running program.
+///
+/// Every callback does nothing by default, so only the events of interest
+/// need to be implemented. Brackets of loops are instructions too, and are
+/// observed every time their condition is checked.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::error::BrainfuckError;
+/// use brainfuck_interpreter::interpreter::interpret_with_observer;
+/// use brainfuck_interpreter::io::StdIo;
+/// use brainfuck_interpreter::observer::{Context, Observer};
+/// use brainfuck_interpreter::tape::Tape;
+/// use brainfuck_lexer::lex;
+/// use std::io::Cursor;
+///
+/// /// Counts the executed instructions.
+/// struct Profiler(u64);
+///
+/// impl Observer for Profiler {
+///     fn before_instruction<T>(&mut self, _ctx: &Context<T>) -> Result<(), BrainfuckError>
+///     where
+///         T: Tape + ?Sized,
+///     {
+///         self.0 += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+/// let mut profiler = Profiler(0);
+/// interpret_with_observer(&lex("++[-]").unwrap(), &mut io, &mut profiler).unwrap();
+///
+/// assert_eq!(profiler.0, 2);
+/// ```
+pub trait Observer {
+    /// Called before an instruction is executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The [`Context`] of the instruction.
+    ///
+    /// # Errors
+    ///
+    /// Returning an error stops the program, and passes the error on to the
+    /// caller of the interpreter.
+    fn before_instruction<T>(&mut self, _ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        Ok(())
+    }
+
+    /// Called after an instruction was executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The [`Context`] of the instruction, with the pointer and
+    ///   memory as the instruction left them.
+    ///
+    /// # Errors
+    ///
+    /// Returning an error stops the program, and passes the error on to the
+    /// caller of the interpreter.
+    fn after_instruction<T>(&mut self, _ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        Ok(())
+    }
+
+    /// Called when the body of a loop is entered from its start, before its
+    /// first instruction is executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The [`Context`] of the start of the loop.
+    fn on_loop_enter<T>(&mut self, _ctx: &Context<T>)
+    where
+        T: Tape + ?Sized,
+    {
+    }
+
+    /// Called when the program has read or printed a byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The [`IoEvent`] of the byte.
+    fn on_io(&mut self, _event: IoEvent) {}
+}
+
+/// Observes nothing.
+impl Observer for () {}
+
+impl<O: Observer> Observer for &mut O {
+    fn before_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        (**self).before_instruction(ctx)
+    }
+
+    fn after_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        (**self).after_instruction(ctx)
+    }
+
+    fn on_loop_enter<T>(&mut self, ctx: &Context<T>)
+    where
+        T: Tape + ?Sized,
+    {
+        (**self).on_loop_enter(ctx)
+    }
+
+    fn on_io(&mut self, event: IoEvent) {
+        (**self).on_io(event)
+    }
+}
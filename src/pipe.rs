@@ -0,0 +1,65 @@
+//! Pipelines of Brainfuck programs.
+//!
+//! Every program of a pipeline runs on its own thread, with its output
+//! connected to the input of the next program by a pipe, like a pipeline of
+//! a shell. The first program reads the standard input, and the last program
+//! prints to the standard output. Programs are passed the values printed by
+//! the program before them unchanged, and only the output of the last program
+//! is encoded.
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::interpret_with_handler;
+use brainfuck_interpreter::io::{OutputMode, StdIo};
+use brainfuck_lexer::Block;
+use std::io::{ErrorKind, LineWriter, Read, Write};
+
+type Input = Box<dyn Read + Send>;
+type Output = Box<dyn Write + Send>;
+
+/// Run the programs of a pipeline until all of them have finished.
+///
+/// Output is passed on at the end of every line, and when a program
+/// finishes. A program stopping before reading all of its input does not
+/// make the program before it fail, and the error of the first program of
+/// the pipeline which failed otherwise is returned.
+///
+/// # Arguments
+///
+/// * `programs` - The programs of the pipeline, in order.
+/// * `mode` - The [`OutputMode`] of the last program.
+pub fn pipe(programs: &[Block], mode: OutputMode) -> Result<(), BrainfuckError> {
+    let Some((last, init)) = programs.split_last() else {
+        return Ok(());
+    };
+
+    let mut stages: Vec<(&Block, StdIo<Input, Output>)> = Vec::new();
+    let mut input: Input = Box::new(std::io::stdin());
+
+    for program in init {
+        let (reader, writer) = std::io::pipe()?;
+        let input = std::mem::replace(&mut input, Box::new(reader));
+        let out: Output = Box::new(LineWriter::new(writer));
+        let io = StdIo::new(input, out).with_output_mode(OutputMode::Raw);
+        stages.push((program, io));
+    }
+    let out: Output = Box::new(std::io::stdout());
+    stages.push((last, StdIo::new(input, out).with_output_mode(mode)));
+
+    std::thread::scope(|scope| {
+        // The ends of the pipes are dropped when their program finishes, which
+        // ends the input of the next program
+        let handles: Vec<_> = stages
+            .into_iter()
+            .map(|(program, mut io)| scope.spawn(move || interpret_with_handler(program, &mut io)))
+            .collect();
+
+        for handle in handles {
+            match handle.join().expect("program panicked") {
+                Err(BrainfuckError::IOError(e)) if e.kind() == ErrorKind::BrokenPipe => {}
+                res => res?,
+            }
+        }
+
+        Ok(())
+    })
+}
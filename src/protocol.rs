@@ -0,0 +1,57 @@
+//! Message framing shared by the language server and the debug adapter.
+//!
+//! Both protocols send JSON messages preceded by a `Content-Length` header
+//! and an empty line.
+
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::sync::mpsc::Sender;
+
+/// Read messages until the input ends or fails.
+///
+/// Messages which are not valid JSON are reported on the standard error and
+/// skipped, so a single bad message does not end the session.
+pub fn read_messages<R: BufRead>(mut input: R, sender: Sender<Value>) -> std::io::Result<()> {
+    loop {
+        let mut len = None;
+        loop {
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length:") {
+                len = value.trim().parse().ok();
+            }
+        }
+
+        let Some(len) = len else {
+            continue;
+        };
+        let mut body = vec![0; len];
+        input.read_exact(&mut body)?;
+
+        let message = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("skipping malformed message: {}", e);
+                continue;
+            }
+        };
+
+        if sender.send(message).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Write a single message.
+pub fn write_message<W: Write>(out: &mut W, message: &Value) -> std::io::Result<()> {
+    let body = message.to_string();
+    write!(out, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    out.flush()
+}
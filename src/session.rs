@@ -0,0 +1,68 @@
+//! Recording and replaying the input of interactive sessions.
+//!
+//! A session is saved as a JSON object with the bytes the program read as
+//! an array of numbers, such as `{"input":[104,105,10]}`. Running the
+//! program again with the input of a session reproduces the session exactly.
+
+use brainfuck_interpreter::io::IoHandler;
+use std::path::Path;
+
+/// Handler keeping a copy of every byte the program reads.
+pub struct Recorder<H> {
+    io: H,
+    input: Vec<u8>,
+}
+
+impl<H> Recorder<H> {
+    /// Record the input read through a handler.
+    pub fn new(io: H) -> Self {
+        Self {
+            io,
+            input: Vec::new(),
+        }
+    }
+
+    /// The bytes read so far.
+    pub fn input(&self) -> &[u8] {
+        &self.input
+    }
+}
+
+impl<H: IoHandler> IoHandler for Recorder<H> {
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.io.read_byte()?;
+        self.input.extend(byte);
+        Ok(byte)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        self.io.write_byte(byte)
+    }
+}
+
+/// Save the input of a session to a file.
+pub fn save(path: &Path, input: &[u8]) -> std::io::Result<()> {
+    let session = serde_json::json!({ "input": input });
+    std::fs::write(path, format!("{}\n", session))
+}
+
+/// Read the input of a session saved with [`save`].
+pub fn load(path: &Path) -> std::io::Result<Vec<u8>> {
+    let invalid = || {
+        let msg = format!("{} is not a recorded session", path.display());
+        std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+    };
+
+    let session: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path)?).map_err(|_| invalid())?;
+    session["input"]
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|byte| {
+            byte.as_u64()
+                .and_then(|byte| u8::try_from(byte).ok())
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
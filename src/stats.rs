@@ -0,0 +1,255 @@
+//! Statistics about the execution of Brainfuck programs.
+
+use crate::error::BrainfuckError;
+use crate::flat::Instruction;
+use crate::observer::{Context, Observer};
+use crate::tape::Tape;
+use brainfuck_lexer::ir::Op;
+#[cfg(feature = "precompiled_patterns")]
+use brainfuck_lexer::lexer::PreCompiledPattern;
+
+/// Counters collected while interpreting a program.
+///
+/// The counts are of the tokens actually executed, so they depend on how
+/// much the program was optimized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// Number of tokens executed, counting every check of a loop condition.
+    pub instructions_executed: u64,
+    /// Number of times a loop was entered.
+    pub loops_entered: u64,
+    /// The highest memory location the pointer has pointed to.
+    pub max_pointer: usize,
+    /// Number of distinct memory locations which were read or written.
+    pub cells_touched: usize,
+    /// Number of bytes read from the input.
+    pub bytes_read: u64,
+    /// Number of bytes written to the output.
+    pub bytes_written: u64,
+}
+
+impl std::fmt::Display for ExecutionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "instructions executed: {}", self.instructions_executed)?;
+        writeln!(f, "loops entered:         {}", self.loops_entered)?;
+        writeln!(f, "max pointer:           {}", self.max_pointer)?;
+        writeln!(f, "cells touched:         {}", self.cells_touched)?;
+        writeln!(f, "bytes read:            {}", self.bytes_read)?;
+        write!(f, "bytes written:         {}", self.bytes_written)
+    }
+}
+
+/// Number of reads and writes of every memory location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Heatmap {
+    reads: Vec<u64>,
+    writes: Vec<u64>,
+}
+
+impl Heatmap {
+    fn new(memory_size: usize) -> Self {
+        Self {
+            reads: vec![0; memory_size],
+            writes: vec![0; memory_size],
+        }
+    }
+
+    /// Number of reads of every memory location.
+    pub fn reads(&self) -> &[u64] {
+        &self.reads
+    }
+
+    /// Number of writes to every memory location.
+    pub fn writes(&self) -> &[u64] {
+        &self.writes
+    }
+
+    /// The memory locations which were read or written, with their number
+    /// of reads and writes.
+    pub fn used_cells(&self) -> impl Iterator<Item = (usize, u64, u64)> + '_ {
+        self.reads
+            .iter()
+            .zip(&self.writes)
+            .enumerate()
+            .filter(|(_, (&reads, &writes))| reads > 0 || writes > 0)
+            .map(|(cell, (&reads, &writes))| (cell, reads, writes))
+    }
+
+    /// Export the used memory locations as CSV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brainfuck_lexer::lex;
+    /// use brainfuck_interpreter::interpreter::interpret_with_heatmap;
+    /// use std::io::Cursor;
+    ///
+    /// let src = "++>.".to_string();
+    /// let mut output = Vec::new();
+    /// let (_, heatmap) =
+    ///     interpret_with_heatmap(&lex(src).unwrap(), &mut Cursor::new(vec![]), &mut output)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(heatmap.to_csv(), "cell,reads,writes\n0,1,1\n1,1,0\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("cell,reads,writes\n");
+        for (cell, reads, writes) in self.used_cells() {
+            csv.push_str(&format!("{},{},{}\n", cell, reads, writes));
+        }
+        csv
+    }
+
+    /// Export the used memory locations as a JSON array of objects.
+    pub fn to_json(&self) -> String {
+        let cells: Vec<_> = self
+            .used_cells()
+            .map(|(cell, reads, writes)| {
+                format!(
+                    "{{\"cell\":{},\"reads\":{},\"writes\":{}}}",
+                    cell, reads, writes
+                )
+            })
+            .collect();
+
+        format!("[{}]\n", cells.join(","))
+    }
+}
+
+/// Collects [`ExecutionStats`] and a [`Heatmap`] by observing a program on
+/// a wrapping tape.
+pub(crate) struct Recorder {
+    stats: ExecutionStats,
+    heatmap: Heatmap,
+}
+
+impl Recorder {
+    pub(crate) fn new(memory_size: usize) -> Self {
+        Self {
+            stats: ExecutionStats::default(),
+            heatmap: Heatmap::new(memory_size),
+        }
+    }
+
+    fn read(&mut self, cell: isize) {
+        self.heatmap.reads[cell as usize] += 1;
+    }
+
+    fn write(&mut self, cell: isize) {
+        self.heatmap.writes[cell as usize] += 1;
+    }
+
+    /// Record the memory locations an operation is about to use.
+    fn record<T>(&mut self, op: &Op, ctx: &Context<T>)
+    where
+        T: Tape + ?Sized,
+    {
+        let ptr = ctx.ptr;
+
+        match op {
+            Op::Add(_) => {
+                self.read(ptr);
+                self.write(ptr);
+            }
+            Op::Print => self.read(ptr),
+            Op::Input => self.write(ptr),
+            #[cfg(feature = "precompiled_patterns")]
+            Op::Pattern(pattern) => match *pattern {
+                PreCompiledPattern::SetToZero => self.write(ptr),
+                PreCompiledPattern::Multiply { dest_offset, .. } => {
+                    let dest = ctx.memory.offset(ptr, dest_offset);
+                    self.read(ptr);
+                    self.read(dest);
+                    self.write(dest);
+                    self.write(ptr);
+                }
+                PreCompiledPattern::AddAt { offset, .. } => {
+                    let dest = ctx.memory.offset(ptr, offset);
+                    self.read(dest);
+                    self.write(dest);
+                }
+                PreCompiledPattern::SetAt { offset, .. } => {
+                    self.write(ctx.memory.offset(ptr, offset))
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Get the collected statistics, given the number of bytes read and
+    /// written.
+    pub(crate) fn finish(self, bytes_read: u64, bytes_written: u64) -> (ExecutionStats, Heatmap) {
+        let stats = ExecutionStats {
+            cells_touched: self.heatmap.used_cells().count(),
+            bytes_read,
+            bytes_written,
+            ..self.stats
+        };
+
+        (stats, self.heatmap)
+    }
+}
+
+/// Every instruction is counted, and the brackets of loops read the memory
+/// location their condition checks.
+impl Observer for Recorder {
+    fn before_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        match ctx.instruction {
+            Instruction::Op(op) => self.record(op, ctx),
+            Instruction::LoopStart(_) | Instruction::LoopEnd(_) => self.read(ctx.ptr),
+        }
+
+        self.stats.instructions_executed += 1;
+        Ok(())
+    }
+
+    fn after_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.stats.max_pointer = self.stats.max_pointer.max(ctx.ptr as usize);
+        Ok(())
+    }
+
+    fn on_loop_enter<T>(&mut self, _ctx: &Context<T>)
+    where
+        T: Tape + ?Sized,
+    {
+        self.stats.loops_entered += 1;
+    }
+}
+
+/// Stream wrapper counting the bytes passing through it.
+pub(crate) struct Counter<'a, T> {
+    inner: &'a mut T,
+    pub(crate) count: u64,
+}
+
+impl<'a, T> Counter<'a, T> {
+    pub(crate) fn new(inner: &'a mut T) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<T: std::io::Read> std::io::Read for Counter<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.count += len as u64;
+        Ok(len)
+    }
+}
+
+impl<T: std::io::Write> std::io::Write for Counter<'_, T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.count += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
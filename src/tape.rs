@@ -0,0 +1,203 @@
+//! Memory of Brainfuck programs.
+//!
+//! The interpreter works on any [`Tape`]. A slice of bytes is a tape of fixed
+//! size, where moving the pointer past either end wraps around to the other.
+//! A [`BidirectionalTape`] instead grows in both directions as the program
+//! moves the pointer, and a [`SparseTape`] only allocates memory around the
+//! locations the program writes to.
+
+use crate::interpreter::offset_ptr;
+use std::collections::HashMap;
+
+/// Number of memory locations in a page of a [`SparseTape`].
+const PAGE_SIZE: usize = 1024;
+
+/// Memory locations of a Brainfuck program, addressed by signed indices.
+///
+/// The pointer of a program starts at index zero.
+pub trait Tape {
+    /// Get the value of a memory location.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the memory location.
+    fn cell(&self, index: isize) -> u8;
+
+    /// Get a mutable reference to a memory location.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the memory location.
+    fn cell_mut(&mut self, index: isize) -> &mut u8;
+
+    /// Get the index of the memory location at an offset from another.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index to start from.
+    /// * `offset` - The distance to move, which is negative to the left.
+    fn offset(&self, index: isize, offset: isize) -> isize;
+
+    /// Get the number of memory locations which have been allocated.
+    fn allocated(&self) -> usize;
+}
+
+/// A tape of fixed size, where the memory locations past either end wrap
+/// around to the other.
+impl Tape for [u8] {
+    fn cell(&self, index: isize) -> u8 {
+        self[index as usize]
+    }
+
+    fn cell_mut(&mut self, index: isize) -> &mut u8 {
+        &mut self[index as usize]
+    }
+
+    fn offset(&self, index: isize, offset: isize) -> isize {
+        offset_ptr(index as usize, offset, self.len()) as isize
+    }
+
+    fn allocated(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A tape which is unbounded in both directions.
+///
+/// Memory is allocated for every location between the leftmost and rightmost
+/// locations which have been written to.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::tape::{BidirectionalTape, Tape};
+///
+/// let mut tape = BidirectionalTape::default();
+/// *tape.cell_mut(-3) = 42;
+///
+/// assert_eq!(tape.offset(0, -3), -3);
+/// assert_eq!(tape.cell(-3), 42);
+/// assert_eq!(tape.cell(1_000_000), 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BidirectionalTape {
+    /// Memory locations from zero and rightwards.
+    right: Vec<u8>,
+    /// Memory locations from minus one and leftwards.
+    left: Vec<u8>,
+}
+
+impl BidirectionalTape {
+    /// Split an index into the half of the tape it is in and its position in
+    /// that half.
+    fn locate(index: isize) -> (bool, usize) {
+        if index >= 0 {
+            (false, index as usize)
+        } else {
+            (true, (-1 - index) as usize)
+        }
+    }
+}
+
+impl Tape for BidirectionalTape {
+    fn cell(&self, index: isize) -> u8 {
+        let (left, i) = Self::locate(index);
+        let half = if left { &self.left } else { &self.right };
+
+        half.get(i).copied().unwrap_or(0)
+    }
+
+    fn cell_mut(&mut self, index: isize) -> &mut u8 {
+        let (left, i) = Self::locate(index);
+        let half = if left {
+            &mut self.left
+        } else {
+            &mut self.right
+        };
+
+        if i >= half.len() {
+            half.resize(i + 1, 0);
+        }
+        &mut half[i]
+    }
+
+    fn offset(&self, index: isize, offset: isize) -> isize {
+        index + offset
+    }
+
+    fn allocated(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+}
+
+/// A tape which is unbounded in both directions, allocating memory in pages.
+///
+/// Only the pages containing memory locations which have been written to are
+/// allocated, so programs can use locations far apart from each other.
+///
+/// # Examples
+///
+/// ```
+/// use brainfuck_interpreter::tape::{SparseTape, Tape};
+///
+/// let mut tape = SparseTape::default();
+/// *tape.cell_mut(-1) = 1;
+/// *tape.cell_mut(1 << 40) = 2;
+///
+/// assert_eq!(tape.cell(1 << 40), 2);
+/// assert_eq!(tape.pages(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseTape {
+    pages: HashMap<isize, Box<[u8; PAGE_SIZE]>>,
+}
+
+impl SparseTape {
+    /// The number of pages which have been allocated.
+    pub fn pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Split an index into the index of its page and its position in it.
+    fn locate(index: isize) -> (isize, usize) {
+        let size = PAGE_SIZE as isize;
+        (index.div_euclid(size), index.rem_euclid(size) as usize)
+    }
+}
+
+impl Tape for SparseTape {
+    fn cell(&self, index: isize) -> u8 {
+        let (page, i) = Self::locate(index);
+
+        self.pages.get(&page).map_or(0, |page| page[i])
+    }
+
+    fn cell_mut(&mut self, index: isize) -> &mut u8 {
+        let (page, i) = Self::locate(index);
+
+        &mut self
+            .pages
+            .entry(page)
+            .or_insert_with(|| Box::new([0; PAGE_SIZE]))[i]
+    }
+
+    fn offset(&self, index: isize, offset: isize) -> isize {
+        index.wrapping_add(offset)
+    }
+
+    fn allocated(&self) -> usize {
+        self.pages.len() * PAGE_SIZE
+    }
+}
+
+/// The kinds of [`Tape`] the interpreter can run programs on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TapeKind {
+    /// A fixed number of memory locations, wrapping around at both ends.
+    #[default]
+    Wrapping,
+    /// A [`BidirectionalTape`].
+    Bidirectional,
+    /// A [`SparseTape`].
+    Sparse,
+}
@@ -0,0 +1,88 @@
+//! Terminal visualization of the memory of a running program.
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::machine::Machine;
+use brainfuck_lexer::Block;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const HIGHLIGHT: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// Run a program while rendering the cells around the pointer.
+///
+/// The output of the program is shown below the memory, as the screen is
+/// redrawn `refresh_rate` times per second.
+pub fn visualize(src: &Block, refresh_rate: u32, window: usize) -> Result<(), BrainfuckError> {
+    let mut machine = Machine::new(src);
+    let mut stdout = std::io::stdout();
+    let mut output = Vec::new();
+
+    let interval = Duration::from_secs(1) / refresh_rate.max(1);
+    let mut last_frame = Instant::now();
+    write!(stdout, "{}", render(&machine, window, &output))?;
+
+    while machine.step(&mut std::io::stdin(), &mut output)? {
+        if last_frame.elapsed() >= interval {
+            write!(stdout, "{}", render(&machine, window, &output))?;
+            stdout.flush()?;
+            last_frame = Instant::now();
+        }
+    }
+
+    write!(stdout, "{}", render(&machine, window, &output))?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Render a single frame.
+fn render(machine: &Machine, window: usize, output: &[u8]) -> String {
+    let memory = machine.memory();
+    let start = machine
+        .ptr()
+        .saturating_sub(window / 2)
+        .min(memory.len().saturating_sub(window));
+    let end = (start + window).min(memory.len());
+
+    let mut rows = [
+        String::from("cell "),
+        String::from("dec  "),
+        String::from("hex  "),
+        String::from("chr  "),
+    ];
+
+    for (i, &cell) in memory.iter().enumerate().take(end).skip(start) {
+        let ch = if cell.is_ascii_graphic() || cell == b' ' {
+            cell as char
+        } else {
+            '.'
+        };
+        let columns = [
+            format!("{:>5}", i),
+            format!("{:>5}", cell),
+            format!("{:>5x}", cell),
+            format!("{:>5}", ch),
+        ];
+
+        for (row, column) in rows.iter_mut().zip(columns) {
+            if i == machine.ptr() {
+                let _ = write!(row, "{}{}{}", HIGHLIGHT, column, RESET);
+            } else {
+                row.push_str(&column);
+            }
+        }
+    }
+
+    let output: String = output.iter().map(|&v| v as char).collect();
+    format!(
+        "{}pc {} ptr {}\n\n{}\n\n{}",
+        CLEAR_SCREEN,
+        machine.pc(),
+        machine.ptr(),
+        rows.join("\n"),
+        output
+    )
+}
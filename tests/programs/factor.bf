@@ -0,0 +1,281 @@
+>>>>[-],[<<<<[-]>[-]>>>>[-]<[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>
+[-<<+>>]<[<<->>[-]]<[-]<-[<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>
+>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<[
+-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>>[-<<<<<<<<<
+<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>
+]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>>[-<<<<<<
+<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>
+>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>>[-<<<
+<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>
+>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>>[-
+<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>
+>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>>>>
+>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<
+<+>>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>>>>
+>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<
+<<<<+>>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]>>>
+>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<
+<<<<<<<+>>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<<<]
+>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-
+<<<<<<<<<<+>>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[->>>>>>>>>>+<<<<+<<<<
+<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>
+>[-<<<<<<<<<<+>>>>>>>>>>]<[-<<<+>+<[>-]>[-<<+>>>]>]<<<<<<<<<<[-]>[-]>>>>>[-<<<<<
+<+>>>>>>]>[-<<<<<<+>>>>>>]<<<------------------------------------------------[->
+>+<<]>>[-<<<<<+>+<[>-]>[-<<+>>>]>>>]<<[-],>[-]<[->>>+<+<<]>>>[-<<<+>>>]<--------
+--<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<-][-]++++++++++<<<<<[->>>>>>>>+<<+<<<
+<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<<<<<<[->>>>>>>+<+<<<<<<]>>>>>>>[-<<<<<<<+>>>>>
+>>][-]>[-]>[-]<<<<<[->>>>>>+<+<<<<<]>>>>>>[-<<<<<<+>>>>>>]<[->>+<<]<<<[->>>>>>>>
+>>>>+<<<<<<<<+<<<<]>>>>>>>>>>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[
+-<<<<<<<<[->>>>>>>>>>>>>>+<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>
+>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<[->>>>>>>>>>>>>>+<+<<<<<<<<<<<<<]>>>>>>
+>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<[->>>>
+>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<
+<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<
+<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>
+>]<<<<<<<[-<<+>>]>>>[-<<<<<<+>>>>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<<<<<[->>
+>>>>>+<+<<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>]<<[->-<]>[->>>+<+<<]>>>[-<<<+>>>]<------
+----<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-
+]<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>+<<+<<<<<]>>>>>>>[-<<<
+<<<<+>>>>>>>]<<[->>>+<<<]<<<[->>>>>>>>>>>>>+<<<<<<<<+<<<<<]>>>>>>>>>>>>>[-<<<<<<
+<<<<<<<+>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<+<
+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<
+]<<<]<<<<<<[->>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>
+>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<
+<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]
+<<<]<<<]>->+<[>-]>[-<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>
+>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<
+<+>>>]>>>[-<<<<<<<+>>>>>>>]>>>[-<<<<<<<<<<<+>>>>>>>>>>>]<<<<<<<<<<<<<<[->>>>>>>>
++<+<<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<<[->>-<<]>>[->>>+<+<<]>>>[-<<<+>>>]<---
+-------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]
+<[-]<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>+<<<+<<<<<]>>>>>>
+>>[-<<<<<<<<+>>>>>>>>]<<<[->>>>+<<<<]<<<[->>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>
+>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<<[->>>>>>>
+>>>>>>>>>+<<<<<<<+<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]
+<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<[->>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<]>>>>>>>>
+>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<
+[->>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>
+>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>
+>>>>+<<<<<<<<+<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<
+<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<<<+>>>>]>>>[-<<<<<<<<+>>>>>>>>]>>>[-<<<
+<<<<<<<<<+>>>>>>>>>>>>]<<<<<<<<<<<<<<<[->>>>>>>>>+<+<<<<<<<<]>>>>>>>>>[-<<<<<<<<
+<+>>>>>>>>>]<<<<[->>>-<<<]>>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>
+>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<[-]>[-]>[-<
+<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>>+<<<<+<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>
+>>>>]<<<<[->>>>>+<<<<<]<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<
+<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>
+>>+<<<<<<<+<<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<
+<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<<[->>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<]>>>>>>>>
+>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<
+<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<
+<<<+>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<<[->>>
+>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<<<<+>>>>>]>>>[-<<<<<<<<<
++>>>>>>>>>]>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>>>]<<<<<<<<<<<<<<<<[->>>>>>>>>>+<+<<<<<
+<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<[->>>>-<<<<]>>>>[->>>+<+<<]>>>[-<<<+
+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[
+-]>>[-]]<[-]<<<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>>>+<<<<
+<+<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<[->>>>>>+<<<<<<]<<<[->>>>>>>>>>>>
+>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<
+<<<[->->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>
+>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<
+<<<<<[->>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<
+<<<<+>>>>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>
+>>>+<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>
+>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>
++<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>
+>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<<<<<+>>>>>>]>>>[-<<<<<<<<<<+>>>>>>>
+>>>]>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<<<<<<<<<<<<<<<[->>>>>>>>>>>+<+<<<<<<<<
+<<]>>>>>>>>>>>[-<<<<<<<<<<<+>>>>>>>>>>>]<<<<<<[->>>>>-<<<<<]>>>>>[->>>+<+<<]>>>[
+-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]
+<[<<[-]>>[-]]<[-]<<<<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<<<[-]>[-]<<[-]>>>>>>>>>>[-<
+<<<<<<<+<+>>>>>>>>>]<<<<<<<<[->>>>>>>>+<<<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]
+<[>>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++.--------------------
+----------------------------<<<<<<<<<[-]]>>>>>>>>[-<<<<<<<+<+>>>>>>>>]<<<<<<<[->
+>>>>>>+<<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>>>+++++++++++++++++++++++
++++++++++++++++++++++++++.------------------------------------------------<<<<<<
+<<[-]]>>>>>>>[-<<<<<<+<+>>>>>>>]<<<<<<[->>>>>>+<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-
+<<+>>]<[>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++.----------------
+--------------------------------<<<<<<<[-]]>>>>>>[-<<<<<+<+>>>>>>]<<<<<[->>>>>+<
+<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>+++++++++++++++++++++++++++++++++++
++++++++++++++.------------------------------------------------<<<<<<[-]]>>>>>+++
++++++++++++++++++++++++++++++++++++++++++++++.----------------------------------
+--------------[-]>[-]>[-]>[-]>[-]<<<<<<<<<<[-]++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++.-------------------------------------------------------
+---<<<<<[->>>>>>>+<<+<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>]<<<<<<[->>>>>>+<+<<<<<]>>>>>
+>[-<<<<<<+>>>>>>][-]++>[-]+[>[-]>[-]>[-]<<<<[->>>>>+<+<<<<]>>>>>[-<<<<<+>>>>>]<[
+->>+<<]<<<<<[->>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>
+>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<[->>>>>>>>>>>>>+<<<<<<<+<<<<<<]>>>>>>>>>
+>>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<[->>>>>>>>>
+>>>>>>>+<+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<[
+-<<<<<<<<-[->->+<[>-]>[-<<<<<<<[->>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>>>>>>>>[-
+<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<[-
+>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<
+<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<+>>]>>>[-<<<<<<+>>>>>>]>>>[-<<<<<<<<<<+>>
+>>>>>>>>]<<<<<<<<<<<<[->>>>>>+<+<<<<<]>>>>>>[-<<<<<<+>>>>>>]<<[->-<]>[->>>+<<+<]
+>>>[-<<<+>>>]<+<[>>>[-]+<<<<<<<[->>>>>>>>>+<+<<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>
+>>>>]<[<->[-]]<[<[-]<<<<<[->>>>>>>>>+<<+<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]<
+<<<<<<<<<<<[->>>>>>>>>>>>+<+<<<<<<<<<<<]>>>>>>>>>>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>]
+<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-
+]<-]<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<<<<[-]>>>>>>>>>-<[-]]>[<<<<<<<<<<+>>>>>>>>
+>>-]<<[-]<-<[-]]>[>++++++++++++++++++++++++++++++++.----------------------------
+---->[-]++++++++++<[->>>>+<<+<<]>>>>[-<<<<+>>>>]<<<<<<<<<<<<[->>>>>>>>>>>>+<+<<<
+<<<<<<<<]>>>>>>>>>>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>][-]>[-]>[-]<<<<<[->>>>>>+<+<<<<
+<]>>>>>>[-<<<<<<+>>>>>>]<[->>+<<]<<<[->>>>>>>>>>>>+<<<<<<<<+<<<<]>>>>>>>>>>>>[-<
+<<<<<<<<<<<+>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<[->>>>>>>>>>>>>>+<<<<<<<+
+<<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<
+<]<<<<<[->>>>>>>>>>>>>>+<+<<<<<<<<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>
+>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>
+>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>
+-]>[-<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<
++>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<+>>]>>>[-<<<<<<+>>>>>
+>]>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<<<<<[->>>>>>>+<+<<<<<<]>>>>>>>[-<<<<<<<+>>
+>>>>>]<<[->-<]>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<-
+>>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[
+-]>[-]>[-]<<<<<[->>>>>>>+<<+<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>]<<[->>>+<<<]<<<[->>>>
+>>>>>>>>>+<<<<<<<<+<<<<<]>>>>>>>>>>>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>>>]<<<<<<<<[->-
+>+<[>-]>[-<<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<
+<<<<<<+>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<[->>>>>>>>>>>>>>>+<+<<<
+<<<<<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<
+[>-]>[-<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<
+<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<[->>>
+>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>
+>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<<+>>>]>>>[-<<<<<<<+>>>>>>>]>>>[-<<
+<<<<<<<<<+>>>>>>>>>>>]<<<<<<<<<<<<<<[->>>>>>>>+<+<<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>
+>>>>]<<<[->>-<<]>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[
+<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>
+>]<[-]>[-]>[-]<<<<<[->>>>>>>>+<<<+<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<<[->>>>+<<
+<<]<<<[->>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>
+>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<]>>>>>>
+>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<
+<[->>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>
+>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<
+<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<
+]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<]>>>>>>>>>>
+>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<
+<<<[-<<<<+>>>>]>>>[-<<<<<<<<+>>>>>>>>]>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>]<<<<<<<<<<<
+<<<<[->>>>>>>>>+<+<<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]<<<<[->>>-<<<]>>>[->>>
++<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]
+>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->
+>>>>>>>>+<<<<+<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]<<<<[->>>>>+<<<<<]<<<[->>>>>>
+>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<
+<<<<[->->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<<]>>>>>>>>>>>>
+>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<<[
+->>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<+
+<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>
+-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<
+<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<
+]<<<]>>>>>]<<<<<<<[-<<<<<+>>>>>]>>>[-<<<<<<<<<+>>>>>>>>>]>>>[-<<<<<<<<<<<<<+>>>>
+>>>>>>>>>]<<<<<<<<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>
+>>>>]<<<<<[->>>>-<<<<]>>>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-
+<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<<[-]>[-]>[-<<+
+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>>>+<<<<<+<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>
+>>>>>>>]<<<<<[->>>>>>+<<<<<<]<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>
+>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<<<<[->>>
+>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<+<<<<<<
+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<[-<<<<<<<
+<-[->->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>
+>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<
+]>->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>
+>>>>>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]
+<<<<<<<[-<<<<<<+>>>>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>
+>>>>]<<<<<<<<<<<<<<<<<[->>>>>>>>>>>+<+<<<<<<<<<<]>>>>>>>>>>>[-<<<<<<<<<<<+>>>>>>
+>>>>>]<<<<<<[->>>>>-<<<<<]>>>>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<
+]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<<<[-]>[-]
+>[-<<+>>]>[-<<+>>]<<<[-]>[-]<<[-]>>>>>>>>>>[-<<<<<<<<+<+>>>>>>>>>]<<<<<<<<[->>>>
+>>>>+<<<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>>>>+++++++++++++++++++++++
++++++++++++++++++++++++++.------------------------------------------------<<<<<<
+<<<[-]]>>>>>>>>[-<<<<<<<+<+>>>>>>>>]<<<<<<<[->>>>>>>+<<<<<<<]<[<[-]+>[-]]<[->>+<
++<]>>[-<<+>>]<[>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++.--------
+----------------------------------------<<<<<<<<[-]]>>>>>>>[-<<<<<<+<+>>>>>>>]<<
+<<<<[->>>>>>+<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>>+++++++++++++++++++
++++++++++++++++++++++++++++++.------------------------------------------------<<
+<<<<<[-]]>>>>>>[-<<<<<+<+>>>>>>]<<<<<[->>>>>+<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+
+>>]<[>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++.--------------------
+----------------------------<<<<<<[-]]>>>>>+++++++++++++++++++++++++++++++++++++
++++++++++++.------------------------------------------------[-]>[-]>[-]>[-]>[-]<
+<<<<<<<<<[-]<<<<<<<<<<<[-]>[-]>>>[->>>>>>+<<<<<<<<<<+>>>>]>>>>>>[-<<<<<<+>>>>>>]
+<<<<<[->>>>>+<<<<<<<<<+>>>>]>>>>>[-<<<<<+>>>>>]<-]<<[-]<<<[-]>[-]<<]<<<[->>>>>>+
+<+<<<<<]>>>>>>[-<<<<<<+>>>>>>]<[<[-]+>[-]]>[-]+<[-]>[->>>+<<+<]>>>[-<<<+>>>]<<<<
+<<<<[->>>>>>>>+<+<<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[
+-<<<<+>>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-]<[->>+<+<]>>[-<<+>>]<[
+<<[-]+>>[-]]<[-]<[>++++++++++++++++++++++++++++++++.----------------------------
+----[-]++++++++++<<<<<[->>>>>>>>+<<+<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<<<<<<[-
+>>>>>>>+<+<<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>][-]>[-]>[-]<<<<<[->>>>>>+<+<<<<<]>>>>>
+>[-<<<<<<+>>>>>>]<[->>+<<]<<<[->>>>>>>>>>>>+<<<<<<<<+<<<<]>>>>>>>>>>>>[-<<<<<<<<
+<<<<+>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<[->>>>>>>>>>>>>>+<<<<<<<+<<<<<<<
+]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<
+[->>>>>>>>>>>>>>+<+<<<<<<<<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<
+[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>
+>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<
+<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>
+>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<+>>]>>>[-<<<<<<+>>>>>>]>>>[-
+<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<<<<<<[->>>>>>>+<+<<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>]<
+<[->-<]>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<
+[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>
+[-]<<<<<[->>>>>>>+<<+<<<<<]>>>>>>>[-<<<<<<<+>>>>>>>]<<[->>>+<<<]<<<[->>>>>>>>>>>
+>>+<<<<<<<<+<<<<<]>>>>>>>>>>>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]
+>[-<<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+
+>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<[->>>>>>>>>>>>>>>+<+<<<<<<<<<<
+<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-
+<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<
++>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<[->>>>>>>>>>
+>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<
+<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<<+>>>]>>>[-<<<<<<<+>>>>>>>]>>>[-<<<<<<<<<
+<<+>>>>>>>>>>>]<<<<<<<<<<<<<<[->>>>>>>>+<+<<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<
+<[->>-<<]>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-
+]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>
+[-]>[-]<<<<<[->>>>>>>>+<<<+<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>>]<<<[->>>>+<<<<]<<<[
+->>>>>>>>>>>>>>+<<<<<<<<+<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<
+<<<<<<[->->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<]>>>>>>>>>>>>>
+>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<[->>>>
+>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>
+>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<]>>
+>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<
+<]>->+<[>-]>[-<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<]>>>>>>>>>>>>>>>>>
+[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<[-<<
+<<+>>>>]>>>[-<<<<<<<<+>>>>>>>>]>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>]<<<<<<<<<<<<<<<[->
+>>>>>>>>+<+<<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]<<<<[->>>-<<<]>>>[->>>+<+<<]>
+>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+
+>>]<[<<[-]>>[-]]<[-]<<<<<<<<[-]>[-]>[-<<+>>]>[-<<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>
+>+<<<<+<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]<<<<[->>>>>+<<<<<]<<<[->>>>>>>>>>>>>
+>>+<<<<<<<<+<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<<<<<[->
+->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>+<<<<<<<+<<<<<<<<<<]>>>>>>>>>>>>>>>>>[-
+<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<<[->>>>>>
+>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>
+>>>>>]<[-<<<<<<<<-[->->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<
+<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>
++<]<<<]<<<]>->+<[>-]>[-<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<]>>>>>
+>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>
+>>>]<<<<<<<[-<<<<<+>>>>>]>>>[-<<<<<<<<<+>>>>>>>>>]>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>
+>>]<<<<<<<<<<<<<<<<[->>>>>>>>>>+<+<<<<<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<
+<<<[->>>>-<<<<]>>>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<+>>]<
+[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<<[-]>[-]>[-<<+>>]>[-<
+<+>>]<[-]>[-]>[-]<<<<<[->>>>>>>>>>+<<<<<+<<<<<]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>
+]<<<<<[->>>>>>+<<<<<<]<<<[->>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<]>>>>>>>>>>>>>>>>[-
+<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<<<<<<<<[->->+<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>
+>>>>>>>>+<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>
+>>>>>>]<<<<+>+<[>-]>[->>+<]<<<]<<<]<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<
+<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<[-<<<<<<<<-[->->
++<[>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>
+>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]<<<]>->+<[
+>-]>[-<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>+<<<<<<<<+<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>
+[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<<<+>+<[>-]>[->>+<]<<<]>>>>>]<<<<<<<
+[-<<<<<<+>>>>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<<
+<<<<<<<<<<<<<<<[->>>>>>>>>>>+<+<<<<<<<<<<]>>>>>>>>>>>[-<<<<<<<<<<<+>>>>>>>>>>>]<
+<<<<<[->>>>>-<<<<<]>>>>>[->>>+<+<<]>>>[-<<<+>>>]<----------<[-]+>[->>+<+<]>>[-<<
++>>]<[<<->>[-]]<[-]<[->>+<+<]>>[-<<+>>]<[<<[-]>>[-]]<[-]<<<<<<<<<<[-]>[-]>[-<<+>
+>]>[-<<+>>]<<<[-]>[-]<<[-]>>>>>>>>>>[-<<<<<<<<+<+>>>>>>>>>]<<<<<<<<[->>>>>>>>+<<
+<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>>>>++++++++++++++++++++++++++++++
+++++++++++++++++++.------------------------------------------------<<<<<<<<<[-]]
+>>>>>>>>[-<<<<<<<+<+>>>>>>>>]<<<<<<<[->>>>>>>+<<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-
+<<+>>]<[>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++.---------------
+---------------------------------<<<<<<<<[-]]>>>>>>>[-<<<<<<+<+>>>>>>>]<<<<<<[->
+>>>>>+<<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>>>>>>++++++++++++++++++++++++++
+++++++++++++++++++++++.------------------------------------------------<<<<<<<[-
+]]>>>>>>[-<<<<<+<+>>>>>>]<<<<<[->>>>>+<<<<<]<[<[-]+>[-]]<[->>+<+<]>>[-<<+>>]<[>>
+>>>>++++++++++++++++++++++++++++++++++++++++++++++++.---------------------------
+---------------------<<<<<<[-]]>>>>>++++++++++++++++++++++++++++++++++++++++++++
+++++.------------------------------------------------[-]>[-]>[-]>[-]>[-]<<<<<<<<
+<<[-]<[-]]<<<<[-]>[-]>[-]<<++++++++++.----------<[-],]
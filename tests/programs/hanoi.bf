@@ -0,0 +1,199 @@
+>>>>>>>>>>>>>>>>>>[-]+[<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<
+<<<<<]>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>]<[->>>+<<+
+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<->[->>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<
+<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>]
+<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<->[->>>>>>>>>>>>>>>>>>>>>>>>+
+<+<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<+>>>
+>>>>>>>>>>>>>>>>>>>>>]<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<<<->[->
+>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>
+[-<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>]<[->>>+<<+<]>>>[-<<<+>>>
+]<+<[<<<<<<<<<<<<<<<<<<<<<<<<<<->[->>>>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<
+<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>
+>>>>>>>>>>>>>>>>>>>>>]<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<<<<<<<-
+>[->>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>
+>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>]
+<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<->[->>>>>>>>>>>>>>>>>
+>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>]<[->>>+<<+
+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<->[->>>>>>>>>>>>>>>>>>>>>>>>>
+>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>]<[->>>+<
+<+<]>>>[-<<<+>>>]<+<[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<->>>>>>>>>>+>>>>>>>>>>>>>
+>>>>>>>>>>>>-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>
+>>>>>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++.++++++++++++++++++++++++++++++++++.+++++++.-----------------.---------
+------------------------------------------------------------.+++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++.+++++.++++++++++.--------.----
+-----------------------------------------------------------------------.++++++++
+++++++++++++++++.------------------------.++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++.++++++++++++.---.--.---------------------------
+--------------------------------------------------.-----------------------------
+---<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<
+<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>
+>>>>>>>>>>>>>>>>>>]<++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++.[-]++++++++++++++++++++++++++++++++.++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++.-----.---------------------------
+----------------------------------------------------.---------------------------
+-----<<<<<<<<<<<<<<<<<<<<<<<<<<<++>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<<
+<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<<<<<<<<<<<
+<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>
+>>>>>>>>>>>>>>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<
+<<+>>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+
+>>>]<[<->[-]]<[<<<<<<<<<<<<<<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>>>>>>>>>>>-]<[-]
+<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<
+<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>
+>>>>>>>>>>>>>>>]<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++.[-]++++++++++.----------<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++.++++++++++++++++++++++++++++++++++.+++++++.-------
+----------.---------------------------------------------------------------------
+.++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+++++.++++
+++++++.--------.----------------------------------------------------------------
+-----------.+++++++++++++++++++++++.-----------------------.++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++.++++++++++++.---.--.---------
+--------------------------------------------------------------------.-----------
+---------------------<<<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<
+<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<+>>
+>>>>>>>>>>>>>>>>>>>>>>>>]<++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++.[-]++++++++++++++++++++++++++++++++.++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++.-----.---------------------
+----------------------------------------------------------.---------------------
+-----------<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<
+<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<<<<<<<<<<<<<<
+]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>
+>>>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>>>>]<[<
+->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+>>>]<[<->[-
+]]<[<<<<<<<<<<<<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<<<
+<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>
+>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>]<++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++.[-]++++++++++.----------
+<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+>+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+
++++++++++++++++++++++++++++++++++.+++++++.-----------------.--------------------
+-------------------------------------------------.++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++.+++++.++++++++++.--------.---------------
+------------------------------------------------------------.+++++++++++++++++++
++++.----------------------.+++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++.++++++++++++.---.--.------------------------------------------
+-----------------------------------.--------------------------------<<<<<<<<<<<<
+<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>
+>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>]<+++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++.[-]++++++++++++++++++++++++++++++
+++.+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++.-----.------------------------------------------------------------------
+-------------.--------------------------------<<<<<<<<<<<<<<<<<<<<<<<++>>>>>>>>>
+>>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>+<<
++<<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<
+<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<
+]>>>>[-<<<<+>>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]
+>>>[-<<<+>>>]<[<->[-]]<[<<<<<<<<<<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>>>>>>>-]<[-
+]<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<<<]>>>
+>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>]<++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.[-]++++++++++.------
+----<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+>+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+
++++++++++++++++++++++++++++++++++.+++++++.-----------------.--------------------
+-------------------------------------------------.++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++.+++++.++++++++++.--------.---------------
+------------------------------------------------------------.+++++++++++++++++++
+++.---------------------.+++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++.++++++++++++.---.--.--------------------------------------------
+---------------------------------.--------------------------------<<<<<<<<<<<<<<
+<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>[-
+<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>]<+++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++.[-]++++++++++++++++++++++++++++++++.+++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.-----.
+-------------------------------------------------------------------------------.
+--------------------------------<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>[-]+
+++<[-]<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<<<<<<<<<
+<]>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>
+]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>>>>]<[<->[-]]<[<<<
+<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+>>>]<[<->[-]]<[<<<<<<<
+<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>
+>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<
+<<+>>>>>>>>>>>>>>>>>>>>>>]<+++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++.[-]++++++++++.----------<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<
+<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++.++++++++++++++++++++++++++++++++++.+++++++.---------
+--------.---------------------------------------------------------------------.+
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+++++.++++++
+++++.--------.------------------------------------------------------------------
+---------.++++++++++++++++++++.--------------------.++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++.++++++++++++.---.--.-----------------
+------------------------------------------------------------.-------------------
+-------------<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<<<]>>>
+>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>]<++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++.[-]+++++++++++++++++++++++++++++
++++.++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++.-----.-----------------------------------------------------------------
+--------------.--------------------------------<<<<<<<<<<<<<<<<<<<++>>>>>>>>>>>>
+>>>>>>>>[-]+++<[-]<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<
+<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>
+]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>>>>]<[<->[-]]<[<<<
+<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+>>>]<[<->[-]]<[<<<<<<<
+<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>
+>>>+<+<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>
+>>>>>>>>>]<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.[-]
+++++++++++.----------<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>
+>>>>>>>>>>++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++.++++++++++++++++++++++++++++++++++.+++++++.-----------------.-----------
+----------------------------------------------------------.+++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++.+++++.++++++++++.--------.------
+---------------------------------------------------------------------.++++++++++
++++++++++.-------------------.++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++.++++++++++++.---.--.---------------------------------------
+--------------------------------------.--------------------------------<<<<<<<<<
+<<<<<<<<[->>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<
+<<<<<<<+>>>>>>>>>>>>>>>>>>]<++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++.[-]++++++++++++++++++++++++++++++++.++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++.-----.-------------------
+------------------------------------------------------------.-------------------
+-------------<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<<<<<<<<<<<[->>
+>>>>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<
+<<<<<<<+>>>>>>>>>>>>>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>
+>>>[-<<<<+>>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>
+>[-<<<+>>>]<[<->[-]]<[<<<<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<
+<<<<[->>>>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<
+<<<+>>>>>>>>>>>>>>>>>>]<++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++.[-]++++++++++.----------<-]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>>>>>>>>+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++.++++++++++++++++++++++++++++++++++.+++++++.-----------------.--
+-------------------------------------------------------------------.++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+++++.++++++++++.------
+--.---------------------------------------------------------------------------.+
++++++++++++++++++.------------------.+++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++.++++++++++++.---.--.--------------------------------
+---------------------------------------------.--------------------------------<<
+<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>+<+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<
+<<<<+>>>>>>>>>>>>>>>>]<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++.[-]++++++++++++++++++++++++++++++++.+++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++.-----.------------------------
+-------------------------------------------------------.------------------------
+--------<<<<<<<<<<<<<<<++>>>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<<<<<<<<<[->>>>>>>>>>>>
+>>>>>>>+<<+<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>
+>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>>>>]<[<->
+[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+>>>]<[<->[-]]
+<[<<<<<<<<<<<<<<<<--->>>>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>+<+<
+<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>]<++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++.[-]++++++++++.----------<-
+]<<[-]<-<[-]]>[<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>+++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++.+++++++++++++++++++++++
++++++++++++.+++++++.-----------------.------------------------------------------
+---------------------------.++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++.+++++.++++++++++.--------.-------------------------------------
+--------------------------------------.+++++++++++++++++.-----------------.+++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.++++++++++++.-
+--.--.--------------------------------------------------------------------------
+---.--------------------------------<<<<<<<<<<<<<[->>>>>>>>>>>>>>+<+<<<<<<<<<<<<
+<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<+++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++.[-]++++++++++++++++++++++++++++++++.+++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.----
+-.------------------------------------------------------------------------------
+-.--------------------------------<<<<<<<<<<<<<+>>>>>>>>>>>>>>[-]+++<[-]<<<<<<<<
+<<<<<[->>>>>>>>>>>>>>>>>+<<+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<
++>>>>>>>>>>>>>>>>>]<<<[->>>+<+<<]>>>[-<<<+>>>]<[>[-]+<<[->>>>+<+<<<]>>>>[-<<<<+>
+>>>]<[<->[-]]<[<<<<[-]+>>[-]+>[-]+>-]<<->-]<[-]<[-][-]+<[->>>+<+<<]>>>[-<<<+>>>]
+<[<->[-]]<[<<<<<<<<<<<<<<--->>>>>>>>>>>>>>-]<[-]<<<<<<<<<<<<<[->>>>>>>>>>>>>>+<+
+<<<<<<<<<<<<<]>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]<+++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++.[-]++++++++++.----------<-]<<[-]<
+<[->>>+<+<<]>>>[-<<<+>>>]<[<[-]>[-]]<]
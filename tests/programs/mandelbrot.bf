@@ -0,0 +1,127 @@
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[-]+>>[-]+++<<<<<<[-]+>>[-]+++++++++++
++++++++>>>>>>>>[-]+++++++++++++[-<<<<<<<<<<<<<<[-]+>>[-]++++++++++++++++++++++++
+++++++++>>>>>>>>>>>>>[-]++++++++++++++++++++++++++++++++++++++++[-<<<<<<<<<<<<<<
+<<<<<<<<<<<<<<<<<<<<[-]+<<<[-]++++++++++++++++>>>[>>>[-]++++++++++++++++++++++++
++++++++++<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>+<<<<<<<<<<<<<<+<]>[-<+>]>>>>>>>>>>>>>
+>[->->+<[>-]>[-<<<[-]+>>++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<<]>
+[-][-]+++++++++++++++++++++++++++++++++<<<<<<<<<<[->>>>>>>>>+<<<<<<<<+<]>[-<+>]>
+>>>>>>>[->->+<[>-]>[-<<<[-]+>>++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>
+]<<<]>[-]<<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<[-]>>>-<[-]]>[<<<<<<<<<<<<<<<<[->>>>>>>
+>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<<<<+<]>[-<+>]<[->>>>>>>>>>>>>>>>>+<<<<<<<<<<<<
+<<<<+<]>[-<+>]>>>>>>>>>>>>>>>>>>++++++++++++++++<<[->+>->+<[>-]>[-<+++++++++++++
++++>>>>+<<<<<[-]>>>]<<<<]>>[-]>>>>[-<[->>+<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>
+>>>>>]>>[-<<+>>]<]<<<<++++++++>>>[-<<<<[-<+>>->+<[>-]>[-<++++++++++++++++<<<<<<<
+<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<]<[->+<]>>>>>]<<<[-]<[-]<<<<<<<<<<<<[->>>>>>>>
+>>>>>>>>+<<<<<<<<<<<<<<<+<]>[-<+>]<[->>>>>>>>>>>+<<<<<<<<<<+<]>[-<+>]>>>>>>>>>>>
+>++++++++++++++++<<[->+>->+<[>-]>[-<++++++++++++++++>>>>+<<<<<[-]>>>]<<<<]>>[-]>
+>>>[-<[->>+<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>]>>[-<<+>>]<]<<<<++++++++>>>[-<<<<[-<+
+>>->+<[>-]>[-<++++++++++++++++<<<<<<<<<<<+>>>>>>>>>>>>>]<<<]<[->+<]>>>>>]<<<[-]<
+[-]>[-]+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++<<<<<<<<
+<<<<<<<<<[->>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<+<]>[-<+>]>>>>>>>>>>>>>>>[->->+<[>-]>
+[-<<<[-]+>>+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<<]<<<<<<<<<<[->>
+>>>>>>>>+<<<<<<<<<+<]>[-<+>]>>>>>>>>>[->->+<[>-]>[-<<<[-]+>>++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++>>]<<<]>[-]<<[->>>+<<+<]>>>[-<<<+>>>]<+<[<<<<<[-]>>>
+>>>-<[-]]>[<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<<<<+<
+]>[-<+>]>>>>>[->>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<+<]>[-<+>]>>>>>>>>>>>>>>>>[<[->
+>>>+<<+<<]>>>>[-<<<<+>>>>]<+<[<<[-]>>>-<[-]]>[<<<+>>>-]<<[-]]<<<<<<<<<<<<<<<<<<<
+<<[->>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<<<<<<<<<<<+<]>[-<+>]>>>>>[->
+>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<+<]>[-<+>]>>>>>>>>>>>>>>>>>>>++++++++<<[->+>-
+>+<[>-]>[-<++++++++>>>>+<<<<<[-]>>>]<<<<]>>[-]>>>>[-<[->>+<<<<<<<<+>>>>>>]>>[-<<
++>>]<]<<<<++++>>>[-<<<<[-<+>>->+<[>-]>[-<++++++++<<<+>>>>>]<<<]<[->+<]>>>>>]<<<[
+-]<[-]<<<<<[->>>>>>+<<+<<<<]>>>>>>[-<<<<<<+>>>>>>]<+<[<[-<+>]>>-<[-]]>[<<[-<<+>>
+]>>-]<<<<<[-]>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<<<
+<<<<<<<<<+<<<[>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>+<]>[-<+>]<<
+<<<<<<<<<<<<<<-<<<[-]]>>>[>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>+
+<]>[-<+>]<<<<<<<<<<<<<<<<-]<<<<<<<<<<<<<<<<<<<[-]>>[-]>>>>>>>>>>>>>>>>[->+<]<[->
+>>+<<<]>>[->>+<[>-]>[-<<<<<<<<<<<<<<<<<<<<<+>>+>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<
+<<<+>>>>>>>>>>>>>>>>>]>+>>]<<-<]>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>]<<<<<<<
+<<<<<<<<<<<<<<<<<<<[-]>>[-]>>[->>>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<<]>>>>>>[->
+>>>>>>>>>>>>>+<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>
+>+<]>[-<+>]<<<<<<<<<<+<<<[>>>>>>>>>>>>>>[-<<<<<<<<<<<<+>>>>>>>>>>>>>+<]>[-<+>]<<
+<<<<<<<<<<-<<<[-]]>>>[>>>>>>>>>>>[-<<<<<<<<<<<<<+>>>>>>>>>>>>>>+<]>[-<+>]<<<<<<<
+<<<<<-]<[->+<]<[->>>+<<<]>>[->>+<[>-]>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>+>>>>>>>>>
+>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>]>+>>]<<-<]>[-<<<
+<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>]<<<<<<<<<<<<<<->+<[>-]>[->>[-]<]>
+>>>>>>-]<<[-]<<<<<<<<<<<<<<<[-]>>>>>>[-]>>>>>>>>-]<<[-]<]<<<[->>>>>>>+<<<+<<<<]>
+>>>>>>[-<<<<<<<+>>>>>>>]<<+<[>-]>[->>+++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++.----------------------------------------------------------
+------<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++
+++++++++++++++++++++++++++++++++.-------------------------------------<<<+++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++++++++++++++++
+++++++++++++++++.-------------------------------------<<<+++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++++++++++++++++++++++++++++++.-
+----------------------------------<<<+++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++>>]<<->+<[>-]>[->>+++++++++++++++++++++++++++++++++++.---------------------
+--------------<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[
+->>++++++++++++++++++++++++++++++++++++++++++.----------------------------------
+--------<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++
+++++++++++++++++++++++++++++++++++++++++.---------------------------------------
+----<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++
+++++++++++++++++++++++++++++++++++++.-------------------------------------------
+<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++.-----------------------------
+--------------------------------<<<+++++++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++>>]<<->+<[>-]>[->>+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+++.-------------------------------------------------------------<<<+++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++++++++++++++++++++++
+++++++++++++++++++.---------------------------------------------<<<+++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++.------------------------------------------------
+----------<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[->>+
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++.----------------------
+------------------------------------<<<+++++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++>>]<<->+<[>-]>[->>++++++++++++++++++++++++++++++++++++++++++++++.--------
+--------------------------------------<<<+++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++>>]<<->+<[>-]>[->>++++++++++++++++++++++++++++++++++++++++++++++.------
+----------------------------------------<<<+++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++>>]<<->+<[>-]>[->>++++++++++++++++++++++++++++++++.------------------
+--------------<<<+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++>>]<<->+<[>-]>[
+->>++++++++++++++++++++++++++++++++.--------------------------------<<<+++++++++
+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++++++>>]<<-[-]<<<<<<<<<<<<<<<<[-]>>[-]>>>>[-]>
+>[-]>>>>[-]>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>+<]>[-<
++>]>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<
+]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>>+<+<<]>>>[-<<<+>>>]<-<[-]+>[->>+<+<]
+>>[-<<+>>]<[<<->>[-]]<[-]<<[-]>[->>+<<<+>]>>[-<<+>>]<+<<[>>>[-]>>>>>>>>>>>>>>>>>
+[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>+<]>[-<+>]>>>>>[-<<<<<<<<<<<<<<<<<<<<<<+>>>>>>
+>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<[>>+<[>-]>[-<<<[-]+>>+<[-]+>>>
+]<<-<-]>[-]<<[->>>+<<+<]>>>[-<<<+>>>]<+<[>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<
+<<<<<+>>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<<<<<[-<<<<<<<<<<<<<<->>>>>>>>>>>>>>]<<<<
+<<<<<<<<<<[->>>>>>>>>>>>>>+<<<<<<<<<<<<<<]>>>>>>>>>>>>[-]>>>>>>>>>>>>[-<<<<<<<<<
+<<<+>>>>>>>>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<<<-<[-]]>[>>>>>>>>>>>>>>>>>>>
+>>[-<<<<<<->>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<-]<<[-]<-<<[-]]>>[>>>>>>>>>>>>
+>>>>>>>>>>>>[-<<<<<<+>>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<<-]<[-]>>>>>>>>>>>
+>>>>>>>>>>>>>>>>>>>>>]<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<++++++++++.---------->>>>
+>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]>>>>
+>>>[-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<
+<<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>>+<+<<]>>>[-<<<+>>>]<-<[-]+>[->>+<+<]>>[-<<+>>]<
+[<<->>[-]]<[-]<<[-]>[->>+<<<+>]>>[-<<+>>]<+<<[>>>[-]>>>>>>>>>>>>>>>>>>>>>[-<<<<<
+<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]>>>[-<<<<<<<<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<<[>>+<[>-]>[-<<<[-]+>>+<[-
+]+>>>]<<-<-]>[-]<<[->>>+<<+<]>>>[-<<<+>>>]<+<[>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<
+<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>+<]>[-<+>]<<<<<[-<<<<<<<<<<<<<<<<<<->>>>>>
+>>>>>>>>>>>>]<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<]>>>>>>>>>
+>>>>>>>[-]>>>>>>>>[-<<<<<<<<+>>>>>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<<<-<[-]]
+>[>>>>>>>>>>>>>>>>>>>>>>>[-<<<<->>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<<-]<<[-]<-
+<<[-]]>>[>>>>>>>>>>>>>>>>>>>>>>>>>>[-<<<<+>>>>>+<]>[-<+>]<<<<<<<<<<<<<<<<<<<<<<<
+<<<<-]<[-]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>]
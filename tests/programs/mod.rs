@@ -0,0 +1,39 @@
+//! Brainfuck programs shared by the integration tests and the benchmarks.
+//!
+//! `sierpinski.bf` and `squares.bf` are written by Daniel B. Cristofani
+//! (<http://brainfuck.org>). `factor.bf`, `hanoi.bf` and `mandelbrot.bf` are
+//! smaller takes on the classic programs, generated to keep the test runs short.
+
+/// A program with its input and expected output.
+pub struct Fixture {
+    pub name: &'static str,
+    pub src: &'static str,
+    pub input: &'static [u8],
+    pub output: &'static str,
+}
+
+macro_rules! fixture {
+    ($name:literal) => {
+        fixture!($name, &[])
+    };
+    ($name:literal, $input:expr) => {
+        Fixture {
+            name: $name,
+            src: include_str!(concat!($name, ".bf")),
+            input: $input,
+            output: include_str!(concat!($name, ".out")),
+        }
+    };
+}
+
+/// All programs in the corpus.
+pub const FIXTURES: &[Fixture] = &[
+    fixture!("cat", include_bytes!("cat.in")),
+    fixture!("factor", include_bytes!("factor.in")),
+    fixture!("hanoi"),
+    fixture!("hello_world"),
+    fixture!("mandelbrot"),
+    fixture!("nested_loops"),
+    fixture!("sierpinski"),
+    fixture!("squares"),
+];
@@ -0,0 +1,46 @@
+//! Sessions with the servers speaking over the standard input and output.
+
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+
+/// Send messages to a server and collect every message it sends back.
+pub fn session(command: &str, messages: &[Value]) -> Vec<Value> {
+    let bodies: Vec<_> = messages.iter().map(Value::to_string).collect();
+    raw_session(command, &bodies)
+}
+
+/// Send message bodies to a server, which need not be valid JSON, and
+/// collect every message it sends back.
+pub fn raw_session(command: &str, bodies: &[String]) -> Vec<Value> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bf"))
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    for body in bodies {
+        write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    }
+    drop(stdin);
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut received = Vec::new();
+    loop {
+        let mut header = String::new();
+        if stdout.read_line(&mut header).unwrap() == 0 {
+            break;
+        }
+        let len: usize = header["Content-Length:".len()..].trim().parse().unwrap();
+        stdout.read_line(&mut header).unwrap();
+
+        let mut body = vec![0; len];
+        stdout.read_exact(&mut body).unwrap();
+        received.push(serde_json::from_slice(&body).unwrap());
+    }
+
+    assert!(child.wait().unwrap().success());
+    received
+}
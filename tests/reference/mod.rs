@@ -0,0 +1,63 @@
+//! Deliberately naive reference interpreter used for differential testing.
+//!
+//! The interpreter works directly on the source characters without any
+//! lexing or optimization, so its semantics are easy to verify by reading.
+
+const HEAP_SIZE: usize = 30_000;
+
+/// Run a program, giving up after `max_steps` commands.
+///
+/// Any character which is not a command is ignored. Returns `None` if the
+/// program does not finish within the step limit or has unbalanced brackets.
+pub fn run(src: &str, input: &[u8], max_steps: usize) -> Option<Vec<u8>> {
+    let program: Vec<char> = src.chars().collect();
+    let mut memory = vec![0u8; HEAP_SIZE];
+    let mut ptr = 0;
+    let mut pc = 0;
+    let mut input = input.iter();
+    let mut out = Vec::new();
+
+    for _ in 0..max_steps {
+        let Some(&ch) = program.get(pc) else {
+            return Some(out);
+        };
+
+        match ch {
+            '+' => memory[ptr] = memory[ptr].wrapping_add(1),
+            '-' => memory[ptr] = memory[ptr].wrapping_sub(1),
+            '>' => ptr = (ptr + 1) % HEAP_SIZE,
+            '<' => ptr = (ptr + HEAP_SIZE - 1) % HEAP_SIZE,
+            // The interpreter prints cells as `char`s, so do the same here
+            '.' => out.extend((memory[ptr] as char).to_string().bytes()),
+            ',' => memory[ptr] = input.next().copied().unwrap_or(0),
+            '[' if memory[ptr] == 0 => pc = matching(&program, pc, 1)?,
+            ']' if memory[ptr] != 0 => pc = matching(&program, pc, -1)?,
+            _ => {}
+        }
+
+        pc += 1;
+    }
+
+    None
+}
+
+/// Find the bracket matching the one at `pc`, searching in `direction`.
+fn matching(program: &[char], mut pc: usize, direction: isize) -> Option<usize> {
+    let mut depth = 0;
+
+    loop {
+        match program[pc] {
+            '[' => depth += direction,
+            ']' => depth -= direction,
+            _ => {}
+        }
+
+        if depth == 0 {
+            return Some(pc);
+        }
+
+        pc = pc
+            .checked_add_signed(direction)
+            .filter(|&pc| pc < program.len())?;
+    }
+}
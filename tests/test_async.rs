@@ -0,0 +1,52 @@
+#![cfg(feature = "async")]
+
+use brainfuck_interpreter::interpreter::interpret_async;
+use brainfuck_lexer::lex;
+
+#[tokio::test]
+async fn hello_world() {
+    let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut buf = Vec::new();
+    let mut input = &[0u8][..];
+    let res = interpret_async(&bf.unwrap(), &mut input, &mut buf).await;
+    assert!(res.is_ok());
+
+    let str: String = buf.into_iter().map(|v| v as char).collect();
+    assert_eq!(str, "Hello World!\n".to_string());
+}
+
+#[tokio::test]
+async fn cat_string() {
+    let src = ",[.,]".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut buf = Vec::new();
+    let mut input = "This is the way".as_bytes();
+    let res = interpret_async(&bf.unwrap(), &mut input, &mut buf).await;
+    assert!(res.is_ok());
+
+    let str: String = buf.into_iter().map(|v| v as char).collect();
+    assert_eq!(str, "This is the way".to_string());
+}
+
+#[tokio::test]
+async fn god_morgen() {
+    let src = include_str!("./god_morgen.bf").to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut buf = Vec::new();
+    let mut input = &[][..];
+    let res = interpret_async(&bf.unwrap(), &mut input, &mut buf).await;
+    assert!(res.is_ok());
+
+    let str: String = buf.into_iter().map(|v| v as char).collect();
+    assert_eq!(str, "God Morgen!".to_string());
+}
@@ -0,0 +1,132 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use brainfuck_interpreter::batch::{run_many, run_many_with_config, BatchConfig};
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{InterpreterConfig, Limits};
+use brainfuck_interpreter::tape::TapeKind;
+use brainfuck_lexer::{lex, Token};
+
+#[test]
+fn order() {
+    let programs: Vec<_> = (0..64u8)
+        .map(|i| (lex(",[->+<]>.").unwrap(), vec![i]))
+        .collect();
+    let results = run_many(&programs);
+
+    assert_eq!(results.len(), 64);
+    for (i, res) in results.iter().enumerate() {
+        assert!(res.error.is_none());
+        assert_eq!(res.output, [i as u8]);
+    }
+}
+
+#[test]
+fn single_thread() {
+    let programs = vec![(lex("+.").unwrap(), vec![]), (lex("++.").unwrap(), vec![])];
+    let config = BatchConfig {
+        threads: NonZeroUsize::new(1),
+        ..Default::default()
+    };
+    let results = run_many_with_config(&programs, &config);
+
+    assert_eq!(results[0].output, [1]);
+    assert_eq!(results[1].output, [2]);
+}
+
+#[test]
+fn empty() {
+    assert!(run_many(&[]).is_empty());
+}
+
+#[test]
+fn limits() {
+    let programs = vec![
+        (lex("+.+.+.").unwrap(), vec![]),
+        (lex("+.").unwrap(), vec![]),
+    ];
+    let config = BatchConfig {
+        interpreter: InterpreterConfig {
+            limits: Limits {
+                max_output: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let results = run_many_with_config(&programs, &config);
+
+    assert!(matches!(
+        results[0].error,
+        Some(BrainfuckError::OutputLimitExceeded(2))
+    ));
+    assert_eq!(results[0].output, [1, 2]);
+    assert!(results[1].error.is_none());
+}
+
+#[test]
+fn timeout() {
+    let programs = vec![
+        (lex("+.[>+<]").unwrap(), vec![]),
+        (lex("+.").unwrap(), vec![]),
+    ];
+    let config = BatchConfig {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let results = run_many_with_config(&programs, &config);
+
+    assert!(matches!(results[0].error, Some(BrainfuckError::Cancelled)));
+    assert_eq!(results[0].output, [1]);
+    assert!(results[1].error.is_none());
+}
+
+#[test]
+fn timeout_per_program() {
+    // Every program on the single thread gets the whole timeout
+    let programs: Vec<_> = (0..4)
+        .map(|_| (lex("+[>+<]").unwrap(), vec![]))
+        .chain([(lex("+.").unwrap(), vec![])])
+        .collect();
+    let config = BatchConfig {
+        timeout: Some(Duration::from_millis(20)),
+        threads: NonZeroUsize::new(1),
+        ..Default::default()
+    };
+    let results = run_many_with_config(&programs, &config);
+
+    for res in &results[..4] {
+        assert!(matches!(res.error, Some(BrainfuckError::Cancelled)));
+    }
+    assert!(results[4].error.is_none());
+    assert_eq!(results[4].output, [1]);
+}
+
+#[test]
+fn panic() {
+    // Writing to the last location of a bidirectional tape needs more
+    // memory than can be allocated
+    let programs = vec![
+        (
+            vec![Token::Next(isize::MAX as usize), Token::Increment(1)],
+            vec![],
+        ),
+        (lex("+.").unwrap(), vec![]),
+    ];
+    let config = BatchConfig {
+        interpreter: InterpreterConfig {
+            tape: TapeKind::Bidirectional,
+            ..Default::default()
+        },
+        threads: NonZeroUsize::new(1),
+        ..Default::default()
+    };
+    let results = run_many_with_config(&programs, &config);
+
+    assert!(matches!(
+        results[0].error,
+        Some(BrainfuckError::Panicked(_))
+    ));
+    assert_eq!(results[1].output, [1]);
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "cache")]
+
+use brainfuck_interpreter::cache::lex_cached;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized};
+use std::path::PathBuf;
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bf-cache-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn cached_files(dir: &PathBuf) -> Vec<PathBuf> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn reuse() {
+    let dir = cache_dir("reuse");
+    let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.";
+    let optimizer = Optimizer::default();
+
+    let code = lex_cached(src, &optimizer, &dir).unwrap();
+    assert_eq!(Ok(code.clone()), lex(src));
+
+    let files = cached_files(&dir);
+    assert_eq!(files.len(), 1);
+
+    assert_eq!(lex_cached(src, &optimizer, &dir).unwrap(), code);
+    assert_eq!(cached_files(&dir), files);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn keyed_by_passes() {
+    let dir = cache_dir("passes");
+    let src = "+[-]";
+
+    let basic = lex_cached(src, &Optimizer::default(), &dir).unwrap();
+    let none = lex_cached(src, &Optimizer::new(OptLevel::None), &dir).unwrap();
+
+    assert_eq!(Ok(none), lex_unoptimized(src));
+    assert_ne!(basic, lex_unoptimized(src).unwrap());
+    assert_eq!(cached_files(&dir).len(), 2);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn corrupted() {
+    let dir = cache_dir("corrupted");
+    let src = "+++.";
+    let optimizer = Optimizer::default();
+
+    let code = lex_cached(src, &optimizer, &dir).unwrap();
+    let file = cached_files(&dir).remove(0);
+    std::fs::write(&file, b"garbage").unwrap();
+
+    assert_eq!(lex_cached(src, &optimizer, &dir).unwrap(), code);
+    assert_ne!(std::fs::read(file).unwrap(), b"garbage");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn checked_against_source() {
+    let dir = cache_dir("checked");
+    let optimizer = Optimizer::default();
+
+    lex_cached("+.", &optimizer, &dir).unwrap();
+    let file = cached_files(&dir).remove(0);
+
+    // Another program stored under the same name is not used
+    let other = cache_dir("checked-other");
+    lex_cached("-.", &optimizer, &other).unwrap();
+    std::fs::copy(cached_files(&other).remove(0), &file).unwrap();
+
+    assert_eq!(
+        lex_cached("+.", &optimizer, &dir).unwrap(),
+        lex("+.").unwrap()
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+    std::fs::remove_dir_all(other).unwrap();
+}
@@ -0,0 +1,203 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn bf() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_bf"))
+}
+
+#[test]
+fn test_programs() {
+    let output = bf()
+        .args([
+            "test",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/programs"),
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("test hello_world.bf ... ok"));
+    assert!(stdout.contains("test result: ok. 8 passed; 0 failed"));
+}
+
+#[test]
+fn test_failures() {
+    let dir = std::env::temp_dir().join(format!("bf-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("echo.b"), ",.").unwrap();
+    std::fs::write(dir.join("echo.in"), "a").unwrap();
+    std::fs::write(dir.join("echo.out"), "b").unwrap();
+    std::fs::write(dir.join("runaway.b"), "+[>+<]").unwrap();
+    std::fs::write(dir.join("runaway.out"), "").unwrap();
+
+    let output = bf()
+        .arg("test")
+        .arg(&dir)
+        .args(["--timeout", "0.1"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("-\"b\"\n+\"a\""), "{}", stdout);
+    assert!(stdout.contains("timed out"), "{}", stdout);
+    assert!(stdout.contains("test result: FAILED. 0 passed; 2 failed"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "dialects")]
+#[test]
+fn symbols() {
+    let path = std::env::temp_dir().join(format!("bf-symbols-{}.toml", std::process::id()));
+    std::fs::write(&path, "increment = \"Ook. Ook.\"\nprint = \"Ook! Ook.\"\n").unwrap();
+
+    let src = format!("{} Ook! Ook. + .", "Ook. Ook. ".repeat(65));
+    let output = bf().arg("--symbols").arg(&path).arg(src).output().unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    // The standard commands are comments in the dialect
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "A");
+}
+
+#[test]
+fn overflow() {
+    let src = format!("+[->{}<]>.", "+".repeat(200));
+
+    for overflow in ["wrap", "saturate", "error"] {
+        let output = bf()
+            .args(["-O", "aggressive", "--overflow", overflow, &src])
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{}", overflow);
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "\u{c8}");
+    }
+}
+
+#[test]
+fn print_numeric() {
+    let output = bf().args(["--print-numeric", "+++.>-."]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "3\n255\n");
+}
+
+#[test]
+fn emit_listing() {
+    let output = bf().args(["--emit", "listing", "+\n."]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "   1 | +\n     |     0  add 1\n   2 | .\n     |     1  print\n"
+    );
+}
+
+#[test]
+fn explain() {
+    let output = bf().args(["explain", "+[->+<]"]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+[->+<]\n ^ multiplies cell 0 by 1 into cell 1\n"
+    );
+}
+
+#[test]
+fn pipe() {
+    let mut child = bf()
+        .args(["pipe", ",[.,]", ",[+.,]", ",[+.,]"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"abc").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"cde");
+
+    // The first program never stops printing, until the second stops reading
+    let output = bf()
+        .args(["pipe", "+[.]", ",.", ",+."])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, [2]);
+
+    // Values above 127 are passed on as they are, and only encoded by the
+    // last program
+    let output = bf()
+        .args(["pipe", "--", "-.", ",-."])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, "\u{fe}".as_bytes());
+
+    let output = bf()
+        .args(["pipe", "--encoding", "raw", "--", "-.", ",."])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, [255]);
+}
+
+#[cfg(feature = "session")]
+#[test]
+fn record_replay() {
+    let path = std::env::temp_dir().join(format!("bf-session-{}.json", std::process::id()));
+    let mut child = bf()
+        .arg("--record")
+        .arg(&path)
+        .arg(",[.,]")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"hi").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"hi");
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"input\":[104,105]}\n"
+    );
+
+    let output = bf()
+        .arg("--replay")
+        .arg(&path)
+        .arg(",[+.,]")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"ij");
+}
+
+#[test]
+fn coverage() {
+    let path = std::env::temp_dir().join(format!("bf-coverage-{}.info", std::process::id()));
+    let output = bf()
+        .args(["--coverage", "lcov", "--coverage-file"])
+        .arg(&path)
+        .arg("++[-]\n[.]")
+        .output()
+        .unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert!(output.status.success());
+    assert!(report.contains("DA:1,2\nDA:2,1\n"), "{}", report);
+}
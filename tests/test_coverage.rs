@@ -0,0 +1,58 @@
+mod programs;
+
+use brainfuck_interpreter::coverage::{interpret_with_coverage, Coverage};
+use brainfuck_lexer::Span;
+use programs::FIXTURES;
+use std::io::Cursor;
+
+fn coverage(src: &str) -> Coverage {
+    interpret_with_coverage(src, &mut Cursor::new(vec![]), &mut Vec::new()).unwrap()
+}
+
+#[test]
+fn dead_code() {
+    // The first loop is never entered, as the cell starts at zero
+    let coverage = coverage("[.]\n+++[-]\n");
+    let counts: Vec<_> = coverage.counts().iter().map(|&(_, count)| count).collect();
+
+    assert_eq!(counts, [1, 0, 0, 1, 1, 3, 3]);
+    assert_eq!(coverage.lines(), [(1, 1), (2, 3)]);
+}
+
+#[test]
+fn spans_of_commands() {
+    let coverage = coverage("a ++ b [-]");
+
+    assert_eq!(coverage.counts()[0], (Span { start: 2, end: 4 }, 1));
+    assert_eq!(coverage.counts()[1], (Span { start: 7, end: 8 }, 1));
+}
+
+#[test]
+fn lcov() {
+    let coverage = coverage("+\n\n[-]\n[\n.\n]");
+    let expected = "SF:x.bf\nDA:1,1\nDA:3,1\nDA:4,1\nDA:5,0\nDA:6,0\nLF:5\nLH:3\nend_of_record\n";
+
+    assert_eq!(coverage.to_lcov("x.bf"), expected);
+}
+
+#[test]
+fn html() {
+    let html = coverage("<a&b>[+]").to_html("<x>");
+
+    assert!(html.contains("<title>&lt;x&gt;</title>"), "{}", html);
+    assert!(html.contains("<span class=\"hit\" title=\"1\">&lt;</span>a&amp;b"));
+    assert!(html.contains("<span class=\"miss\" title=\"0\">+</span>"));
+    assert!(html.contains("<p>1 of 1 lines executed</p>"));
+}
+
+#[test]
+fn fixtures_are_covered() {
+    for fixture in FIXTURES {
+        let mut output = Vec::new();
+        let mut input = Cursor::new(fixture.input);
+        let coverage = interpret_with_coverage(fixture.src, &mut input, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), fixture.output);
+        assert!(coverage.counts()[0].1 > 0, "{}", fixture.name);
+    }
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "dap")]
+
+mod protocol;
+
+use serde_json::{json, Value};
+
+/// Send requests to the debug adapter and collect every message it sends back.
+fn session(requests: &[(&str, Value)]) -> Vec<Value> {
+    let requests: Vec<_> = requests
+        .iter()
+        .enumerate()
+        .map(|(seq, (command, arguments))| {
+            json!({ "seq": seq + 1, "type": "request", "command": command, "arguments": arguments })
+        })
+        .collect();
+
+    protocol::session("dap", &requests)
+}
+
+fn find<'a>(messages: &'a [Value], kind: &str, name: &str) -> Vec<&'a Value> {
+    let key = if kind == "event" { "event" } else { "command" };
+
+    messages
+        .iter()
+        .filter(|message| message["type"] == kind && message[key] == name)
+        .collect()
+}
+
+#[test]
+fn breakpoints() {
+    let path = std::env::temp_dir().join(format!("bf-dap-{}.bf", std::process::id()));
+    std::fs::write(&path, "++++++++[>++++++++<-]>+.\n+.\n").unwrap();
+
+    let messages = session(&[
+        ("initialize", json!({ "adapterID": "brainfuck" })),
+        ("launch", json!({ "program": path })),
+        ("setBreakpoints", json!({ "breakpoints": [{ "line": 2 }] })),
+        ("configurationDone", json!({})),
+        ("stackTrace", json!({ "threadId": 1 })),
+        ("variables", json!({ "variablesReference": 1 })),
+        ("continue", json!({ "threadId": 1 })),
+        ("disconnect", json!({})),
+    ]);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(messages
+        .iter()
+        .filter(|message| message["type"] == "response")
+        .all(|response| response["success"] == true));
+
+    let breakpoints = find(&messages, "response", "setBreakpoints");
+    assert_eq!(breakpoints[0]["body"]["breakpoints"][0]["verified"], true);
+
+    let stopped = find(&messages, "event", "stopped");
+    assert_eq!(stopped.len(), 1);
+    assert_eq!(stopped[0]["body"]["reason"], "breakpoint");
+
+    let frames = find(&messages, "response", "stackTrace");
+    assert_eq!(frames[0]["body"]["stackFrames"][0]["line"], 2);
+    assert_eq!(frames[0]["body"]["stackFrames"][0]["column"], 1);
+
+    let variables = find(&messages, "response", "variables");
+    assert_eq!(variables[0]["body"]["variables"][1]["value"], "65");
+
+    let output: String = find(&messages, "event", "output")
+        .iter()
+        .map(|event| event["body"]["output"].as_str().unwrap())
+        .collect();
+    assert_eq!(output, "AB");
+    assert_eq!(find(&messages, "event", "terminated").len(), 1);
+}
+
+#[test]
+fn breakpoint_on_first_command() {
+    let path = std::env::temp_dir().join(format!("bf-dap-first-{}.bf", std::process::id()));
+    std::fs::write(&path, "+.\n").unwrap();
+
+    let messages = session(&[
+        ("initialize", json!({ "adapterID": "brainfuck" })),
+        ("launch", json!({ "program": path })),
+        ("setBreakpoints", json!({ "breakpoints": [{ "line": 1 }] })),
+        ("configurationDone", json!({})),
+        (
+            "readMemory",
+            json!({ "memoryReference": "0", "count": u64::MAX }),
+        ),
+        ("continue", json!({ "threadId": 1 })),
+        ("disconnect", json!({})),
+    ]);
+    std::fs::remove_file(path).unwrap();
+
+    let stopped = find(&messages, "event", "stopped");
+    assert_eq!(stopped.len(), 1);
+    assert_eq!(stopped[0]["body"]["reason"], "breakpoint");
+
+    let memory = find(&messages, "response", "readMemory");
+    assert_eq!(memory[0]["success"], true);
+
+    // Continuing from the breakpoint does not hit it again
+    let output = find(&messages, "event", "output");
+    assert_eq!(output[0]["body"]["output"], "\u{1}");
+    assert_eq!(find(&messages, "event", "terminated").len(), 1);
+}
+
+#[test]
+fn stepping() {
+    let path = std::env::temp_dir().join(format!("bf-dap-step-{}.bf", std::process::id()));
+    std::fs::write(&path, "+[\n-\n]\n+").unwrap();
+
+    let messages = session(&[
+        ("initialize", json!({ "adapterID": "brainfuck" })),
+        ("launch", json!({ "program": path, "stopOnEntry": true })),
+        ("configurationDone", json!({})),
+        ("stepIn", json!({ "threadId": 1 })),
+        ("next", json!({ "threadId": 1 })),
+        ("stackTrace", json!({ "threadId": 1 })),
+        ("stepBack", json!({ "threadId": 1 })),
+        ("stackTrace", json!({ "threadId": 1 })),
+        ("disconnect", json!({})),
+    ]);
+    std::fs::remove_file(path).unwrap();
+
+    let reasons: Vec<_> = find(&messages, "event", "stopped")
+        .iter()
+        .map(|event| event["body"]["reason"].clone())
+        .collect();
+    assert_eq!(reasons, ["entry", "step", "step", "step"]);
+
+    // Stepping over the loop moves past its end, and stepping back returns
+    // to the end it left from
+    let frames = find(&messages, "response", "stackTrace");
+    assert_eq!(frames[0]["body"]["stackFrames"][0]["line"], 4);
+    assert_eq!(frames[1]["body"]["stackFrames"][0]["line"], 3);
+    assert_eq!(frames[1]["body"]["stackFrames"][0]["column"], 1);
+}
@@ -0,0 +1,69 @@
+use std::io::Cursor;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::flat::{flatten, Instruction};
+use brainfuck_interpreter::interpreter::interpret_in_place;
+use brainfuck_interpreter::io::StdIo;
+use brainfuck_lexer::lex_unoptimized;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+
+#[test]
+fn nested_loops() {
+    let program = flatten(&lex_unoptimized("[+[-]][>]").unwrap());
+    let jumps: Vec<_> = program
+        .iter()
+        .map(|instruction| match *instruction {
+            Instruction::LoopStart(end) => Some(end),
+            Instruction::LoopEnd(start) => Some(start),
+            Instruction::Op(_) => None,
+        })
+        .collect();
+
+    assert_eq!(
+        jumps,
+        [
+            Some(5),
+            None,
+            Some(4),
+            None,
+            Some(2),
+            Some(0),
+            Some(8),
+            None,
+            Some(6)
+        ]
+    );
+}
+
+#[test]
+fn in_place() {
+    let program = flatten(&lex_unoptimized("<++[->+++<]>.[>+<-]").unwrap());
+    let mut memory = [0u8; 4];
+    let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+    interpret_in_place(&program, &mut memory, &mut io).unwrap();
+
+    // Moving left of the first cell wraps around to the last
+    assert_eq!(memory, [0, 6, 0, 0]);
+    assert_eq!(io.into_inner().1, [6]);
+
+    // Optimized for the length of the memory, moving right past the last
+    // cell reaches the cells set before instead of unused ones
+    let src = lex_unoptimized("+++[>++<-]>>>>>[.[-]]").unwrap();
+    let optimizer = Optimizer::new(OptLevel::Aggressive).with_tape_len(4);
+    let program = flatten(&optimizer.optimize(&src));
+    let mut memory = [0u8; 4];
+    let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+    interpret_in_place(&program, &mut memory, &mut io).unwrap();
+
+    assert_eq!(memory, [0; 4]);
+    assert_eq!(io.into_inner().1, [6]);
+}
+
+#[test]
+fn in_place_empty_memory() {
+    let program = flatten(&lex_unoptimized("+").unwrap());
+    let mut io = StdIo::new(Cursor::new(vec![]), Vec::new());
+
+    let res = interpret_in_place(&program, &mut [], &mut io);
+    assert!(matches!(res, Err(BrainfuckError::TapeLimitExceeded(0))));
+}
@@ -0,0 +1,59 @@
+use std::io::Cursor;
+
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_lexer::error::GenerateError;
+use brainfuck_lexer::generate::text_to_bf;
+use brainfuck_lexer::lex;
+
+fn run(src: &str) -> String {
+    let mut output = Vec::new();
+    interpret(&lex(src).unwrap(), &mut Cursor::new(vec![]), &mut output).unwrap();
+
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let texts = [
+        "",
+        "a",
+        "Hello, World!\n",
+        "zyxwvutsrqponmlkjihgfedcba",
+        "\0\x7f\0 \x7f",
+        "The quick brown fox jumps over the lazy dog.",
+    ];
+
+    for text in texts {
+        assert_eq!(run(&text_to_bf(text).unwrap()), text);
+    }
+}
+
+#[test]
+fn every_character() {
+    let text: String = (0..=255u8).rev().map(char::from).collect();
+    assert_eq!(run(&text_to_bf(&text).unwrap()), text);
+}
+
+#[test]
+fn short_programs() {
+    // Shorter than adding every difference directly
+    let text = "Hello, World!\n";
+    let naive: usize = text
+        .bytes()
+        .scan(0u8, |current, byte| {
+            let diff = byte.wrapping_sub(*current) as i8;
+            *current = byte;
+            Some(diff.unsigned_abs() as usize + 1)
+        })
+        .sum();
+
+    assert!(text_to_bf(text).unwrap().len() < naive);
+}
+
+#[test]
+fn non_ascii() {
+    // Characters are printed from their code points, not their UTF-8 bytes
+    assert_eq!(run(&text_to_bf("café").unwrap()), "café");
+
+    assert_eq!(text_to_bf("→"), Err(GenerateError::Unprintable('→')));
+}
@@ -0,0 +1,120 @@
+mod programs;
+
+use std::collections::VecDeque;
+use std::io::Cursor;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::interpret_with_handler;
+use brainfuck_interpreter::io::{IoHandler, OutputMode, StdIo, CP437};
+use brainfuck_lexer::lex;
+use programs::FIXTURES;
+
+/// Handler feeding the output of the program back as its input.
+#[derive(Default)]
+struct Loopback {
+    queue: VecDeque<u8>,
+    written: Vec<u8>,
+}
+
+impl IoHandler for Loopback {
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        Ok(self.queue.pop_front())
+    }
+
+    fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        self.queue.push_back(byte);
+        self.written.push(byte);
+        Ok(())
+    }
+}
+
+/// Handler which fails to produce any input.
+struct Broken;
+
+impl IoHandler for Broken {
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        Err(std::io::Error::other("no input"))
+    }
+
+    fn write_byte(&mut self, _byte: u8) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_handler() {
+    // Print a value, then read it back and print it incremented. Values
+    // above 127 are passed to the handler as a single byte
+    let src = "+++++++++[>++++++++++++++<-]>.,+.,+.,".to_string();
+    let mut io = Loopback::default();
+    interpret_with_handler(&lex(src).unwrap(), &mut io).unwrap();
+
+    assert_eq!(io.written, [126, 127, 128]);
+    assert_eq!(io.queue, []);
+}
+
+#[test]
+fn end_of_input() {
+    let src = "+,.".to_string();
+    let mut io = Loopback::default();
+    interpret_with_handler(&lex(src).unwrap(), &mut io).unwrap();
+
+    assert_eq!(io.written, [0]);
+}
+
+#[test]
+fn handler_errors() {
+    let res = interpret_with_handler(&lex(",").unwrap(), &mut Broken);
+
+    assert!(matches!(res, Err(BrainfuckError::IOError(_))));
+}
+
+#[test]
+fn std_adapter() {
+    for fixture in FIXTURES {
+        let block = lex(fixture.src).unwrap();
+
+        let mut io = StdIo::new(Cursor::new(fixture.input), Vec::new());
+        interpret_with_handler(&block, &mut io).unwrap();
+        let (_, output) = io.into_inner();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            fixture.output,
+            "{}",
+            fixture.name
+        );
+    }
+}
+
+/// Run a program printing every value from 126 to 130, and get its output.
+fn output_with(mode: OutputMode) -> Vec<u8> {
+    let src = "+++++++++[>++++++++++++++<-]>.+.+.+.+.";
+    let mut io = StdIo::new(Cursor::new(vec![]), Vec::new()).with_output_mode(mode);
+    interpret_with_handler(&lex(src).unwrap(), &mut io).unwrap();
+
+    io.into_inner().1
+}
+
+#[test]
+fn output_modes() {
+    assert_eq!(
+        output_with(OutputMode::Unicode),
+        "~\u{7f}\u{80}\u{81}\u{82}".as_bytes()
+    );
+    assert_eq!(output_with(OutputMode::Raw), [126, 127, 128, 129, 130]);
+    assert_eq!(
+        output_with(OutputMode::Numeric),
+        b"126\n127\n128\n129\n130\n"
+    );
+
+    let codepage = OutputMode::Codepage(CP437.chars().collect());
+    assert_eq!(output_with(codepage), "~\u{7f}Çüé".as_bytes());
+}
+
+#[test]
+fn short_codepage() {
+    // Values without a character in the codepage are written as Unicode
+    let codepage = OutputMode::Codepage(vec!['€']);
+    assert_eq!(output_with(codepage), "~\u{7f}€\u{81}\u{82}".as_bytes());
+}
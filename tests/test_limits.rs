@@ -0,0 +1,111 @@
+use std::io::Cursor;
+use std::sync::atomic::AtomicBool;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{interpret_with_config, InterpreterConfig, Limits};
+use brainfuck_interpreter::io::StdIo;
+use brainfuck_interpreter::tape::TapeKind;
+use brainfuck_lexer::lex;
+
+fn run(src: &str, input: &[u8], tape: TapeKind, limits: Limits) -> Result<Vec<u8>, BrainfuckError> {
+    let config = InterpreterConfig {
+        tape,
+        limits,
+        ..Default::default()
+    };
+    let mut io = StdIo::new(Cursor::new(input), Vec::new());
+    interpret_with_config(
+        &lex(src).unwrap(),
+        &mut io,
+        &config,
+        &AtomicBool::new(false),
+    )?;
+
+    Ok(io.into_inner().1)
+}
+
+#[test]
+fn steps() {
+    let limits = Limits {
+        max_steps: Some(1000),
+        ..Default::default()
+    };
+
+    let res = run("+[>+<]", &[], TapeKind::Wrapping, limits);
+    assert!(matches!(res, Err(BrainfuckError::StepLimitExceeded(1000))));
+
+    // Skipping a loop is a single step
+    let limits = Limits {
+        max_steps: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(run("[>]+.", &[], TapeKind::Wrapping, limits).unwrap(), [1]);
+}
+
+#[test]
+fn output() {
+    let limits = Limits {
+        max_output: Some(3),
+        ..Default::default()
+    };
+
+    let res = run("+[.]", &[], TapeKind::Wrapping, limits);
+    assert!(matches!(res, Err(BrainfuckError::OutputLimitExceeded(3))));
+    assert_eq!(
+        run("+...", &[], TapeKind::Wrapping, limits).unwrap(),
+        [1, 1, 1]
+    );
+}
+
+#[test]
+fn input() {
+    let limits = Limits {
+        max_input: Some(2),
+        ..Default::default()
+    };
+
+    let res = run(",[.,]", b"abc", TapeKind::Wrapping, limits);
+    assert!(matches!(res, Err(BrainfuckError::InputLimitExceeded(2))));
+    assert_eq!(
+        run(",.,.", b"abc", TapeKind::Wrapping, limits).unwrap(),
+        b"ab"
+    );
+
+    // Reading past the end of the input reads nothing
+    assert_eq!(run(",,,,.", b"a", TapeKind::Wrapping, limits).unwrap(), [0]);
+}
+
+#[test]
+fn cells() {
+    let limits = Limits {
+        max_cells: Some(4096),
+        ..Default::default()
+    };
+
+    for tape in [TapeKind::Bidirectional, TapeKind::Sparse] {
+        let res = run("+[>+]", &[], tape, limits);
+        assert!(
+            matches!(res, Err(BrainfuckError::TapeLimitExceeded(4096))),
+            "{:?}",
+            tape
+        );
+    }
+
+    // The pointer of the wrapping tape may not move past the limit instead
+    let limits = Limits {
+        max_cells: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        run(">>+[-<+>]<<.", &[], TapeKind::Wrapping, limits).unwrap(),
+        [0]
+    );
+    for src in [">>>", "<"] {
+        let res = run(src, &[], TapeKind::Wrapping, limits);
+        assert!(
+            matches!(res, Err(BrainfuckError::TapeLimitExceeded(3))),
+            "{}",
+            src
+        );
+    }
+}
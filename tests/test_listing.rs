@@ -0,0 +1,41 @@
+use brainfuck_interpreter::listing::listing;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+
+#[test]
+fn lines() {
+    let src = ">>.[-]\n[this is dead]\n>+++.\n";
+
+    assert_eq!(
+        listing(src, &Optimizer::new(OptLevel::Basic)).unwrap(),
+        "   1 | >>.[-]\n     |     0  move 2\n     |     1  print\n     |     2  zero\n   \
+         2 | [this is dead]\n   3 | >+++.\n     |     3  move 1\n     |     4  add 3\n     \
+         |     5  print\n"
+    );
+}
+
+#[test]
+fn loops() {
+    let src = "+[\n.\n]>";
+
+    assert_eq!(
+        listing(src, &Optimizer::new(OptLevel::Basic)).unwrap(),
+        "   1 | +[\n     |     0  add 1\n     |     1  jz 4\n   2 | .\n     |     2  print\n   \
+         3 | ]>\n     |     3  jnz 2\n     |     4  move 1\n"
+    );
+}
+
+#[test]
+fn folded() {
+    let src = "++\n[>+<-]\n>.";
+
+    assert_eq!(
+        listing(src, &Optimizer::new(OptLevel::Aggressive)).unwrap(),
+        "   1 | ++\n   2 | [>+<-]\n   3 | >.\n     |     0  set 2 at +1\n     \
+         |     1  move 1\n     |     2  print\n"
+    );
+}
+
+#[test]
+fn unbalanced() {
+    assert!(listing("[", &Optimizer::new(OptLevel::Basic)).is_err());
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "llvm")]
+
+mod programs;
+
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_interpreter::llvm::{emit, Emit};
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized};
+use programs::FIXTURES;
+
+/// Whether the tools needed to build executables are installed.
+///
+/// The IR uses opaque pointers, which `llc` only accepts since LLVM 15.
+fn has_toolchain() -> bool {
+    let llc = std::env::var_os("LLC").unwrap_or("llc".into());
+    let Ok(output) = Command::new(llc).arg("--version").output() else {
+        return false;
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .split("LLVM version ")
+        .nth(1)
+        .and_then(|version| version.split('.').next())
+        .and_then(|major| major.parse::<u32>().ok())
+        .is_some_and(|major| major >= 15)
+}
+
+/// Compile and run a program, returning its output.
+fn run_native(src: &brainfuck_lexer::Block, name: &str, input: &[u8]) -> Vec<u8> {
+    let exe = std::env::temp_dir().join(format!("bf-test-{}-{}", name, std::process::id()));
+    emit(src, Emit::Executable, &exe).unwrap();
+
+    let mut child = Command::new(&exe)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The program may exit before reading all of its input
+    let _ = child.stdin.take().unwrap().write_all(input);
+    let output = child.wait_with_output().unwrap();
+
+    std::fs::remove_file(exe).unwrap();
+    output.stdout
+}
+
+fn interpreted(src: &brainfuck_lexer::Block, input: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    interpret(src, &mut Cursor::new(input), &mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn fixtures() {
+    if !has_toolchain() {
+        return;
+    }
+
+    for fixture in FIXTURES {
+        let src = lex_unoptimized(fixture.src).unwrap();
+        let aggressive = Optimizer::new(OptLevel::Aggressive).optimize(&src);
+
+        for (level, block) in [("none", src), ("aggressive", aggressive)] {
+            let name = format!("{}-{}", fixture.name, level);
+            let output = run_native(&block, &name, fixture.input);
+            assert_eq!(output, fixture.output.as_bytes(), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn same_as_interpreter() {
+    if !has_toolchain() {
+        return;
+    }
+
+    // Wrapping around the memory, input past its end and non-ASCII output
+    let sources = ["<+++.>>>[-]<<<<-.", ",.,.,.", "-.>++++++++[<---->-]<."];
+
+    for (i, src) in sources.into_iter().enumerate() {
+        let block = lex(src).unwrap();
+        let output = run_native(&block, &i.to_string(), b"a");
+        assert_eq!(output, interpreted(&block, b"a"), "{}", src);
+    }
+}
+
+#[test]
+fn emit_ir() {
+    let path: PathBuf = std::env::temp_dir().join(format!("bf-test-{}.ll", std::process::id()));
+    emit(&lex("+.").unwrap(), Emit::Ir, &path).unwrap();
+
+    let ir = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(ir.contains("define i32 @main()"));
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "lsp")]
+
+mod protocol;
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///test.bf";
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Open a document, send requests about it and collect the responses by id.
+fn session(text: &str, requests: &[Value]) -> (Vec<Value>, Vec<Value>) {
+    let mut messages = vec![
+        request(0, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        notification(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": URI, "languageId": "brainfuck", "version": 1, "text": text } }),
+        ),
+    ];
+    messages.extend_from_slice(requests);
+    messages.push(request(u64::MAX, "shutdown", Value::Null));
+    messages.push(notification("exit", Value::Null));
+
+    let received = protocol::session("lsp", &messages);
+    let (notifications, responses) = received
+        .into_iter()
+        .partition(|message| message["id"].is_null());
+
+    (notifications, responses)
+}
+
+fn document(extra: Value) -> Value {
+    let mut params = json!({ "textDocument": { "uri": URI } });
+    params
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    params
+}
+
+#[test]
+fn diagnostics() {
+    let (notifications, _) = session("+]\n[[-]", &[]);
+
+    let diagnostics = &notifications[0]["params"]["diagnostics"];
+    assert_eq!(
+        notifications[0]["method"],
+        "textDocument/publishDiagnostics"
+    );
+    assert_eq!(diagnostics.as_array().unwrap().len(), 2);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 0, "character": 1 })
+    );
+    assert_eq!(
+        diagnostics[1]["range"]["start"],
+        json!({ "line": 1, "character": 0 })
+    );
+    assert_eq!(diagnostics[1]["message"], "closure with no closing bracket");
+}
+
+#[test]
+fn brackets() {
+    let (_, responses) = session(
+        "+[\n  >[-]\n]",
+        &[
+            request(
+                1,
+                "textDocument/documentHighlight",
+                document(json!({ "position": { "line": 2, "character": 0 } })),
+            ),
+            request(2, "textDocument/foldingRange", document(json!({}))),
+        ],
+    );
+
+    let highlights = &responses[1]["result"];
+    assert_eq!(
+        highlights[0]["range"]["start"],
+        json!({ "line": 0, "character": 1 })
+    );
+    assert_eq!(
+        highlights[1]["range"]["start"],
+        json!({ "line": 2, "character": 0 })
+    );
+
+    assert_eq!(
+        responses[2]["result"],
+        json!([{ "startLine": 0, "endLine": 2 }])
+    );
+}
+
+#[test]
+fn formatting() {
+    let options = json!({ "options": { "tabSize": 2, "insertSpaces": true } });
+    let (_, responses) = session(
+        "+[>+[-]<-]",
+        &[request(1, "textDocument/formatting", document(options))],
+    );
+
+    let edits = &responses[1]["result"];
+    assert_eq!(edits[0]["newText"], "+\n[\n  >+[-]<-\n]\n");
+    assert_eq!(
+        edits[0]["range"]["end"],
+        json!({ "line": 0, "character": 10 })
+    );
+}
+
+#[test]
+fn changes() {
+    let range = |line, start, end| {
+        json!({
+            "start": { "line": line, "character": start },
+            "end": { "line": line, "character": end },
+        })
+    };
+    let change = |changes: Value| {
+        notification(
+            "textDocument/didChange",
+            document(json!({ "contentChanges": changes })),
+        )
+    };
+
+    let (notifications, responses) = session(
+        "+[\n  >+\n]",
+        &[
+            // Remove the closing bracket, then type it again on the same line
+            change(json!([{ "range": range(2, 0, 1), "text": "" }])),
+            change(json!([
+                { "range": range(1, 4, 4), "text": "<-" },
+                { "range": range(1, 6, 6), "text": "]" },
+            ])),
+            request(1, "textDocument/foldingRange", document(json!({}))),
+            // Replace the whole document
+            change(json!([{ "text": "[" }])),
+        ],
+    );
+
+    let diagnostics: Vec<_> = notifications
+        .iter()
+        .map(|notification| {
+            notification["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .len()
+        })
+        .collect();
+    assert_eq!(diagnostics, [0, 1, 0, 1]);
+    assert_eq!(
+        responses[1]["result"],
+        json!([{ "startLine": 0, "endLine": 1 }])
+    );
+}
+
+#[test]
+fn malformed_message() {
+    let messages = [
+        request(0, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        request(1, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ];
+    let mut bodies: Vec<_> = messages.iter().map(Value::to_string).collect();
+    bodies.insert(2, "{\"jsonrpc\": ".to_string());
+
+    // The server skips the bad message and still answers the next request
+    let received = protocol::raw_session("lsp", &bodies);
+    assert!(received.iter().any(|message| message["id"] == 1));
+}
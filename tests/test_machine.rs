@@ -0,0 +1,128 @@
+use std::io::Cursor;
+
+use brainfuck_interpreter::machine::{Comparison, Condition, Machine, Watch};
+use brainfuck_lexer::{lex, lex_unoptimized};
+
+#[test]
+fn hello_world() {
+    let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut machine = Machine::new(&bf.unwrap());
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+    let res = machine.run(&mut input, &mut buf);
+    assert!(res.is_ok());
+    assert!(machine.is_finished());
+
+    let str: String = buf.into_iter().map(|v| v as char).collect();
+    assert_eq!(str, "Hello World!\n".to_string());
+}
+
+#[test]
+fn step_back() {
+    let src = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut machine = Machine::with_history(&bf.unwrap(), 1024);
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+
+    for _ in 0..20 {
+        assert!(machine.step(&mut input, &mut buf).unwrap());
+    }
+    let snapshot = machine.clone();
+
+    for _ in 0..30 {
+        assert!(machine.step(&mut input, &mut buf).unwrap());
+    }
+    for _ in 0..30 {
+        assert!(machine.step_back());
+    }
+
+    assert_eq!(machine.pc(), snapshot.pc());
+    assert_eq!(machine.ptr(), snapshot.ptr());
+    assert_eq!(machine.memory(), snapshot.memory());
+}
+
+#[test]
+fn bounded_history() {
+    let src = "+>+>+>+".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut machine = Machine::with_history(&bf.unwrap(), 2);
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+
+    machine.run(&mut input, &mut buf).unwrap();
+    assert_eq!(machine.history_len(), 2);
+    assert!(machine.step_back());
+    assert!(machine.step_back());
+    assert!(!machine.step_back());
+    assert_eq!(machine.memory()[..4], [1, 1, 1, 0]);
+}
+
+#[test]
+fn conditions() {
+    let condition: Condition = "cell 5 == 42".parse().unwrap();
+    let expected = Condition {
+        watch: Watch::Cell(5),
+        comparison: Comparison::Equal,
+        value: 42,
+    };
+    assert_eq!(condition, expected);
+    assert_eq!(condition.to_string(), "cell 5 == 42");
+
+    let condition: Condition = "cell >= 2".parse().unwrap();
+    assert_eq!(condition.watch, Watch::CurrentCell);
+    let condition: Condition = "ptr != 0".parse().unwrap();
+    assert_eq!(condition.watch, Watch::Pointer);
+
+    for invalid in ["", "cell", "cell 5", "cell 5 = 42", "reg == 1", "ptr < 1 2"] {
+        assert!(invalid.parse::<Condition>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn run_until_condition() {
+    let src = "+++++[>++<-]>.".to_string();
+    let mut machine = Machine::new(&lex_unoptimized(src).unwrap());
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+
+    let condition: Condition = "cell 1 >= 6".parse().unwrap();
+    let res = machine.run_until(&mut input, &mut buf, |machine| condition.holds(machine));
+    assert_eq!(res.ok(), Some(true));
+    assert_eq!(machine.memory()[1], 6);
+    assert!(buf.is_empty());
+
+    let res = machine.run_until(&mut input, &mut buf, |_| false);
+    assert_eq!(res.ok(), Some(false));
+    assert_eq!(buf, [10]);
+}
+
+#[cfg(feature = "breakpoint_token")]
+#[test]
+fn breakpoints() {
+    let src = "+@+@+.".to_string();
+    let mut machine = Machine::new(&lex(src).unwrap());
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+
+    let mut stops = Vec::new();
+    while machine
+        .run_until(&mut input, &mut buf, Machine::at_breakpoint)
+        .unwrap()
+    {
+        stops.push(machine.memory()[0]);
+    }
+
+    assert_eq!(stops, [1, 2]);
+    assert_eq!(buf, [3]);
+}
@@ -0,0 +1,45 @@
+use std::io::Cursor;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{interpret, interpret_with_stats};
+use brainfuck_lexer::error::LexerError;
+use brainfuck_lexer::lexer::{drop_block, DEFAULT_MAX_DEPTH};
+use brainfuck_lexer::{lex, lex_with_config, LexerConfig};
+
+const DEPTH: usize = 100_000;
+
+fn nested(depth: usize) -> String {
+    format!("+{}-{}.", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn too_deep() {
+    let res = lex(nested(DEPTH));
+    assert_eq!(res, Err(LexerError::NestingTooDeep(DEFAULT_MAX_DEPTH)));
+
+    let err: BrainfuckError = res.unwrap_err().into();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "parser error: closures nested deeper than the limit of {}",
+            DEFAULT_MAX_DEPTH
+        )
+    );
+}
+
+#[test]
+fn deep_nesting() {
+    let config = LexerConfig { max_depth: DEPTH };
+    let block = lex_with_config(nested(DEPTH), &config).unwrap();
+
+    let mut out = Vec::new();
+    interpret(&block, &mut Cursor::new(vec![]), &mut out).unwrap();
+    assert_eq!(out, [0]);
+
+    let mut out = Vec::new();
+    let stats = interpret_with_stats(&block, &mut Cursor::new(vec![]), &mut out).unwrap();
+    assert_eq!(stats.loops_entered, DEPTH as u64);
+    assert_eq!(out, [0]);
+
+    drop_block(block);
+}
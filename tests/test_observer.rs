@@ -0,0 +1,128 @@
+use std::io::Cursor;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::flat::Instruction;
+use brainfuck_interpreter::interpreter::interpret_with_observer;
+use brainfuck_interpreter::io::StdIo;
+use brainfuck_interpreter::observer::{Context, IoEvent, Observer};
+use brainfuck_interpreter::tape::Tape;
+use brainfuck_lexer::lex_unoptimized;
+
+/// Records every event of a program.
+#[derive(Default)]
+struct Tracer {
+    before: Vec<(usize, isize)>,
+    after: Vec<(usize, isize, u8)>,
+    loops: Vec<usize>,
+    io: Vec<IoEvent>,
+}
+
+impl Observer for Tracer {
+    fn before_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.before.push((ctx.pc, ctx.ptr));
+        Ok(())
+    }
+
+    fn after_instruction<T>(&mut self, ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.after.push((ctx.pc, ctx.ptr, ctx.memory.cell(ctx.ptr)));
+        Ok(())
+    }
+
+    fn on_loop_enter<T>(&mut self, ctx: &Context<T>)
+    where
+        T: Tape + ?Sized,
+    {
+        assert!(matches!(ctx.instruction, Instruction::LoopStart(_)));
+        self.loops.push(ctx.pc);
+    }
+
+    fn on_io(&mut self, event: IoEvent) {
+        self.io.push(event);
+    }
+}
+
+/// Stops a program after a number of instructions.
+struct StepLimiter(u64);
+
+impl Observer for StepLimiter {
+    fn before_instruction<T>(&mut self, _ctx: &Context<T>) -> Result<(), BrainfuckError>
+    where
+        T: Tape + ?Sized,
+    {
+        self.0 = self.0.checked_sub(1).ok_or(BrainfuckError::Cancelled)?;
+        Ok(())
+    }
+}
+
+fn run<O>(src: &str, input: &[u8], observer: &mut O) -> Result<Vec<u8>, BrainfuckError>
+where
+    O: Observer,
+{
+    let mut io = StdIo::new(Cursor::new(input), Vec::new());
+    interpret_with_observer(&lex_unoptimized(src).unwrap(), &mut io, observer)?;
+
+    Ok(io.into_inner().1)
+}
+
+#[test]
+fn trace() {
+    let mut tracer = Tracer::default();
+    run("++[>+<-]", &[], &mut tracer).unwrap();
+
+    // Both brackets are observed, and the loop is entered only once
+    let pcs: Vec<_> = tracer.before.iter().map(|&(pc, _)| pc).collect();
+    assert_eq!(pcs, [0, 1, 2, 3, 4, 5, 6, 2, 3, 4, 5, 6]);
+    assert_eq!(tracer.before[3], (3, 1));
+    assert_eq!(tracer.after[1], (1, 0, 2));
+    assert_eq!(tracer.after.last(), Some(&(6, 0, 0)));
+    assert_eq!(tracer.loops, [1]);
+}
+
+#[test]
+fn io() {
+    let mut tracer = Tracer::default();
+    let output = run(",+.,.", b"a", &mut tracer).unwrap();
+
+    assert_eq!(output, b"b\0");
+    assert_eq!(
+        tracer.io,
+        [
+            IoEvent::Read(b'a'),
+            IoEvent::Write(b'b'),
+            IoEvent::Read(0),
+            IoEvent::Write(0)
+        ]
+    );
+}
+
+#[test]
+fn skipped_loops_are_not_entered() {
+    let mut tracer = Tracer::default();
+    run("[>]+[-]", &[], &mut tracer).unwrap();
+
+    // Skipping the first loop is a single instruction
+    assert_eq!(tracer.before.len(), 5);
+    assert_eq!(tracer.loops, [4]);
+}
+
+#[test]
+fn observer_stops_program() {
+    let res = run("+[.]", &[], &mut StepLimiter(10));
+    assert!(matches!(res, Err(BrainfuckError::Cancelled)));
+
+    assert_eq!(run("+.", &[], &mut StepLimiter(2)).unwrap(), [1]);
+}
+
+#[test]
+fn observers_are_borrowed() {
+    let mut tracer = Tracer::default();
+    run("+.", &[], &mut &mut tracer).unwrap();
+
+    assert_eq!(tracer.io, [IoEvent::Write(1)]);
+}
@@ -0,0 +1,108 @@
+use std::io::Cursor;
+use std::sync::atomic::AtomicBool;
+
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{
+    interpret_with_config, InterpreterConfig, OverflowBehavior,
+};
+use brainfuck_interpreter::io::{OutputMode, StdIo};
+use brainfuck_lexer::lex_unoptimized;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer, WRAPPING_PASSES};
+
+fn run(
+    src: &str,
+    optimizer: &Optimizer,
+    overflow: OverflowBehavior,
+) -> Result<Vec<u8>, BrainfuckError> {
+    let config = InterpreterConfig {
+        overflow,
+        ..Default::default()
+    };
+    let mut io = StdIo::new(Cursor::new(vec![]), Vec::new()).with_output_mode(OutputMode::Raw);
+    let code = optimizer.optimize(&lex_unoptimized(src)?);
+    interpret_with_config(&code, &mut io, &config, &AtomicBool::new(false))?;
+
+    Ok(io.into_inner().1)
+}
+
+/// Aggressive optimizer without the passes assuming wrapping arithmetic.
+fn non_wrapping() -> Optimizer {
+    WRAPPING_PASSES
+        .iter()
+        .fold(Optimizer::new(OptLevel::Aggressive), |optimizer, pass| {
+            optimizer.disable(pass)
+        })
+}
+
+#[test]
+fn wrap() {
+    let basic = Optimizer::new(OptLevel::Basic);
+
+    assert_eq!(run("-.", &basic, OverflowBehavior::Wrap).unwrap(), [255]);
+}
+
+#[test]
+fn saturate() {
+    let basic = Optimizer::new(OptLevel::Basic);
+
+    assert_eq!(run("--.", &basic, OverflowBehavior::Saturate).unwrap(), [0]);
+    assert_eq!(
+        run("-[+]+.", &basic, OverflowBehavior::Saturate).unwrap(),
+        [1]
+    );
+}
+
+#[test]
+fn saturate_optimized() {
+    let optimizer = non_wrapping();
+
+    // Multiplying by a negative factor stops at zero
+    let src = "+++[>-<-]>.";
+    assert_eq!(
+        run(src, &optimizer, OverflowBehavior::Saturate).unwrap(),
+        [0]
+    );
+
+    // Subtracting at an offset stops at zero
+    let src = ">-<+>.";
+    assert_eq!(
+        run(src, &optimizer, OverflowBehavior::Saturate).unwrap(),
+        [0]
+    );
+
+    // Repeated additions are not combined modulo 256
+    let src = "+".repeat(300) + ".";
+    assert_eq!(
+        run(&src, &optimizer, OverflowBehavior::Saturate).unwrap(),
+        [255]
+    );
+}
+
+#[test]
+fn large_factors() {
+    let optimizer = non_wrapping();
+    let plus = "+".repeat(200);
+
+    // Factors and values above 127 are not taken as negative
+    for src in [format!("+[->{}<]>.", plus), format!(">{}<+>.", plus)] {
+        for overflow in [OverflowBehavior::Saturate, OverflowBehavior::Error] {
+            assert_eq!(run(&src, &optimizer, overflow).unwrap(), [200], "{}", src);
+        }
+    }
+}
+
+#[test]
+fn error() {
+    let optimizer = non_wrapping();
+
+    let res = run("+.--", &optimizer, OverflowBehavior::Error);
+    assert!(matches!(res, Err(BrainfuckError::CellOverflow(0))));
+
+    let res = run("+++[>>-<<-]", &optimizer, OverflowBehavior::Error);
+    assert!(matches!(res, Err(BrainfuckError::CellOverflow(2))));
+
+    assert_eq!(
+        run("+++[>>+<<-]>>.", &optimizer, OverflowBehavior::Error).unwrap(),
+        [3]
+    );
+}
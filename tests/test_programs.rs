@@ -1,7 +1,14 @@
+mod programs;
+
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
-use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_interpreter::error::BrainfuckError;
+use brainfuck_interpreter::interpreter::{interpret, interpret_with_cancel};
 use brainfuck_lexer::lex;
+use programs::FIXTURES;
 
 #[test]
 fn hello_world() {
@@ -66,3 +73,60 @@ fn god_morgen() {
     let str: String = buf.into_iter().map(|v| v as char).collect();
     assert_eq!(str, "God Morgen!".to_string());
 }
+
+#[test]
+fn cancel_runaway_program() {
+    let src = "+[>+<]".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let cancel = Arc::clone(&cancel);
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let mut input = Cursor::new(vec![]);
+            interpret_with_cancel(&bf.unwrap(), &mut input, &mut buf, &cancel)
+        })
+    };
+
+    std::thread::sleep(Duration::from_millis(50));
+    cancel.store(true, Ordering::Relaxed);
+
+    let res = handle.join().unwrap();
+    assert!(matches!(res, Err(BrainfuckError::Cancelled)));
+}
+
+#[test]
+fn fixtures() {
+    for fixture in FIXTURES {
+        let bf = lex(fixture.src);
+
+        assert!(bf.is_ok(), "{}", fixture.name);
+
+        let mut buf = Vec::new();
+        let mut input = Cursor::new(fixture.input);
+        let res = interpret(&bf.unwrap(), &mut input, &mut buf);
+        assert!(res.is_ok(), "{}", fixture.name);
+
+        let str: String = buf.into_iter().map(|v| v as char).collect();
+        assert_eq!(str, fixture.output, "{}", fixture.name);
+    }
+}
+
+#[test]
+fn wrap_around() {
+    let src = "<+++++[>++++++++++<-]>---.".to_string();
+    let bf = lex(src);
+
+    assert!(bf.is_ok());
+
+    let mut buf = Vec::new();
+    let mut input = Cursor::new(vec![]);
+    let res = interpret(&bf.unwrap(), &mut input, &mut buf);
+    assert!(res.is_ok());
+
+    let str: String = buf.into_iter().map(|v| v as char).collect();
+    assert_eq!(str, "/".to_string());
+}
@@ -0,0 +1,86 @@
+mod programs;
+mod reference;
+
+use std::io::Cursor;
+
+use brainfuck_interpreter::interpreter::interpret;
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized, to_source};
+use programs::FIXTURES;
+
+const MAX_STEPS: usize = 100_000_000;
+
+fn assert_same_output(src: &str, input: &[u8]) {
+    let expected = reference::run(src, input, MAX_STEPS);
+
+    assert!(expected.is_some(), "{}", src);
+
+    let bf = lex(src);
+
+    assert!(bf.is_ok(), "{}", src);
+
+    let mut buf = Vec::new();
+    let res = interpret(&bf.unwrap(), &mut Cursor::new(input), &mut buf);
+    assert!(res.is_ok(), "{}", src);
+
+    assert_eq!(buf, expected.unwrap(), "{}", src);
+
+    let bf = lex_unoptimized(src);
+
+    assert!(bf.is_ok(), "{}", src);
+
+    let mut unoptimized_buf = Vec::new();
+    let res = interpret(&bf.unwrap(), &mut Cursor::new(input), &mut unoptimized_buf);
+    assert!(res.is_ok(), "{}", src);
+
+    assert_eq!(unoptimized_buf, buf, "{}", src);
+
+    let bf = Optimizer::new(OptLevel::Aggressive).optimize(&lex_unoptimized(src).unwrap());
+
+    let mut aggressive_buf = Vec::new();
+    let res = interpret(&bf, &mut Cursor::new(input), &mut aggressive_buf);
+    assert!(res.is_ok(), "{}", src);
+
+    assert_eq!(aggressive_buf, buf, "{}", src);
+
+    let regenerated = reference::run(&to_source(&bf), input, MAX_STEPS);
+    assert_eq!(regenerated, Some(buf), "{}", src);
+}
+
+#[test]
+fn fixtures() {
+    for fixture in FIXTURES {
+        let expected = reference::run(fixture.src, fixture.input, MAX_STEPS);
+        assert_eq!(
+            expected.as_deref(),
+            Some(fixture.output.as_bytes()),
+            "{}",
+            fixture.name
+        );
+
+        assert_same_output(fixture.src, fixture.input);
+    }
+}
+
+#[test]
+fn patterns() {
+    assert_same_output("+++++[-].", &[]);
+    assert_same_output("+++++[->+++<]>.", &[]);
+    assert_same_output(">+++++[-<+++>]<.", &[]);
+    assert_same_output("+++++[>++<-]>.", &[]);
+    assert_same_output(">+++++[<++>-]<.", &[]);
+}
+
+#[test]
+fn offsets() {
+    assert_same_output("+>++>+++<<[>.<-]>>.<<+-++--", &[]);
+    assert_same_output(">>+<<<-<+>>>.[-<+>]<.", &[]);
+    assert_same_output(",>,<[>>+<+<-]>>.<.", &[3, 5]);
+}
+
+#[test]
+fn wrap_around() {
+    assert_same_output("<+>.<.", &[]);
+    assert_same_output("<+++[->>+<<]>>.", &[]);
+    assert_same_output("-.+++[-<->]<.", &[]);
+}
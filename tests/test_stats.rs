@@ -0,0 +1,86 @@
+mod programs;
+
+use std::io::Cursor;
+
+use brainfuck_interpreter::interpreter::{interpret_with_heatmap, interpret_with_stats};
+use brainfuck_interpreter::stats::ExecutionStats;
+use brainfuck_lexer::{lex, lex_unoptimized};
+use programs::FIXTURES;
+
+#[test]
+fn counters() {
+    let src = "+[-]>,.".to_string();
+    let mut buf = Vec::new();
+    let stats = interpret_with_stats(
+        &lex_unoptimized(src).unwrap(),
+        &mut Cursor::new("a"),
+        &mut buf,
+    );
+
+    let expected = ExecutionStats {
+        instructions_executed: 7,
+        loops_entered: 1,
+        max_pointer: 1,
+        cells_touched: 2,
+        bytes_read: 1,
+        bytes_written: 1,
+    };
+    assert_eq!(stats.unwrap(), expected);
+    assert_eq!(buf, b"a");
+}
+
+#[test]
+fn io_bytes() {
+    // Reading past the end of the input reads nothing, and non-ASCII values
+    // are written as two bytes
+    let src = ",-.".to_string();
+    let stats = interpret_with_stats(&lex(src).unwrap(), &mut Cursor::new(""), &mut Vec::new());
+
+    let stats = stats.unwrap();
+    assert_eq!(stats.bytes_read, 0);
+    assert_eq!(stats.bytes_written, 2);
+}
+
+#[test]
+fn optimized_fixtures() {
+    for fixture in FIXTURES {
+        let run = |code| {
+            let mut buf = Vec::new();
+            let stats = interpret_with_stats(&code, &mut Cursor::new(fixture.input), &mut buf);
+            assert_eq!(buf, fixture.output.as_bytes(), "{}", fixture.name);
+            stats.unwrap()
+        };
+
+        let unoptimized = run(lex_unoptimized(fixture.src).unwrap());
+        let optimized = run(lex(fixture.src).unwrap());
+
+        assert!(
+            optimized.instructions_executed <= unoptimized.instructions_executed,
+            "{}",
+            fixture.name
+        );
+        assert_eq!(optimized.bytes_written, unoptimized.bytes_written);
+        assert_eq!(optimized.max_pointer, unoptimized.max_pointer);
+    }
+}
+
+#[test]
+fn heatmap() {
+    let src = "+[->++<]>.".to_string();
+    let (stats, heatmap) = interpret_with_heatmap(
+        &lex_unoptimized(src).unwrap(),
+        &mut Cursor::new(""),
+        &mut Vec::new(),
+    )
+    .unwrap();
+
+    let used: Vec<_> = heatmap.used_cells().collect();
+    assert_eq!(used, vec![(0, 4, 2), (1, 2, 1)]);
+    assert_eq!(stats.cells_touched, used.len());
+
+    assert_eq!(heatmap.to_csv(), "cell,reads,writes\n0,4,2\n1,2,1\n");
+    assert_eq!(
+        heatmap.to_json(),
+        "[{\"cell\":0,\"reads\":4,\"writes\":2},{\"cell\":1,\"reads\":2,\"writes\":1}]\n"
+    );
+}
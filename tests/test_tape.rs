@@ -0,0 +1,155 @@
+mod programs;
+
+use std::io::Cursor;
+use std::sync::atomic::AtomicBool;
+
+use brainfuck_interpreter::flat::flatten;
+use brainfuck_interpreter::interpreter::{
+    interpret_in_place, interpret_with_config, InterpreterConfig,
+};
+use brainfuck_interpreter::io::StdIo;
+use brainfuck_interpreter::tape::{BidirectionalTape, SparseTape, Tape, TapeKind};
+use brainfuck_lexer::optimizer::{OptLevel, Optimizer};
+use brainfuck_lexer::{lex, lex_unoptimized};
+use programs::FIXTURES;
+
+fn run(src: &str, input: &[u8], tape: TapeKind) -> Vec<u8> {
+    let config = InterpreterConfig {
+        tape,
+        ..Default::default()
+    };
+    let mut io = StdIo::new(Cursor::new(input), Vec::new());
+    interpret_with_config(
+        &lex(src).unwrap(),
+        &mut io,
+        &config,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
+
+    io.into_inner().1
+}
+
+#[test]
+fn wrapping_slice() {
+    let tape: &mut [u8] = &mut [0; 10];
+    *tape.cell_mut(9) = 1;
+
+    assert_eq!(tape.offset(0, -1), 9);
+    assert_eq!(tape.offset(9, 1), 0);
+    assert_eq!(tape.offset(5, 23), 8);
+    assert_eq!(tape.cell(tape.offset(0, -1)), 1);
+}
+
+#[test]
+fn bidirectional() {
+    let mut tape = BidirectionalTape::default();
+    *tape.cell_mut(-1) = 1;
+    *tape.cell_mut(2) = 2;
+
+    assert_eq!(tape.cell(-1), 1);
+    assert_eq!(tape.cell(-2), 0);
+    assert_eq!(tape.cell(0), 0);
+    assert_eq!(tape.cell(2), 2);
+    assert_eq!(tape.offset(0, -30_001), -30_001);
+}
+
+#[test]
+fn sparse() {
+    let mut tape = SparseTape::default();
+    assert_eq!(tape.cell(-1), 0);
+    assert_eq!(tape.pages(), 0);
+
+    *tape.cell_mut(-1) = 1;
+    *tape.cell_mut(0) = 2;
+    *tape.cell_mut(1023) = 3;
+    *tape.cell_mut(isize::MAX) = 4;
+
+    assert_eq!(tape.cell(-1), 1);
+    assert_eq!(tape.cell(0), 2);
+    assert_eq!(tape.cell(1023), 3);
+    assert_eq!(tape.cell(isize::MAX), 4);
+    assert_eq!(tape.pages(), 3);
+    assert_eq!(tape.offset(isize::MAX, 1), isize::MIN);
+}
+
+#[test]
+fn distant_cells() {
+    // Add to a cell from a cell a million locations away
+    let far = ">".repeat(1_000_000);
+    let near = "<".repeat(1_000_000);
+    let src = format!("+++++++[>++++++++++<-]>-{far}+++++++++[-{near}+{far}]{near}.");
+
+    assert_eq!(run(&src, &[], TapeKind::Sparse), b"N");
+}
+
+#[test]
+fn left_of_start() {
+    // Store a value left of the first cell, and read it back after moving
+    // far to the right
+    let src = "<<+++++++[>++++++++++<-]>[<+>-]<->>>>>>>>>>[-]<<<<<<<<<<.";
+    assert_eq!(run(src, &[], TapeKind::Bidirectional), b"E");
+    assert_eq!(run(src, &[], TapeKind::Sparse), b"E");
+
+    // Moving left of the first cell of a wrapping tape moves to the last one
+    let src = "<+[>+<-]>.<<[-]>+.";
+    assert_eq!(run(src, &[], TapeKind::Wrapping), b"\x01\x01");
+}
+
+#[test]
+fn optimized_around_tape() {
+    let optimizer = Optimizer::new(OptLevel::Aggressive);
+    let run = |src: &str| {
+        let mut io = StdIo::new(Cursor::new(b""), Vec::new());
+        let code = optimizer.optimize(&lex_unoptimized(src).unwrap());
+        interpret_with_config(
+            &code,
+            &mut io,
+            &InterpreterConfig::default(),
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        io.into_inner().1
+    };
+
+    // Moving left from the first cell reaches it again after 30000 cells
+    let src = format!("+{}[.-]", "<".repeat(30_000));
+    assert_eq!(run(&src), b"\x01");
+
+    let src = format!("+<{}+>{}.", "<".repeat(29_999), ">".repeat(29_999));
+    assert_eq!(run(&src), b"\x02");
+}
+
+#[test]
+fn optimized_for_short_tape() {
+    let src = format!("+{}[.[-]]", ">".repeat(16));
+    let optimizer = Optimizer::new(OptLevel::Aggressive).with_tape_len(16);
+    let program = flatten(&optimizer.optimize(&lex_unoptimized(src).unwrap()));
+
+    // Moving 16 cells right wraps around to the first cell
+    let mut memory = [0u8; 16];
+    let mut io = StdIo::new(Cursor::new(b""), Vec::new());
+    interpret_in_place(&program, &mut memory, &mut io).unwrap();
+    assert_eq!(io.into_inner().1, [1]);
+}
+
+#[test]
+fn fixtures() {
+    for fixture in FIXTURES {
+        for tape in [
+            TapeKind::Wrapping,
+            TapeKind::Bidirectional,
+            TapeKind::Sparse,
+        ] {
+            let output = run(fixture.src, fixture.input, tape);
+
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                fixture.output,
+                "{} on {:?}",
+                fixture.name,
+                tape
+            );
+        }
+    }
+}